@@ -0,0 +1,236 @@
+// Збірка проекту з інкрементальним кешем у `ціль/`.
+// Кожен `.тризуб` проекту розбирається у `ціль/<шлях>.тризубс`; `ціль/кеш.json`
+// зберігає мапу хеш→артефакт. Хеш покриває вміст файлу, версію тризуба й хеші
+// модулів, які файл імпортує, тож зміна залежності перезбирає і її імпортерів.
+// Хеш — FNV-1a над байтами, тож ключі кешу однакові між версіями Rust.
+
+use anyhow::Result;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tryzub_parser::{Declaration, Program, Statement};
+
+/// Тека артефактів збірки відносно кореня проекту
+pub const TARGET_DIR: &str = "ціль";
+
+const CACHE_FILE: &str = "кеш.json";
+
+/// Запис кешу: артефакт і вміст, з якого його зібрано
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+struct CacheEntry {
+    /// Шлях артефакту відносно `ціль/`
+    артефакт: String,
+    /// Хеш лише вмісту файлу, щоб знати імпорти без повторного розбору
+    вміст: String,
+    імпорти: Vec<String>,
+}
+
+/// Підсумок збірки: відносні шляхи перезібраних і пропущених файлів
+pub struct BuildSummary {
+    pub built: Vec<PathBuf>,
+    pub cached: Vec<PathBuf>,
+}
+
+/// Збирає всі файли проекту з `проект.toml` у теці `dir`
+pub fn build_project(dir: &Path) -> Result<BuildSummary> {
+    let manifest = dir.join("проект.toml");
+    if !manifest.is_file() {
+        return Err(anyhow::anyhow!("Не знайдено проект.toml у {:?}", dir));
+    }
+    let target = dir.join(TARGET_DIR);
+    fs::create_dir_all(&target)
+        .map_err(|e| anyhow::anyhow!("Не вдалося створити {:?}: {}", target, e))?;
+
+    let cache_path = target.join(CACHE_FILE);
+    let cache: BTreeMap<String, CacheEntry> = fs::read_to_string(&cache_path).ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default();
+
+    let mut search_dirs = vec![dir.to_path_buf()];
+    search_dirs.extend(crate::read_local_dependencies(&manifest)?.into_iter().map(|(_, path)| path));
+
+    let mut sources = Vec::new();
+    collect_sources(dir, &target, &mut sources)?;
+    sources.sort();
+
+    let mut build = Build { cache, search_dirs, files: HashMap::new(), parsed: HashMap::new(), visiting: HashSet::new() };
+    let mut summary = BuildSummary { built: Vec::new(), cached: Vec::new() };
+    let mut fresh = BTreeMap::new();
+    for source in &sources {
+        let relative = source.strip_prefix(dir).unwrap_or(source).to_path_buf();
+        let artifact = relative.with_extension(tryzub_parser::binary::EXTENSION);
+        let artifact_name = artifact.to_string_lossy().replace('\\', "/");
+        let key = build.key(source)?;
+
+        let up_to_date = build.cache.get(&key).is_some_and(|entry| entry.артефакт == artifact_name)
+            && target.join(&artifact).is_file();
+        if up_to_date {
+            summary.cached.push(relative);
+        } else {
+            let program = match build.parsed.remove(source) {
+                Some(program) => program,
+                None => crate::load_program(source)?,
+            };
+            let out = target.join(&artifact);
+            if let Some(parent) = out.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&out, tryzub_parser::binary::serialize(&program)?)
+                .map_err(|e| anyhow::anyhow!("Не вдалося записати {:?}: {}", out, e))?;
+            summary.built.push(relative);
+        }
+        // Старі ключі того ж артефакту більше не дійсні, тож у кеш потрапляють лише поточні
+        let file = &build.files[source];
+        fresh.insert(key, CacheEntry { артефакт: artifact_name, вміст: file.content.clone(), імпорти: file.imports.clone() });
+    }
+
+    fs::write(&cache_path, serde_json::to_string_pretty(&fresh)?)
+        .map_err(|e| anyhow::anyhow!("Не вдалося записати {:?}: {}", cache_path, e))?;
+    Ok(summary)
+}
+
+struct Build {
+    cache: BTreeMap<String, CacheEntry>,
+    /// Теки, де шукаються імпортовані модулі: корінь проекту й локальні залежності
+    search_dirs: Vec<PathBuf>,
+    files: HashMap<PathBuf, SourceFile>,
+    /// Програми, розібрані заради імпортів; збірка бере їх замість повторного розбору
+    parsed: HashMap<PathBuf, Program>,
+    visiting: HashSet<PathBuf>,
+}
+
+struct SourceFile {
+    key: String,
+    content: String,
+    imports: Vec<String>,
+}
+
+impl Build {
+    /// Ключ кешу файлу з урахуванням ключів модулів, які він імпортує
+    fn key(&mut self, file: &Path) -> Result<String> {
+        if let Some(known) = self.files.get(file) {
+            return Ok(known.key.clone());
+        }
+        let bytes = fs::read(file)
+            .map_err(|e| anyhow::anyhow!("Не вдалося прочитати файл {:?}: {}", file, e))?;
+        let content = content_hash(&bytes);
+        let cached = self.cache.values().find(|entry| entry.вміст == content).map(|entry| entry.імпорти.clone());
+        let imports = match cached {
+            Some(imports) => imports,
+            None => {
+                let program = crate::load_program(file)?;
+                let names = imported_modules(&program.declarations);
+                self.parsed.insert(file.to_path_buf(), program);
+                names
+            }
+        };
+
+        // Циклічний імпорт не додає ключа: цикл однаково зупинить VM при запуску
+        self.visiting.insert(file.to_path_buf());
+        let mut hasher = Fnv::new();
+        hasher.write(env!("CARGO_PKG_VERSION").as_bytes());
+        hasher.write(&[0]);
+        hasher.write(content.as_bytes());
+        for module in &imports {
+            if let Some(dependency) = self.resolve(file, module) {
+                if !self.visiting.contains(&dependency) {
+                    hasher.write(self.key(&dependency)?.as_bytes());
+                }
+            }
+        }
+        self.visiting.remove(file);
+
+        let key = format!("{:016x}", hasher.finish());
+        self.files.insert(file.to_path_buf(), SourceFile { key: key.clone(), content, imports });
+        Ok(key)
+    }
+
+    /// Файл модуля `name` так, як його знайде VM: поруч з імпортером або в теках пошуку
+    fn resolve(&self, importer: &Path, name: &str) -> Option<PathBuf> {
+        let near = importer.parent().map(Path::to_path_buf);
+        near.iter().chain(self.search_dirs.iter())
+            .flat_map(|dir| [
+                dir.join(format!("{}.тризуб", name)),
+                dir.join(format!("{}.tryzub", name)),
+                dir.join(name).join(format!("{}.тризуб", name)),
+            ])
+            .find(|path| path.is_file())
+    }
+}
+
+/// 64-бітний FNV-1a: на відміну від `DefaultHasher`, результат не залежить від версії Rust
+struct Fnv(u64);
+
+impl Fnv {
+    fn new() -> Self {
+        Fnv(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+fn content_hash(bytes: &[u8]) -> String {
+    let mut hasher = Fnv::new();
+    hasher.write(bytes);
+    format!("{:016x}", hasher.finish())
+}
+
+/// Імена модулів з `імпорт` на верхньому рівні, у модулях і тілах функцій
+fn imported_modules(declarations: &[Declaration]) -> Vec<String> {
+    let mut names = Vec::new();
+    for decl in declarations {
+        match decl {
+            Declaration::Import { path, .. } => names.extend(path.last().cloned()),
+            Declaration::Module { declarations, .. } => names.extend(imported_modules(declarations)),
+            Declaration::Function { body, .. } => {
+                let nested: Vec<Declaration> = body.iter()
+                    .filter_map(|stmt| match stmt {
+                        Statement::Declaration(decl) => Some(decl.clone()),
+                        _ => None,
+                    })
+                    .collect();
+                names.extend(imported_modules(&nested));
+            }
+            _ => {}
+        }
+    }
+    names
+}
+
+/// Файли `.тризуб` проекту без теки артефактів і прихованих тек
+fn collect_sources(dir: &Path, target: &Path, out: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let hidden = path.file_name().is_some_and(|name| name.to_string_lossy().starts_with('.'));
+        if path == target || hidden {
+            continue;
+        }
+        if path.is_dir() {
+            collect_sources(&path, target, out)?;
+        } else if path.extension().is_some_and(|e| e == "тризуб" || e == "tryzub") {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_hash_is_stable() {
+        // Еталонні значення FNV-1a: ключі кешу мають збігатися між збірками тризуба
+        assert_eq!(content_hash(b""), "cbf29ce484222325");
+        assert_eq!(content_hash(b"a"), "af63dc4c8601ec8c");
+    }
+}
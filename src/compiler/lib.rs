@@ -1,26 +1,76 @@
 use anyhow::Result;
 use inkwell::builder::Builder;
 use inkwell::context::Context;
-use inkwell::module::Module;
+use inkwell::debug_info::{
+    AsDIScope, DICompileUnit, DIFlags, DIFlagsConstants, DIScope, DWARFEmissionKind,
+    DWARFSourceLanguage, DebugInfoBuilder,
+};
+use inkwell::module::{FlagBehavior, Linkage, Module};
 use inkwell::passes::PassManager;
 use inkwell::targets::{CodeModel, FileType, InitializationConfig, RelocMode, Target, TargetMachine};
 use inkwell::types::{BasicMetadataTypeEnum, BasicType, BasicTypeEnum, FunctionType};
-use inkwell::values::{BasicMetadataValueEnum, BasicValue, BasicValueEnum, FunctionValue, PointerValue};
+use inkwell::values::{BasicMetadataValueEnum, BasicValue, BasicValueEnum, FunctionValue, GlobalValue, PointerValue};
 use inkwell::{AddressSpace, OptimizationLevel};
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use tryzub_parser::{
     Program, Declaration, Statement, Expression, Literal, BinaryOp, UnaryOp,
-    Type, Parameter, Visibility, AssignmentOp,
+    Type, Parameter, Visibility, AssignmentOp, Span,
 };
+use tryzub_parser::semantic::{infer_types, type_name, InferredTypes};
 
 pub struct Compiler<'ctx> {
     context: &'ctx Context,
     builder: Builder<'ctx>,
     module: Module<'ctx>,
     functions: HashMap<String, FunctionValue<'ctx>>,
-    variables: HashMap<String, PointerValue<'ctx>>,
+    /// Стек областей видимості: вкладені блоки та цикли можуть перекривати імена
+    variables: Vec<HashMap<String, PointerValue<'ctx>>>,
+    /// Імена сталих для кожної області з `variables`
+    constants: Vec<HashSet<String>>,
+    /// Глобальні `змінна`/`стала` рівня модуля; локальні імена їх перекривають
+    globals: HashMap<String, PointerValue<'ctx>>,
+    global_constants: HashSet<String>,
     current_function: Option<FunctionValue<'ctx>>,
+    /// Типи неанотованих змінних, виведені семантичним аналізом
+    inferred_types: InferredTypes,
+    /// Стан генерації DWARF; `None`, якщо налагоджувальна інформація вимкнена
+    debug_info: Option<DebugInfo<'ctx>>,
+    /// Проміжні рядки з `tryzub_string_concat`, які ще ніхто не використав
+    temp_strings: HashSet<PointerValue<'ctx>>,
+    /// Рядкові константи модуля за вмістом, щоб однакові літерали й шаблони
+    /// друку не дублювались
+    string_constants: HashMap<String, GlobalValue<'ctx>>,
+    /// Generic-функції; LLVM-код для них генерується лише під конкретний тип
+    generic_functions: HashMap<String, GenericFunction>,
+    /// Збірка бібліотеки: без обгортки `main`, приватні функції не експортуються
+    library: bool,
+}
+
+/// Вид бібліотеки для `generate_library`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LibraryKind {
+    /// `.a` (`.lib` на Windows) через архіватор
+    Static,
+    /// `.so` / `.dylib` / `.dll` через `clang -shared`
+    Shared,
+}
+
+/// Шаблон generic-функції з одним параметром типу
+#[derive(Clone)]
+struct GenericFunction {
+    type_param: String,
+    params: Vec<Parameter>,
+    return_type: Option<Type>,
+    body: Vec<Statement>,
+    span: Span,
+}
+
+struct DebugInfo<'ctx> {
+    builder: DebugInfoBuilder<'ctx>,
+    compile_unit: DICompileUnit<'ctx>,
+    /// Підпрограма функції, що компілюється зараз
+    scope: Option<DIScope<'ctx>>,
 }
 
 impl<'ctx> Compiler<'ctx> {
@@ -33,26 +83,144 @@ impl<'ctx> Compiler<'ctx> {
             builder,
             module,
             functions: HashMap::new(),
-            variables: HashMap::new(),
+            variables: vec![HashMap::new()],
+            constants: vec![HashSet::new()],
+            globals: HashMap::new(),
+            global_constants: HashSet::new(),
             current_function: None,
+            inferred_types: InferredTypes::default(),
+            debug_info: None,
+            temp_strings: HashSet::new(),
+            string_constants: HashMap::new(),
+            generic_functions: HashMap::new(),
+            library: false,
+        }
+    }
+    
+    /// Компілювати як бібліотеку: `головна` не обов'язкова, код позиційно-незалежний
+    pub fn enable_library_mode(&mut self) {
+        self.library = true;
+    }
+    
+    /// Вмикає емісію DWARF для вихідного файлу `source_path`
+    pub fn enable_debug_info(&mut self, source_path: &Path) {
+        let debug_version = self.context.i32_type().const_int(3, false);
+        self.module.add_basic_value_flag("Debug Info Version", FlagBehavior::Warning, debug_version);
+        
+        let filename = source_path.file_name()
+            .map(|f| f.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let directory = source_path.parent()
+            .map(|d| d.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        
+        let (builder, compile_unit) = self.module.create_debug_info_builder(
+            true,
+            DWARFSourceLanguage::C,
+            &filename,
+            &directory,
+            "tryzub",
+            false,
+            "",
+            0,
+            "",
+            DWARFEmissionKind::Full,
+            0,
+            false,
+            false,
+            "",
+            "",
+        );
+        
+        self.debug_info = Some(DebugInfo { builder, compile_unit, scope: None });
+    }
+    
+    /// Створює DWARF-підпрограму для функції та робить її поточною областю
+    fn debug_function(&mut self, function: FunctionValue<'ctx>, name: &str, span: Span) {
+        if let Some(debug) = self.debug_info.as_mut() {
+            let file = debug.compile_unit.get_file();
+            let subroutine_type = debug.builder.create_subroutine_type(file, None, &[], DIFlags::PUBLIC);
+            let subprogram = debug.builder.create_function(
+                debug.compile_unit.as_debug_info_scope(),
+                name,
+                None,
+                file,
+                span.line as u32,
+                subroutine_type,
+                true,
+                true,
+                span.line as u32,
+                DIFlags::PUBLIC,
+                false,
+            );
+            function.set_subprogram(subprogram);
+            debug.scope = Some(subprogram.as_debug_info_scope());
+        }
+    }
+    
+    /// Прив'язує наступні інструкції до позиції у вихідному файлі
+    fn set_debug_location(&self, span: Span) {
+        if let Some(debug) = &self.debug_info {
+            if let Some(scope) = debug.scope {
+                let location = debug.builder.create_debug_location(
+                    self.context,
+                    span.line as u32,
+                    span.column as u32,
+                    scope,
+                    None,
+                );
+                self.builder.set_current_debug_location(location);
+            }
         }
     }
     
     pub fn compile(&mut self, program: Program) -> Result<()> {
-        // Спочатку декларуємо всі функції
+        let program = program.configured();
+        self.inferred_types = infer_types(&program);
+
+        // Спочатку декларуємо всі функції та глобальні змінні; generic лише
+        // запам'ятовуємо до першого виклику
         for decl in &program.declarations {
-            if let Declaration::Function { name, params, return_type, .. } = decl {
-                self.declare_function(name, params, return_type)?;
+            if let Declaration::Variable { name, ty, value, is_mutable, span } = decl {
+                self.compile_global_variable(name, ty, value, *is_mutable, *span)?;
+                continue;
+            }
+            if let Declaration::Function { name, generic_params, params, return_type, body, span, visibility, .. } = decl {
+                if name == "головна" && !self.library {
+                    Self::check_main_signature(generic_params, params, return_type, *span)?;
+                }
+                if generic_params.is_empty() {
+                    self.declare_function(name, params, return_type)?;
+                    if self.library && *visibility == Visibility::Private {
+                        self.functions[name].set_linkage(Linkage::Internal);
+                    }
+                    continue;
+                }
+                let [type_param] = generic_params.as_slice() else {
+                    return Err(anyhow::anyhow!(
+                        "Функція '{}': компілятор поки підтримує лише один параметр типу", name
+                    ));
+                };
+                self.generic_functions.insert(name.clone(), GenericFunction {
+                    type_param: type_param.clone(),
+                    params: params.clone(),
+                    return_type: return_type.clone(),
+                    body: body.clone(),
+                    span: *span,
+                });
             }
         }
         
         // Потім компілюємо їх тіла
         for decl in program.declarations {
+            if matches!(&decl, Declaration::Function { generic_params, .. } if !generic_params.is_empty()) {
+                continue;
+            }
             self.compile_declaration(decl)?;
         }
         
         // Додаємо точку входу якщо є функція "головна"
-        if self.functions.contains_key("головна") {
+        if !self.library && self.functions.contains_key("головна") {
             self.create_main_wrapper()?;
         }
         
@@ -84,68 +252,48 @@ impl<'ctx> Compiler<'ctx> {
         
         let entry = self.context.append_basic_block(main_fn, "entry");
         self.builder.position_at_end(entry);
+        // Обгортка не має відповідника у вихідному коді
+        self.builder.unset_current_debug_location();
         
-        // Викликаємо функцію "головна"
+        // Викликаємо функцію "головна"; її цл32 стає кодом виходу, інакше 0
         let головна = self.functions.get("головна").unwrap();
-        self.builder.build_call(*головна, &[], "call");
-        
-        // Повертаємо 0
-        let zero = i32_type.const_int(0, false);
-        self.builder.build_return(Some(&zero));
+        let code = self.builder.build_call(*головна, &[], "call")
+            .try_as_basic_value()
+            .left()
+            .map(|value| value.into_int_value())
+            .unwrap_or_else(|| i32_type.const_int(0, false));
+        self.builder.build_return(Some(&code));
         
         Ok(())
     }
     
+    /// Обгортка `main` викликає `головна` без аргументів і повертає її результат
+    /// як код виходу, тож допустимі лише `головна()` і `головна() -> цл32`
+    fn check_main_signature(generic_params: &[String], params: &[Parameter], return_type: &Option<Type>, span: Span) -> Result<()> {
+        if !generic_params.is_empty() || !params.is_empty() {
+            return Err(anyhow::anyhow!(
+                "Функція 'головна' на рядку {} не може мати параметрів: точка входу викликається без аргументів",
+                span.line
+            ));
+        }
+        match return_type {
+            None | Some(Type::Цл32) => Ok(()),
+            Some(ty) => Err(anyhow::anyhow!(
+                "Функція 'головна' на рядку {} має нічого не повертати або повертати цл32, а не '{}'",
+                span.line, type_name(ty)
+            )),
+        }
+    }
+    
     fn compile_declaration(&mut self, decl: Declaration) -> Result<()> {
         match decl {
-            Declaration::Variable { name, ty, value, is_mutable } => {
-                let llvm_type = if let Some(ref t) = ty {
-                    self.get_llvm_type(t)
-                } else if let Some(ref val) = value {
-                    self.infer_type_from_expression(val)
-                } else {
-                    return Err(anyhow::anyhow!("Не можу вивести тип змінної {}", name));
-                };
-                
-                let alloca = self.builder.build_alloca(llvm_type, &name);
-                
-                if let Some(init_value) = value {
-                    let value = self.compile_expression(init_value)?;
-                    self.builder.build_store(alloca, value);
-                }
-                
-                self.variables.insert(name, alloca);
+            Declaration::Variable { .. } => {
+                // Глобальні змінні вже створені під час декларування
             }
             
-            Declaration::Function { name, params, return_type, body, .. } => {
+            Declaration::Function { name, params, return_type, body, span, .. } => {
                 let function = *self.functions.get(&name).unwrap();
-                self.current_function = Some(function);
-                
-                let entry = self.context.append_basic_block(function, "entry");
-                self.builder.position_at_end(entry);
-                
-                // Створюємо змінні для параметрів
-                self.variables.clear();
-                for (i, param) in params.iter().enumerate() {
-                    let arg = function.get_nth_param(i as u32).unwrap();
-                    let alloca = self.builder.build_alloca(arg.get_type(), &param.name);
-                    self.builder.build_store(alloca, arg);
-                    self.variables.insert(param.name.clone(), alloca);
-                }
-                
-                // Компілюємо тіло функції
-                let mut has_return = false;
-                for stmt in body {
-                    if matches!(stmt, Statement::Return(_)) {
-                        has_return = true;
-                    }
-                    self.compile_statement(stmt)?;
-                }
-                
-                // Додаємо неявний return якщо його немає
-                if !has_return && return_type.is_none() {
-                    self.builder.build_return(None);
-                }
+                self.compile_function_body(function, &name, &params, &return_type, body, span)?;
             }
             
             Declaration::Struct { .. } => {
@@ -160,8 +308,261 @@ impl<'ctx> Compiler<'ctx> {
         Ok(())
     }
     
+    fn compile_function_body(
+        &mut self,
+        function: FunctionValue<'ctx>,
+        name: &str,
+        params: &[Parameter],
+        return_type: &Option<Type>,
+        body: Vec<Statement>,
+        span: Span,
+    ) -> Result<()> {
+        self.current_function = Some(function);
+        
+        let entry = self.context.append_basic_block(function, "entry");
+        self.builder.position_at_end(entry);
+        self.debug_function(function, name, span);
+        self.set_debug_location(span);
+        
+        // Створюємо змінні для параметрів
+        self.variables = vec![HashMap::new()];
+        self.constants = vec![HashSet::new()];
+        for (i, param) in params.iter().enumerate() {
+            let arg = function.get_nth_param(i as u32).unwrap();
+            let alloca = self.builder.build_alloca(arg.get_type(), &param.name);
+            self.builder.build_store(alloca, arg);
+            self.declare_variable(param.name.clone(), alloca);
+        }
+        
+        // Компілюємо тіло функції
+        let mut has_return = false;
+        let last = body.len().saturating_sub(1);
+        for (i, stmt) in body.into_iter().enumerate() {
+            if matches!(stmt, Statement::Return(_)) {
+                has_return = true;
+            }
+            // Останній вираз функції з типом повернення — неявне `повернути`
+            if let (true, Some(_), Statement::Expression(expr)) = (i == last, return_type, &stmt) {
+                let val = self.compile_expression(expr.clone())?;
+                self.builder.build_return(Some(&val));
+                has_return = true;
+                continue;
+            }
+            self.compile_statement(stmt)?;
+        }
+        
+        // Додаємо неявний return якщо його немає
+        if !has_return {
+            if return_type.is_some() {
+                return Err(anyhow::anyhow!(
+                    "Функція '{}' на рядку {} має тип повернення, але не закінчується 'повернути' чи виразом",
+                    name, span.line
+                ));
+            }
+            self.builder.build_return(None);
+        }
+        Ok(())
+    }
+    
+    /// Мономорфізує generic-функцію під тип аргументів виклику. Інстанції живуть у
+    /// `functions` під іменем `ім'я<тип>`, тож кожна комбінація компілюється один раз.
+    fn instantiate_generic(&mut self, name: &str, args: &[BasicValueEnum<'ctx>]) -> Result<FunctionValue<'ctx>> {
+        let generic = self.generic_functions[name].clone();
+        let type_param = Type::Named(generic.type_param.clone());
+        let concrete = generic.params.iter().zip(args)
+            .find(|(param, _)| param.ty == type_param)
+            .and_then(|(_, arg)| self.tryzub_type_of(arg.get_type()))
+            .ok_or_else(|| anyhow::anyhow!(
+                "Не вдалося вивести тип '{}' для виклику '{}' з аргументів", generic.type_param, name
+            ))?;
+        
+        let mangled = format!("{}<{}>", name, type_name(&concrete));
+        if let Some(function) = self.functions.get(&mangled) {
+            return Ok(*function);
+        }
+        
+        let params: Vec<Parameter> = generic.params.iter()
+            .map(|p| Parameter { ty: substitute_type(&p.ty, &generic.type_param, &concrete), ..p.clone() })
+            .collect();
+        let return_type = generic.return_type.as_ref()
+            .map(|t| substitute_type(t, &generic.type_param, &concrete));
+        // Оголошуємо до компіляції тіла, щоб рекурсивні виклики знаходили інстанцію
+        self.declare_function(&mangled, &params, &return_type)?;
+        let function = self.functions[&mangled];
+        
+        // Тіло компілюється посеред іншої функції — зберігаємо її стан
+        let saved_block = self.builder.get_insert_block();
+        let saved_location = self.builder.get_current_debug_location();
+        let saved_function = self.current_function;
+        let saved_variables = std::mem::take(&mut self.variables);
+        let saved_constants = std::mem::take(&mut self.constants);
+        let saved_temps = std::mem::take(&mut self.temp_strings);
+        let saved_scope = self.debug_info.as_ref().and_then(|d| d.scope);
+        
+        let result = self.compile_function_body(function, &mangled, &params, &return_type, generic.body, generic.span);
+        
+        self.current_function = saved_function;
+        self.variables = saved_variables;
+        self.constants = saved_constants;
+        self.temp_strings = saved_temps;
+        if let Some(debug) = self.debug_info.as_mut() {
+            debug.scope = saved_scope;
+        }
+        if let Some(block) = saved_block {
+            self.builder.position_at_end(block);
+        }
+        match saved_location {
+            Some(location) => self.builder.set_current_debug_location(location),
+            None => self.builder.unset_current_debug_location(),
+        }
+        
+        result?;
+        Ok(function)
+    }
+    
+    /// Тип Тризуба, що відповідає LLVM-типу значення (для виведення параметра типу)
+    fn tryzub_type_of(&self, ty: BasicTypeEnum<'ctx>) -> Option<Type> {
+        match ty {
+            BasicTypeEnum::IntType(int) => match int.get_bit_width() {
+                1 => Some(Type::Лог),
+                8 => Some(Type::Цл8),
+                16 => Some(Type::Цл16),
+                32 => Some(Type::Цл32),
+                64 => Some(Type::Цл64),
+                _ => None,
+            },
+            BasicTypeEnum::FloatType(float) if float == self.context.f32_type() => Some(Type::Дрб32),
+            BasicTypeEnum::FloatType(float) if float == self.context.f64_type() => Some(Type::Дрб64),
+            BasicTypeEnum::PointerType(_) => Some(Type::Тхт),
+            _ => None,
+        }
+    }
+    
+    /// Алокує локальну змінну в entry-блоці поточної функції та ініціалізує її
+    fn compile_local_variable(&mut self, name: String, ty: Option<Type>, value: Option<Expression>, is_mutable: bool, span: Span) -> Result<()> {
+        self.set_debug_location(span);
+        let llvm_type = if let Some(ref t) = ty {
+            self.get_llvm_type(t)
+        } else if let Some(t) = self.inferred_types.get(&name, span) {
+            self.get_llvm_type(t)
+        } else if let Some(ref val) = value {
+            self.infer_type_from_expression(val)
+        } else {
+            return Err(anyhow::anyhow!("Не можу вивести тип змінної {}", name));
+        };
+        
+        let alloca = self.create_entry_block_alloca(llvm_type, &name);
+        
+        if let Some(init_value) = value {
+            let value = self.compile_expression(init_value)?;
+            self.builder.build_store(alloca, value);
+        }
+        
+        // Повторне оголошення через `змінна` знімає незмінність у цій області
+        if let Some(constants) = self.constants.last_mut() {
+            if is_mutable {
+                constants.remove(&name);
+            } else {
+                constants.insert(name.clone());
+            }
+        }
+        self.declare_variable(name, alloca);
+        Ok(())
+    }
+    
+    /// Глобальна змінна модуля; поза функцією немає де обчислити вираз,
+    /// тож ініціалізатор має бути сталою
+    fn compile_global_variable(&mut self, name: &str, ty: &Option<Type>, value: &Option<Expression>, is_mutable: bool, span: Span) -> Result<()> {
+        let llvm_type = match (ty, value) {
+            (Some(t), _) => self.get_llvm_type(t),
+            (None, Some(val)) => self.infer_type_from_expression(val),
+            (None, None) => return Err(anyhow::anyhow!("Не можу вивести тип змінної {}", name)),
+        };
+        let init = match value {
+            Some(expr) => self.const_initializer(name, expr, llvm_type, span)?,
+            None => llvm_type.const_zero(),
+        };
+        
+        let global = self.module.add_global(llvm_type, None, name);
+        global.set_initializer(&init);
+        global.set_constant(!is_mutable);
+        self.globals.insert(name.to_string(), global.as_pointer_value());
+        if !is_mutable {
+            self.global_constants.insert(name.to_string());
+        }
+        Ok(())
+    }
+    
+    fn const_initializer(&self, name: &str, expr: &Expression, ty: BasicTypeEnum<'ctx>, span: Span) -> Result<BasicValueEnum<'ctx>> {
+        match (expr, ty) {
+            (Expression::Literal(Literal::Integer(n)), BasicTypeEnum::IntType(t)) => Ok(t.const_int(*n as u64, true).into()),
+            (Expression::Literal(Literal::Integer(n)), BasicTypeEnum::FloatType(t)) => Ok(t.const_float(*n as f64).into()),
+            (Expression::Literal(Literal::Float(f)), BasicTypeEnum::FloatType(t)) => Ok(t.const_float(*f).into()),
+            (Expression::Literal(Literal::Bool(b)), BasicTypeEnum::IntType(t)) => Ok(t.const_int(*b as u64, false).into()),
+            (Expression::Literal(Literal::Char(c)), BasicTypeEnum::IntType(t)) => Ok(t.const_int(*c as u64, false).into()),
+            _ => Err(anyhow::anyhow!(
+                "Глобальна '{}' на рядку {} має ініціалізуватися числовою, логічною чи символьною сталою",
+                name, span.line
+            )),
+        }
+    }
+    
+    fn push_scope(&mut self) {
+        self.variables.push(HashMap::new());
+        self.constants.push(HashSet::new());
+    }
+    
+    fn pop_scope(&mut self) {
+        if self.variables.len() > 1 {
+            self.variables.pop();
+            self.constants.pop();
+        }
+    }
+    
+    fn declare_variable(&mut self, name: String, ptr: PointerValue<'ctx>) {
+        if let Some(scope) = self.variables.last_mut() {
+            scope.insert(name, ptr);
+        }
+    }
+    
+    /// Чи найближче оголошення імені — стала
+    fn is_constant(&self, name: &str) -> bool {
+        match self.variables.iter().zip(&self.constants).rev().find(|(scope, _)| scope.contains_key(name)) {
+            Some((_, constants)) => constants.contains(name),
+            None => self.global_constants.contains(name),
+        }
+    }
+    
+    /// Шукає змінну від найглибшої області видимості до зовнішньої,
+    /// а потім серед глобальних
+    fn lookup_variable(&self, name: &str) -> Option<PointerValue<'ctx>> {
+        self.variables.iter().rev().find_map(|scope| scope.get(name).copied())
+            .or_else(|| self.globals.get(name).copied())
+    }
+    
+    /// alloca в entry-блоці, щоб змінні в циклах не виділяли стек на кожній ітерації
+    fn create_entry_block_alloca(&self, ty: BasicTypeEnum<'ctx>, name: &str) -> PointerValue<'ctx> {
+        let entry = self.current_function.and_then(|f| f.get_first_basic_block());
+        match entry {
+            Some(entry) => {
+                let builder = self.context.create_builder();
+                match entry.get_first_instruction() {
+                    Some(first) => builder.position_before(&first),
+                    None => builder.position_at_end(entry),
+                }
+                builder.build_alloca(ty, name)
+            }
+            None => self.builder.build_alloca(ty, name),
+        }
+    }
+    
     fn compile_statement(&mut self, stmt: Statement) -> Result<()> {
         match stmt {
+            // Виклик-оператор відкидає результат, тож void-функція не потребує значення
+            Statement::Expression(Expression::Call { callee, args, .. }) => {
+                self.compile_call(*callee, args)?;
+            }
+            
             Statement::Expression(expr) => {
                 self.compile_expression(expr)?;
             }
@@ -176,12 +577,15 @@ impl<'ctx> Compiler<'ctx> {
             }
             
             Statement::Block(statements) => {
+                self.push_scope();
                 for stmt in statements {
                     self.compile_statement(stmt)?;
                 }
+                self.pop_scope();
             }
             
-            Statement::If { condition, then_branch, else_branch } => {
+            Statement::If { condition, then_branch, else_branch, span } => {
+                self.set_debug_location(span);
                 let cond_value = self.compile_expression(condition)?;
                 let cond = self.builder.build_int_compare(
                     inkwell::IntPredicate::NE,
@@ -213,7 +617,8 @@ impl<'ctx> Compiler<'ctx> {
                 self.builder.position_at_end(cont_bb);
             }
             
-            Statement::While { condition, body } => {
+            Statement::While { condition, body, span } => {
+                self.set_debug_location(span);
                 let function = self.current_function.unwrap();
                 let loop_bb = self.context.append_basic_block(function, "loop");
                 let after_bb = self.context.append_basic_block(function, "afterloop");
@@ -239,7 +644,8 @@ impl<'ctx> Compiler<'ctx> {
                 self.builder.position_at_end(after_bb);
             }
             
-            Statement::For { variable, from, to, step, body } => {
+            Statement::For { variable, from, to, step, body, span } => {
+                self.set_debug_location(span);
                 // Створюємо змінну циклу
                 let i32_type = self.context.i32_type();
                 let loop_var = self.builder.build_alloca(i32_type, &variable);
@@ -247,7 +653,8 @@ impl<'ctx> Compiler<'ctx> {
                 // Ініціалізуємо змінну
                 let from_value = self.compile_expression(from)?;
                 self.builder.build_store(loop_var, from_value);
-                self.variables.insert(variable.clone(), loop_var);
+                self.push_scope();
+                self.declare_variable(variable.clone(), loop_var);
                 
                 // Створюємо блоки
                 let function = self.current_function.unwrap();
@@ -287,60 +694,45 @@ impl<'ctx> Compiler<'ctx> {
                 self.builder.build_unconditional_branch(loop_bb);
                 
                 self.builder.position_at_end(after_bb);
-                self.variables.remove(&variable);
+                self.pop_scope();
             }
             
-            Statement::Assignment { target, value, op } => {
+            Statement::Assignment { target, value, op, span } => {
+                self.set_debug_location(span);
                 if let Expression::Identifier(name) = target {
-                    let ptr = self.variables.get(&name)
+                    let ptr = self.lookup_variable(&name)
                         .ok_or_else(|| anyhow::anyhow!("Невідома змінна: {}", name))?;
+                    if self.is_constant(&name) {
+                        return Err(anyhow::anyhow!("Не можна присвоїти сталій '{}' на рядку {}", name, span.line));
+                    }
                     
                     let new_value = match op {
                         AssignmentOp::Assign => self.compile_expression(value)?,
-                        AssignmentOp::AddAssign => {
-                            let current = self.builder.build_load(*ptr, "current");
-                            let add_value = self.compile_expression(value)?;
-                            self.builder.build_int_add(
-                                current.into_int_value(),
-                                add_value.into_int_value(),
-                                "addtmp"
-                            ).into()
-                        }
-                        AssignmentOp::SubAssign => {
-                            let current = self.builder.build_load(*ptr, "current");
-                            let sub_value = self.compile_expression(value)?;
-                            self.builder.build_int_sub(
-                                current.into_int_value(),
-                                sub_value.into_int_value(),
-                                "subtmp"
-                            ).into()
-                        }
-                        AssignmentOp::MulAssign => {
-                            let current = self.builder.build_load(*ptr, "current");
-                            let mul_value = self.compile_expression(value)?;
-                            self.builder.build_int_mul(
-                                current.into_int_value(),
-                                mul_value.into_int_value(),
-                                "multmp"
-                            ).into()
-                        }
-                        AssignmentOp::DivAssign => {
-                            let current = self.builder.build_load(*ptr, "current");
-                            let div_value = self.compile_expression(value)?;
-                            self.builder.build_int_signed_div(
-                                current.into_int_value(),
-                                div_value.into_int_value(),
-                                "divtmp"
-                            ).into()
+                        op => {
+                            let current = self.builder.build_load(ptr, "current");
+                            let operand = self.compile_expression(value)?;
+                            self.compile_compound_assignment(&name, op, current, operand)?
                         }
                     };
                     
-                    self.builder.build_store(*ptr, new_value);
+                    self.builder.build_store(ptr, new_value);
                 } else {
                     return Err(anyhow::anyhow!("Присвоєння можливе тільки до змінних"));
                 }
             }
             
+            Statement::Declaration(decl) => match decl {
+                Declaration::Variable { name, ty, value, is_mutable, span } => {
+                    self.compile_local_variable(name, ty, value, is_mutable, span)?;
+                }
+                Declaration::Function { name, .. } => {
+                    return Err(anyhow::anyhow!("Вкладені функції ще не підтримуються компілятором: {}", name));
+                }
+                _ => {
+                    // Локальні типи, трейти тощо не потребують коду
+                }
+            },
+            
             _ => {
                 // Інші statements делегуються до tree-walking VM
             }
@@ -358,17 +750,22 @@ impl<'ctx> Compiler<'ctx> {
                     // Створюємо декларацію printf якщо її ще немає
                     let printf = self.get_or_create_printf();
                     Ok(printf.as_global_value().as_pointer_value().into())
-                } else if let Some(ptr) = self.variables.get(&name) {
-                    Ok(self.builder.build_load(*ptr, &name))
+                } else if let Some(ptr) = self.lookup_variable(&name) {
+                    Ok(self.builder.build_load(ptr, &name))
                 } else {
                     Err(anyhow::anyhow!("Невідома змінна: {}", name))
                 }
             }
             
-            Expression::Binary { left, op, right } => {
+            Expression::Binary { left, op, right, .. } => {
                 let lhs = self.compile_expression(*left)?;
                 let rhs = self.compile_expression(*right)?;
                 
+                if op == BinaryOp::Add && lhs.is_pointer_value() && rhs.is_pointer_value() {
+                    let result = self.compile_string_concat(lhs.into_pointer_value(), rhs.into_pointer_value());
+                    return Ok(result.into());
+                }
+                
                 match op {
                     BinaryOp::Add => {
                         if lhs.is_int_value() {
@@ -474,6 +871,20 @@ impl<'ctx> Compiler<'ctx> {
                             "booltmp"
                         ).into())
                     }
+                    BinaryOp::BitAnd | BinaryOp::BitOr | BinaryOp::BitXor | BinaryOp::Shl | BinaryOp::Shr => {
+                        if !lhs.is_int_value() || !rhs.is_int_value() {
+                            return Err(anyhow::anyhow!("Побітовий оператор {:?} застосовний лише до цілих", op));
+                        }
+                        let (l, r) = (lhs.into_int_value(), rhs.into_int_value());
+                        let result = match op {
+                            BinaryOp::BitAnd => self.builder.build_and(l, r, "andtmp"),
+                            BinaryOp::BitOr => self.builder.build_or(l, r, "ortmp"),
+                            BinaryOp::BitXor => self.builder.build_xor(l, r, "xortmp"),
+                            BinaryOp::Shl => self.builder.build_left_shift(l, r, "shltmp"),
+                            _ => self.builder.build_right_shift(l, r, true, "shrtmp"),
+                        };
+                        Ok(result.into())
+                    }
                     _ => Err(anyhow::anyhow!("Оператор {:?} ще не реалізований", op)),
                 }
             }
@@ -505,46 +916,191 @@ impl<'ctx> Compiler<'ctx> {
                 }
             }
             
-            Expression::Call { callee, args } => {
-                if let Expression::Identifier(name) = *callee {
-                    if name == "друк" {
-                        // Спеціальна обробка для друку
-                        self.compile_print_call(args)
-                    } else if let Some(function) = self.functions.get(&name) {
-                        let mut arg_values = Vec::new();
-                        for arg in args {
-                            arg_values.push(self.compile_expression(arg)?.into());
-                        }
-                        Ok(self.builder.build_call(*function, &arg_values, "calltmp")
-                            .try_as_basic_value()
-                            .left()
-                            .unwrap_or_else(|| self.context.i32_type().const_zero().into()))
-                    } else {
-                        Err(anyhow::anyhow!("Невідома функція: {}", name))
-                    }
-                } else {
-                    Err(anyhow::anyhow!("Непрямі виклики функцій ще не підтримуються"))
+            Expression::Call { callee, args, .. } => {
+                let name = match callee.as_ref() {
+                    Expression::Identifier(name) => name.clone(),
+                    _ => String::new(),
+                };
+                self.compile_call(*callee, args)?.ok_or_else(|| anyhow::anyhow!(
+                    "Функція '{}' нічого не повертає, тож її виклик не може бути значенням", name
+                ))
+            }
+            
+            Expression::Block(statements, result) => {
+                self.push_scope();
+                for stmt in statements {
+                    self.compile_statement(stmt)?;
                 }
+                let value = match result {
+                    Some(expr) => self.compile_expression(*expr),
+                    None => self.compile_literal(Literal::Null),
+                };
+                self.pop_scope();
+                value
             }
             
+            Expression::Cast { expr, ty } => {
+                let value = self.compile_expression(*expr)?;
+                self.compile_cast(value, &ty)
+            }
+
             _ => Err(anyhow::anyhow!("Вираз {:?} ще не реалізований", expr)),
         }
     }
     
-    fn compile_literal(&self, lit: Literal) -> Result<BasicValueEnum<'ctx>> {
+    /// Виклик функції за іменем; `None`, якщо функція нічого не повертає
+    fn compile_call(&mut self, callee: Expression, args: Vec<Expression>) -> Result<Option<BasicValueEnum<'ctx>>> {
+        let Expression::Identifier(name) = callee else {
+            return Err(anyhow::anyhow!("Непрямі виклики функцій ще не підтримуються"));
+        };
+        if name == "друк" {
+            // Спеціальна обробка для друку
+            return self.compile_print_call(args).map(Some);
+        }
+        if !self.generic_functions.contains_key(&name) && !self.functions.contains_key(&name) {
+            return Err(anyhow::anyhow!("Невідома функція: {}", name));
+        }
+        
+        let mut arg_values = Vec::new();
+        for arg in args {
+            arg_values.push(self.compile_expression(arg)?);
+        }
+        let function = if self.generic_functions.contains_key(&name) {
+            self.instantiate_generic(&name, &arg_values)?
+        } else {
+            self.functions[&name]
+        };
+        let arg_values: Vec<BasicMetadataValueEnum> = arg_values.into_iter().map(Into::into).collect();
+        Ok(self.builder.build_call(function, &arg_values, "calltmp")
+            .try_as_basic_value()
+            .left())
+    }
+    
+    /// `+=`, `-=`, `*=`, `/=`, `%=`: тип результату задає змінна, тож правий операнд
+    /// приводиться до нього (ціле розширюється чи стає дробовим), а інструкція
+    /// обирається за типом змінної
+    fn compile_compound_assignment(
+        &mut self,
+        name: &str,
+        op: AssignmentOp,
+        current: BasicValueEnum<'ctx>,
+        operand: BasicValueEnum<'ctx>,
+    ) -> Result<BasicValueEnum<'ctx>> {
+        if current.is_pointer_value() && operand.is_pointer_value() && op == AssignmentOp::AddAssign {
+            let result = self.compile_string_concat(current.into_pointer_value(), operand.into_pointer_value());
+            return Ok(result.into());
+        }
+        let operand = match (current, operand) {
+            (BasicValueEnum::IntValue(_), BasicValueEnum::IntValue(_))
+            | (BasicValueEnum::FloatValue(_), BasicValueEnum::IntValue(_) | BasicValueEnum::FloatValue(_)) => {
+                let target = self.tryzub_type_of(current.get_type())
+                    .ok_or_else(|| anyhow::anyhow!("Непідтримуваний тип змінної '{}' у {:?}", name, op))?;
+                self.compile_cast(operand, &target)?
+            }
+            _ => {
+                let source = |v: BasicValueEnum<'ctx>| self.tryzub_type_of(v.get_type())
+                    .map(|t| type_name(&t))
+                    .unwrap_or_else(|| "невідомий тип".to_string());
+                return Err(anyhow::anyhow!(
+                    "Несумісні типи у {:?} для змінної '{}': '{}' і '{}'",
+                    op, name, source(current), source(operand)
+                ));
+            }
+        };
+        
+        let result: BasicValueEnum<'ctx> = if current.is_int_value() {
+            let (lhs, rhs) = (current.into_int_value(), operand.into_int_value());
+            let value = match op {
+                AssignmentOp::AddAssign => self.builder.build_int_add(lhs, rhs, "addtmp"),
+                AssignmentOp::SubAssign => self.builder.build_int_sub(lhs, rhs, "subtmp"),
+                AssignmentOp::MulAssign => self.builder.build_int_mul(lhs, rhs, "multmp"),
+                AssignmentOp::DivAssign => self.builder.build_int_signed_div(lhs, rhs, "divtmp"),
+                AssignmentOp::ModAssign => self.builder.build_int_signed_rem(lhs, rhs, "remtmp"),
+                AssignmentOp::Assign => unreachable!("просте присвоєння не є складеним"),
+            };
+            value.into()
+        } else {
+            let (lhs, rhs) = (current.into_float_value(), operand.into_float_value());
+            let value = match op {
+                AssignmentOp::AddAssign => self.builder.build_float_add(lhs, rhs, "faddtmp"),
+                AssignmentOp::SubAssign => self.builder.build_float_sub(lhs, rhs, "fsubtmp"),
+                AssignmentOp::MulAssign => self.builder.build_float_mul(lhs, rhs, "fmultmp"),
+                AssignmentOp::DivAssign => self.builder.build_float_div(lhs, rhs, "fdivtmp"),
+                AssignmentOp::ModAssign => self.builder.build_float_rem(lhs, rhs, "fremtmp"),
+                AssignmentOp::Assign => unreachable!("просте присвоєння не є складеним"),
+            };
+            value.into()
+        };
+        Ok(result)
+    }
+    
+    /// `вираз як тип` для чисел: зміна ширини цілих, ціле↔дробове, дробове↔дробове.
+    /// Джерело вважається знаковим, окрім `лог`, який розширюється нулями
+    fn compile_cast(&mut self, value: BasicValueEnum<'ctx>, target: &Type) -> Result<BasicValueEnum<'ctx>> {
+        let unsigned_target = matches!(target, Type::Чс8 | Type::Чс16 | Type::Чс32 | Type::Чс64);
+        let target_type = match target {
+            Type::Цл8 | Type::Цл16 | Type::Цл32 | Type::Цл64 | Type::Чс8 | Type::Чс16 | Type::Чс32
+            | Type::Чс64 | Type::Дрб32 | Type::Дрб64 | Type::Лог | Type::Сим => self.get_llvm_type(target),
+            _ => return Err(self.invalid_cast(value, target)),
+        };
+        
+        let result: BasicValueEnum<'ctx> = match (value, target_type) {
+            (BasicValueEnum::IntValue(int), BasicTypeEnum::IntType(int_type)) => {
+                if int_type.get_bit_width() == 1 {
+                    let zero = int.get_type().const_zero();
+                    self.builder.build_int_compare(inkwell::IntPredicate::NE, int, zero, "tobool").into()
+                } else {
+                    let is_signed = int.get_type().get_bit_width() != 1;
+                    self.builder.build_int_cast_sign_flag(int, int_type, is_signed, "intcast").into()
+                }
+            }
+            (BasicValueEnum::IntValue(int), BasicTypeEnum::FloatType(float_type)) => {
+                self.builder.build_signed_int_to_float(int, float_type, "inttofloat").into()
+            }
+            (BasicValueEnum::FloatValue(float), BasicTypeEnum::IntType(int_type)) if int_type.get_bit_width() > 1 => {
+                if unsigned_target {
+                    self.builder.build_float_to_unsigned_int(float, int_type, "floattouint").into()
+                } else {
+                    self.builder.build_float_to_signed_int(float, int_type, "floattoint").into()
+                }
+            }
+            (BasicValueEnum::FloatValue(float), BasicTypeEnum::FloatType(float_type)) => {
+                self.builder.build_float_cast(float, float_type, "floatcast").into()
+            }
+            _ => return Err(self.invalid_cast(value, target)),
+        };
+        Ok(result)
+    }
+    
+    fn invalid_cast(&self, value: BasicValueEnum<'ctx>, target: &Type) -> anyhow::Error {
+        let source = self.tryzub_type_of(value.get_type())
+            .map(|t| type_name(&t))
+            .unwrap_or_else(|| "невідомий тип".to_string());
+        anyhow::anyhow!("Неможливо привести '{}' до '{}'", source, type_name(target))
+    }
+    
+    fn compile_literal(&mut self, lit: Literal) -> Result<BasicValueEnum<'ctx>> {
         match lit {
             Literal::Integer(n) => Ok(self.context.i32_type().const_int(n as u64, false).into()),
             Literal::Float(f) => Ok(self.context.f64_type().const_float(f).into()),
-            Literal::String(s) => {
-                let value = self.builder.build_global_string_ptr(&s, "str");
-                Ok(value.as_pointer_value().into())
-            }
+            Literal::String(s) => Ok(self.string_constant(&s, "str").into()),
             Literal::Char(c) => Ok(self.context.i8_type().const_int(c as u64, false).into()),
             Literal::Bool(b) => Ok(self.context.bool_type().const_int(b as u64, false).into()),
             Literal::Null => Ok(self.context.i32_type().ptr_type(AddressSpace::Generic).const_null().into()),
         }
     }
     
+    /// Вказівник на рядкову константу; однаковий вміст перевикористовує вже
+    /// створену глобаль
+    fn string_constant(&mut self, text: &str, name: &str) -> PointerValue<'ctx> {
+        if let Some(global) = self.string_constants.get(text) {
+            return global.as_pointer_value();
+        }
+        let global = self.builder.build_global_string_ptr(text, name);
+        self.string_constants.insert(text.to_string(), global);
+        global.as_pointer_value()
+    }
+    
     fn compile_print_call(&mut self, args: Vec<Expression>) -> Result<BasicValueEnum<'ctx>> {
         let printf = self.get_or_create_printf();
         
@@ -567,9 +1123,9 @@ impl<'ctx> Compiler<'ctx> {
         }
         
         format_string.push('\n');
-        let format_str = self.builder.build_global_string_ptr(&format_string, "fmt");
+        let format_str = self.string_constant(&format_string, "fmt");
         
-        let mut all_args = vec![format_str.as_pointer_value().into()];
+        let mut all_args = vec![format_str.into()];
         all_args.extend(print_args);
         
         Ok(self.builder.build_call(printf, &all_args, "printf_call")
@@ -578,6 +1134,40 @@ impl<'ctx> Compiler<'ctx> {
             .unwrap_or_else(|| self.context.i32_type().const_zero().into()))
     }
     
+    /// Конкатенація рядків через runtime. Проміжні результати ланцюжка `а + б + в`
+    /// звільняються одразу; кінцевий рядок належить коду, що його використовує.
+    fn compile_string_concat(&mut self, lhs: PointerValue<'ctx>, rhs: PointerValue<'ctx>) -> PointerValue<'ctx> {
+        let str_type = self.context.i8_type().ptr_type(AddressSpace::Generic);
+        let concat = self.get_or_declare_function(
+            "tryzub_string_concat",
+            str_type.fn_type(&[str_type.into(), str_type.into()], false),
+        );
+        let free = self.get_or_declare_function(
+            "tryzub_string_free",
+            self.context.void_type().fn_type(&[str_type.into()], false),
+        );
+        
+        let result = self.builder
+            .build_call(concat, &[lhs.into(), rhs.into()], "concat")
+            .try_as_basic_value()
+            .left()
+            .unwrap()
+            .into_pointer_value();
+        
+        for operand in [lhs, rhs] {
+            if self.temp_strings.remove(&operand) {
+                self.builder.build_call(free, &[operand.into()], "");
+            }
+        }
+        self.temp_strings.insert(result);
+        result
+    }
+    
+    fn get_or_declare_function(&self, name: &str, fn_type: FunctionType<'ctx>) -> FunctionValue<'ctx> {
+        self.module.get_function(name)
+            .unwrap_or_else(|| self.module.add_function(name, fn_type, None))
+    }
+    
     fn get_or_create_printf(&mut self) -> FunctionValue<'ctx> {
         if let Some(function) = self.module.get_function("printf") {
             function
@@ -629,11 +1219,16 @@ impl<'ctx> Compiler<'ctx> {
             }
             Expression::Literal(Literal::Char(_)) => self.context.i8_type().into(),
             Expression::Literal(Literal::Bool(_)) => self.context.bool_type().into(),
+            Expression::Cast { ty, .. } => self.get_llvm_type(ty),
             _ => self.context.i32_type().into(), // Default
         }
     }
     
     pub fn generate_object_file(&self, path: &Path, opt_level: u8) -> Result<()> {
+        if let Some(debug) = &self.debug_info {
+            debug.builder.finalize();
+        }
+        
         Target::initialize_all(&InitializationConfig::default());
         
         let target_triple = TargetMachine::get_default_triple();
@@ -644,7 +1239,7 @@ impl<'ctx> Compiler<'ctx> {
                 "generic",
                 "",
                 self.get_opt_level(opt_level),
-                RelocMode::Default,
+                if self.library { RelocMode::PIC } else { RelocMode::Default },
                 CodeModel::Default,
             )
             .ok_or_else(|| anyhow::anyhow!("Не вдалося створити target machine"))?;
@@ -664,30 +1259,66 @@ impl<'ctx> Compiler<'ctx> {
     }
 }
 
+/// Замінює параметр типу `param` на конкретний тип усюди всередині `ty`
+fn substitute_type(ty: &Type, param: &str, concrete: &Type) -> Type {
+    let sub = |t: &Type| substitute_type(t, param, concrete);
+    match ty {
+        Type::Named(name) if name == param => concrete.clone(),
+        Type::Array(elem, size) => Type::Array(Box::new(sub(elem)), *size),
+        Type::Slice(elem) => Type::Slice(Box::new(sub(elem))),
+        Type::Tuple(items) => Type::Tuple(items.iter().map(sub).collect()),
+        Type::Reference(inner, is_mut) => Type::Reference(Box::new(sub(inner)), *is_mut),
+        Type::Function(params, ret) => Type::Function(
+            params.iter().map(sub).collect(),
+            ret.as_ref().map(|r| Box::new(sub(r))),
+        ),
+        Type::Generic(name, args) => Type::Generic(name.clone(), args.iter().map(sub).collect()),
+        Type::Optional(inner) => Type::Optional(Box::new(sub(inner))),
+        Type::Result(ok, err) => Type::Result(Box::new(sub(ok)), Box::new(sub(err))),
+        other => other.clone(),
+    }
+}
+
 pub fn optimize(ast: Program, opt_level: u8) -> Result<Program> {
-    // AST оптимізації через pattern recognition у VM
-    Ok(ast)
+    if opt_level == 0 {
+        return Ok(ast);
+    }
+    Ok(tryzub_parser::cse::eliminate_common_subexpressions(ast))
 }
 
-pub fn generate_executable(ast: Program, output: std::path::PathBuf, _target: Option<String>) -> Result<()> {
+pub fn generate_executable(
+    ast: Program,
+    output: std::path::PathBuf,
+    _target: Option<String>,
+    debug_source: Option<&Path>,
+) -> Result<()> {
     let context = Context::create();
     let mut compiler = Compiler::new(&context, "tryzub_module");
     
-    compiler.compile(ast)?;
+    if let Some(source) = debug_source {
+        compiler.enable_debug_info(source);
+    }
+    // З налагодженням не оптимізуємо, щоб рядки збігалися
+    let opt_level = if debug_source.is_some() { 0 } else { 2 };
+    compiler.compile(optimize(ast, opt_level)?)?;
     
     // Генеруємо об'єктний файл
     let obj_path = output.with_extension("o");
-    compiler.generate_object_file(&obj_path, 2)?;
+    compiler.generate_object_file(&obj_path, opt_level)?;
     
-    // Лінкуємо в виконуваний файл
-    let status = std::process::Command::new("clang")
-        .args(&[
-            obj_path.to_str().unwrap(),
-            "-o",
-            output.to_str().unwrap(),
-            "-lm", // Математична бібліотека
-        ])
-        .status()?;
+    // Лінкуємо в виконуваний файл разом зі статичною бібліотекою runtime
+    let mut command = std::process::Command::new("clang");
+    command.args(&[
+        obj_path.to_str().unwrap(),
+        "-o",
+        output.to_str().unwrap(),
+        "-lm", // Математична бібліотека
+        if debug_source.is_some() { "-g" } else { "-O2" },
+    ]);
+    if let Some(runtime) = runtime_library() {
+        command.arg(runtime).args(["-lpthread", "-ldl"]);
+    }
+    let status = command.status()?;
     
     if !status.success() {
         return Err(anyhow::anyhow!("Помилка лінкування"));
@@ -699,6 +1330,54 @@ pub fn generate_executable(ast: Program, output: std::path::PathBuf, _target: Op
     Ok(())
 }
 
+/// Збирає бібліотеку з публічних функцій програми; функція `головна` не потрібна
+pub fn generate_library(ast: Program, output: std::path::PathBuf, kind: LibraryKind) -> Result<()> {
+    let context = Context::create();
+    let mut compiler = Compiler::new(&context, "tryzub_module");
+    compiler.enable_library_mode();
+    compiler.compile(ast)?;
+    
+    let obj_path = output.with_extension("o");
+    compiler.generate_object_file(&obj_path, 2)?;
+    
+    let status = match kind {
+        LibraryKind::Static => {
+            let archiver = if cfg!(windows) { "llvm-ar" } else { "ar" };
+            std::process::Command::new(archiver)
+                .arg("rcs")
+                .arg(&output)
+                .arg(&obj_path)
+                .status()?
+        }
+        LibraryKind::Shared => {
+            let mut command = std::process::Command::new("clang");
+            command.arg("-shared").arg(&obj_path).arg("-o").arg(&output).arg("-lm");
+            if let Some(runtime) = runtime_library() {
+                command.arg(runtime);
+            }
+            command.status()?
+        }
+    };
+    
+    if !status.success() {
+        return Err(anyhow::anyhow!("Помилка збирання бібліотеки"));
+    }
+    
+    std::fs::remove_file(obj_path)?;
+    
+    Ok(())
+}
+
+/// Шлях до `libtryzub_runtime.a`: змінна `TRYZUB_RUNTIME_LIB` або каталог поруч з `тризуб`
+fn runtime_library() -> Option<PathBuf> {
+    if let Ok(path) = std::env::var("TRYZUB_RUNTIME_LIB") {
+        return Some(PathBuf::from(path));
+    }
+    let exe = std::env::current_exe().ok()?;
+    let candidate = exe.parent()?.join("libtryzub_runtime.a");
+    candidate.exists().then_some(candidate)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -723,4 +1402,507 @@ mod tests {
         
         assert!(compiler.compile(program).is_ok());
     }
+    
+    #[test]
+    fn test_compile_local_variables() {
+        let source = r#"
+функція обчислити(н: цл32) -> цл32 {
+    змінна а: цл32 = н * 2
+    змінна б = а + 1
+    змінна в = б - н
+    повернути в
+}
+
+функція головна() {
+    друк(обчислити(7))
+}
+"#;
+        
+        let tokens = tokenize(source).unwrap();
+        let program = parse(tokens).unwrap();
+        
+        let context = Context::create();
+        let mut compiler = Compiler::new(&context, "test_locals");
+        
+        assert!(compiler.compile(program).is_ok());
+        assert!(compiler.module.verify().is_ok());
+    }
+    
+    #[test]
+    fn test_compile_compound_assignment_types() {
+        let source = r#"
+функція обчислити() -> дрб64 {
+    змінна x: цл32 = 10
+    x += 1
+    x %= 4
+    змінна великий: цл64 = 5
+    великий *= 3
+    змінна f: дрб64 = 1.5
+    f += 1.0
+    f -= 1
+    f /= 2.0
+    повернути f
+}
+"#;
+        
+        let tokens = tokenize(source).unwrap();
+        let program = parse(tokens).unwrap();
+        
+        let context = Context::create();
+        let mut compiler = Compiler::new(&context, "test_compound");
+        
+        assert!(compiler.compile(program).is_ok());
+        assert!(compiler.module.verify().is_ok());
+        
+        let ir = compiler.module.print_to_string().to_string();
+        assert!(ir.contains("add i32"), "{}", ir);
+        assert!(ir.contains("srem i32"), "{}", ir);
+        assert!(ir.contains("mul i64"), "{}", ir);
+        assert!(ir.contains("fadd double"), "{}", ir);
+        
+        let source = r#"
+функція ф() {
+    змінна x: цл32 = 1
+    x += 0.5
+}
+"#;
+        let program = parse(tokenize(source).unwrap()).unwrap();
+        let mut compiler = Compiler::new(&context, "test_compound_err");
+        let err = compiler.compile(program).unwrap_err().to_string();
+        assert!(err.contains("Несумісні типи"), "{}", err);
+    }
+
+    #[test]
+    fn test_compile_void_call_statement() {
+        let source = r#"
+функція привітати(x: цл32) {
+    друк(x)
+}
+
+функція головна() {
+    привітати(1)
+    привітати(2)
+}
+"#;
+        
+        let tokens = tokenize(source).unwrap();
+        let program = parse(tokens).unwrap();
+        
+        let context = Context::create();
+        let mut compiler = Compiler::new(&context, "test_void_call");
+        
+        assert!(compiler.compile(program).is_ok());
+        assert!(compiler.module.verify().is_ok());
+        
+        let ir = compiler.module.print_to_string().to_string();
+        assert!(ir.contains("call void @"), "{}", ir);
+        
+        // Значення void-виклику використати не можна
+        let source = r#"
+функція нічого() {
+}
+
+функція ф() -> цл32 {
+    стала x = нічого()
+    повернути 0
+}
+"#;
+        let program = parse(tokenize(source).unwrap()).unwrap();
+        let mut compiler = Compiler::new(&context, "test_void_call_err");
+        let err = compiler.compile(program).unwrap_err().to_string();
+        assert!(err.contains("нічого не повертає"), "{}", err);
+    }
+
+    #[test]
+    fn test_compile_main_signature() {
+        let context = Context::create();
+        let compile = |name: &str, source: &str| {
+            let program = parse(tokenize(source).unwrap()).unwrap();
+            let mut compiler = Compiler::new(&context, name);
+            compiler.compile(program).map(|_| compiler.module.print_to_string().to_string())
+        };
+        
+        let err = compile("test_main_params", "функція головна(x: цл32) {\n    друк(x)\n}").unwrap_err().to_string();
+        assert!(err.contains("'головна'") && err.contains("параметрів"), "{}", err);
+        
+        let err = compile("test_main_return", "функція головна() -> тхт {\n    повернути \"так\"\n}").unwrap_err().to_string();
+        assert!(err.contains("цл32"), "{}", err);
+        
+        // Результат `головна() -> цл32` стає кодом виходу
+        let ir = compile("test_main_code", "функція головна() -> цл32 {\n    повернути 3\n}").unwrap();
+        assert!(ir.contains("ret i32 %call"), "{}", ir);
+    }
+
+    #[test]
+    fn test_compile_deduplicates_string_constants() {
+        let source = r#"
+функція головна() {
+    друк("привіт")
+    друк("привіт")
+    друк(1)
+    друк(2)
+}
+"#;
+        
+        let tokens = tokenize(source).unwrap();
+        let program = parse(tokens).unwrap();
+        
+        let context = Context::create();
+        let mut compiler = Compiler::new(&context, "test_strings");
+        
+        assert!(compiler.compile(program).is_ok());
+        assert!(compiler.module.verify().is_ok());
+        
+        let ir = compiler.module.print_to_string().to_string();
+        let globals = |prefix: &str| ir.lines().filter(|line| line.starts_with(prefix)).count();
+        // Один літерал "привіт", шаблони "%s\n" і "%d\n" — по одному на вміст
+        assert_eq!(globals("@str"), 1, "{}", ir);
+        assert_eq!(globals("@fmt"), 2, "{}", ir);
+    }
+    
+    #[test]
+    fn test_compile_shadowed_variable() {
+        let source = r#"
+функція обчислити() -> цл32 {
+    змінна х: цл32 = 1
+    {
+        змінна х: цл32 = 100
+        друк(х)
+    }
+    повернути х
+}
+
+функція головна() {
+    друк(обчислити())
+}
+"#;
+        
+        let tokens = tokenize(source).unwrap();
+        let program = parse(tokens).unwrap();
+        
+        let context = Context::create();
+        let mut compiler = Compiler::new(&context, "test_shadow");
+        
+        assert!(compiler.compile(program).is_ok());
+        assert!(compiler.module.verify().is_ok());
+        // Після виходу з блоку лишається лише область видимості функції
+        assert_eq!(compiler.variables.len(), 1);
+    }
+    
+    #[test]
+    fn test_compile_constant_assignment() {
+        let source = r#"
+функція головна() {
+    стала межа: цл32 = 10
+    змінна х: цл32 = межа
+    х = х + 1
+    {
+        змінна межа: цл32 = 0
+        межа += 1
+    }
+}
+"#;
+        
+        let tokens = tokenize(source).unwrap();
+        let program = parse(tokens).unwrap();
+        
+        let context = Context::create();
+        let mut compiler = Compiler::new(&context, "test_constants");
+        
+        assert!(compiler.compile(program).is_ok());
+        
+        let source = r#"
+функція головна() {
+    змінна х: цл32 = 1
+    стала межа: цл32 = 10
+    межа += х
+}
+"#;
+        let tokens = tokenize(source).unwrap();
+        let program = parse(tokens).unwrap();
+        
+        let context = Context::create();
+        let mut compiler = Compiler::new(&context, "test_constants_err");
+        
+        let err = compiler.compile(program).unwrap_err().to_string();
+        assert!(err.contains("Не можна присвоїти сталій 'межа' на рядку 5"), "{}", err);
+    }
+    
+    #[test]
+    fn test_compile_generic_instantiation() {
+        let source = r#"
+функція подвоїти<Т>(х: Т) -> Т {
+    повернути х + х
+}
+
+функція головна() {
+    друк(подвоїти(21))
+    друк(подвоїти(1.25))
+    друк(подвоїти(4))
+}
+"#;
+        
+        let tokens = tokenize(source).unwrap();
+        let program = parse(tokens).unwrap();
+        
+        let context = Context::create();
+        let mut compiler = Compiler::new(&context, "test_generic");
+        
+        assert!(compiler.compile(program).is_ok());
+        assert!(compiler.module.verify().is_ok());
+        
+        // Одна інстанція на кожен тип, сам шаблон не генерується
+        let int_fn = compiler.module.get_function("подвоїти<цл32>").unwrap();
+        let float_fn = compiler.module.get_function("подвоїти<дрб64>").unwrap();
+        assert!(compiler.module.get_function("подвоїти").is_none());
+        assert_eq!(int_fn.get_type().get_return_type(), Some(context.i32_type().into()));
+        assert_eq!(float_fn.get_type().get_return_type(), Some(context.f64_type().into()));
+        let instances = compiler.module.get_functions()
+            .filter(|f| f.get_name().to_string_lossy().starts_with("подвоїти<"))
+            .count();
+        assert_eq!(instances, 2);
+    }
+    
+    #[test]
+    fn test_compile_casts() {
+        let source = r#"
+функція головна() {
+    змінна x: цл32 = 7
+    змінна f: дрб64 = 2.75
+    змінна широке: цл64 = x як цл64
+    змінна ціле: цл32 = f як цл32
+    змінна дробове: дрб64 = x як дрб64
+    змінна вузьке: цл8 = широке як цл8
+}
+"#;
+        
+        let tokens = tokenize(source).unwrap();
+        let program = parse(tokens).unwrap();
+        
+        let context = Context::create();
+        let mut compiler = Compiler::new(&context, "test_casts");
+        
+        assert!(compiler.compile(program).is_ok());
+        assert!(compiler.module.verify().is_ok());
+        let ir = compiler.module.print_to_string().to_string();
+        assert!(ir.contains("sext i32"));
+        assert!(ir.contains("fptosi double"));
+        assert!(ir.contains("sitofp i32"));
+        assert!(ir.contains("trunc i64"));
+        
+        let source = r#"
+функція головна() {
+    змінна н = "текст" як цл32
+}
+"#;
+        let tokens = tokenize(source).unwrap();
+        let program = parse(tokens).unwrap();
+        let context = Context::create();
+        let mut compiler = Compiler::new(&context, "test_invalid_cast");
+        let err = compiler.compile(program).unwrap_err().to_string();
+        assert!(err.contains("Неможливо привести 'тхт' до 'цл32'"), "{}", err);
+    }
+    
+    #[test]
+    fn test_compile_global_constant() {
+        let source = r#"
+функція подвоєний() -> цл64 {
+    повернути МАКС * 2
+}
+
+стала МАКС: цл64 = 100
+змінна лічильник = 0
+
+функція головна() {
+    лічильник = лічильник + подвоєний()
+}
+"#;
+        let tokens = tokenize(source).unwrap();
+        let program = parse(tokens).unwrap();
+        
+        let context = Context::create();
+        let mut compiler = Compiler::new(&context, "test_globals");
+        
+        assert!(compiler.compile(program).is_ok());
+        assert!(compiler.module.verify().is_ok());
+        let ir = compiler.module.print_to_string().to_string();
+        assert!(ir.contains("constant i64 100"), "{}", ir);
+        assert!(ir.contains("global i64 0"), "{}", ir);
+        assert!(ir.contains("load i64"), "{}", ir);
+        
+        let source = r#"
+стала МАКС = 100
+функція головна() {
+    МАКС = 5
+}
+"#;
+        let tokens = tokenize(source).unwrap();
+        let program = parse(tokens).unwrap();
+        let context = Context::create();
+        let mut compiler = Compiler::new(&context, "test_global_assign");
+        let err = compiler.compile(program).unwrap_err().to_string();
+        assert!(err.contains("Не можна присвоїти сталій 'МАКС'"), "{}", err);
+    }
+    
+    #[test]
+    fn test_compile_implicit_return() {
+        let source = r#"
+функція квадрат(х: цл32) -> цл32 {
+    змінна к = х * х
+    к + 1
+}
+
+функція головна() {
+    друк(квадрат(6))
+}
+"#;
+        
+        let tokens = tokenize(source).unwrap();
+        let program = parse(tokens).unwrap();
+        
+        let context = Context::create();
+        let mut compiler = Compiler::new(&context, "test_implicit_return");
+        
+        assert!(compiler.compile(program).is_ok());
+        assert!(compiler.module.verify().is_ok());
+        
+        let source = r#"
+функція нічого(х: цл32) -> цл32 {
+    змінна к = х
+}
+"#;
+        let tokens = tokenize(source).unwrap();
+        let program = parse(tokens).unwrap();
+        let context = Context::create();
+        let mut compiler = Compiler::new(&context, "test_missing_return");
+        let err = compiler.compile(program).unwrap_err().to_string();
+        assert!(err.contains("не закінчується 'повернути'"), "{}", err);
+    }
+    
+    #[test]
+    fn test_debug_info_sections() {
+        let source = r#"
+функція головна() {
+    змінна x: цл32 = 10
+    друк(x)
+}
+"#;
+        
+        let tokens = tokenize(source).unwrap();
+        let program = parse(tokens).unwrap();
+        
+        let context = Context::create();
+        let mut compiler = Compiler::new(&context, "test_debug");
+        compiler.enable_debug_info(Path::new("/tmp/налагодження.тризуб"));
+        
+        assert!(compiler.compile(program).is_ok());
+        
+        let obj_path = std::env::temp_dir().join("tryzub_test_debug.o");
+        compiler.generate_object_file(&obj_path, 0).unwrap();
+        let bytes = std::fs::read(&obj_path).unwrap();
+        std::fs::remove_file(&obj_path).ok();
+        
+        let contains = |needle: &[u8]| bytes.windows(needle.len()).any(|w| w == needle);
+        assert!(contains(b".debug_info"));
+        assert!(contains(b".debug_line"));
+    }
+    
+    #[test]
+    fn test_compile_library() {
+        let source = r#"
+публічний функція подвоїти(х: цл32) -> цл32 {
+    повернути помічник(х) * 2
+}
+
+функція помічник(х: цл32) -> цл32 {
+    повернути х
+}
+"#;
+        
+        let tokens = tokenize(source).unwrap();
+        let program = parse(tokens).unwrap();
+        
+        let context = Context::create();
+        let mut compiler = Compiler::new(&context, "test_library");
+        compiler.enable_library_mode();
+        
+        assert!(compiler.compile(program.clone()).is_ok());
+        assert!(compiler.module.verify().is_ok());
+        assert!(compiler.module.get_function("main").is_none());
+        assert_eq!(compiler.module.get_function("подвоїти").unwrap().get_linkage(), Linkage::External);
+        assert_eq!(compiler.module.get_function("помічник").unwrap().get_linkage(), Linkage::Internal);
+        
+        let lib_path = std::env::temp_dir().join("libtryzub_test_library.a");
+        generate_library(program, lib_path.clone(), LibraryKind::Static).unwrap();
+        let bytes = std::fs::read(&lib_path).unwrap();
+        std::fs::remove_file(&lib_path).ok();
+        assert!(bytes.starts_with(b"!<arch>\n"));
+    }
+    
+    #[test]
+    fn test_compile_bitwise_operators() {
+        let source = r#"
+функція маска(а: цл32, б: цл32) -> цл32 {
+    повернути (а & б) | (а << 2) ^ (б >> 1)
+}
+
+функція головна() {
+    друк(маска(12, 10))
+}
+"#;
+        
+        let tokens = tokenize(source).unwrap();
+        let program = parse(tokens).unwrap();
+        
+        let context = Context::create();
+        let mut compiler = Compiler::new(&context, "test_bitwise");
+        
+        assert!(compiler.compile(program).is_ok());
+        assert!(compiler.module.verify().is_ok());
+        
+        let source = r#"
+функція погана(х: дрб64) -> дрб64 {
+    повернути х & 1.0
+}
+"#;
+        let tokens = tokenize(source).unwrap();
+        let program = parse(tokens).unwrap();
+        
+        let context = Context::create();
+        let mut compiler = Compiler::new(&context, "test_bitwise_float");
+        
+        assert!(compiler.compile(program).is_err());
+    }
+    
+    #[test]
+    fn test_compile_string_concat() {
+        let source = r#"
+функція привітання() -> тхт {
+    повернути "Слава " + "Україні" + "!"
+}
+"#;
+        
+        let tokens = tokenize(source).unwrap();
+        let program = parse(tokens).unwrap();
+        
+        let context = Context::create();
+        let mut compiler = Compiler::new(&context, "test_concat");
+        
+        assert!(compiler.compile(program).is_ok());
+        assert!(compiler.module.verify().is_ok());
+        
+        // Лінкуємо виклики з функціями runtime і виконуємо через JIT
+        let engine = compiler.module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+        let concat = compiler.module.get_function("tryzub_string_concat").unwrap();
+        let free = compiler.module.get_function("tryzub_string_free").unwrap();
+        engine.add_global_mapping(&concat, tryzub_runtime::tryzub_string_concat as usize);
+        engine.add_global_mapping(&free, tryzub_runtime::tryzub_string_free as usize);
+        
+        type Greeting = unsafe extern "C" fn() -> *mut std::os::raw::c_char;
+        let function = unsafe { engine.get_function::<Greeting>("привітання") }.unwrap();
+        let result = unsafe { function.call() };
+        let text = unsafe { std::ffi::CStr::from_ptr(result) }.to_str().unwrap().to_string();
+        unsafe { tryzub_runtime::tryzub_string_free(result) };
+        assert_eq!(text, "Слава Україні!");
+    }
 }
@@ -173,7 +173,7 @@ impl<'a, 'b> AotTrans<'a, 'b> {
                 let ct = if ty == Ty::F { types::F64 } else { types::I64 };
                 let var = self.e.decl(name); self.b.declare_var(var, ct); self.b.def_var(var, v);
             }
-            Statement::Assignment { target, value, op } => {
+            Statement::Assignment { target, value, op, .. } => {
                 if let Expression::Index { object, index } = target {
                     let a = self.expr(object).0; let i = self.expr(index).0; let v = self.expr(value).0;
                     self.rt("__array_set", &[a, i, v]);
@@ -192,7 +192,7 @@ impl<'a, 'b> AotTrans<'a, 'b> {
                     }
                 }
             }
-            Statement::If { condition, then_branch, else_branch } => {
+            Statement::If { condition, then_branch, else_branch, .. } => {
                 let cv = self.expr(condition).0;
                 let tb = self.b.create_block(); let eb = self.b.create_block(); let mb = self.b.create_block();
                 let c = self.b.ins().icmp_imm(IntCC::NotEqual, cv, 0);
@@ -205,7 +205,7 @@ impl<'a, 'b> AotTrans<'a, 'b> {
                 if !self.ret { self.b.ins().jump(mb, &[]); } let er = self.ret; self.ret = false;
                 if tr && er { self.ret = true; } else { self.b.switch_to_block(mb); self.b.seal_block(mb); }
             }
-            Statement::While { condition, body } => {
+            Statement::While { condition, body, .. } => {
                 let h = self.b.create_block(); let bb = self.b.create_block(); let ex = self.b.create_block();
                 self.b.ins().jump(h, &[]); self.b.switch_to_block(h);
                 let cv = self.expr(condition).0;
@@ -253,7 +253,7 @@ impl<'a, 'b> AotTrans<'a, 'b> {
                 if let Some(v) = self.e.get(n) { (self.b.use_var(v), Ty::I) }
                 else { (self.b.ins().iconst(types::I64, 0), Ty::I) }
             }
-            Expression::Binary { left, op, right } => {
+            Expression::Binary { left, op, right, .. } => {
                 let (l, lt) = self.expr(left); let (r, rt) = self.expr(right);
                 if (lt == Ty::S || rt == Ty::S) && matches!(op, BinaryOp::Add) {
                     return (self.rt("__concat", &[l, r]), Ty::S);
@@ -292,7 +292,7 @@ impl<'a, 'b> AotTrans<'a, 'b> {
                     _ => (v, t),
                 }
             }
-            Expression::Call { callee, args } => {
+            Expression::Call { callee, args, .. } => {
                 if let Expression::Identifier(name) = callee.as_ref() {
                     if name == "друк" && args.len() == 1 {
                         let (v, t) = self.expr(&args[0]);
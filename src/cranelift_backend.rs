@@ -346,7 +346,7 @@ impl<'a, 'b> FuncTranslator<'a, 'b> {
                     (self.builder.ins().iconst(types::I64, 0), CrType::I64)
                 }
             }
-            Expression::Binary { left, op, right } => {
+            Expression::Binary { left, op, right, .. } => {
                 let (lhs, lty) = self.translate_expr_typed(left);
                 let (rhs, rty) = self.translate_expr_typed(right);
                 if (lty == CrType::Str || rty == CrType::Str) && matches!(op, BinaryOp::Add) {
@@ -404,7 +404,7 @@ impl<'a, 'b> FuncTranslator<'a, 'b> {
                     _ => (val, ty),
                 }
             }
-            Expression::Call { callee, args } => {
+            Expression::Call { callee, args, .. } => {
                 if let Expression::Identifier(name) = callee.as_ref() {
                     if name == "друк" && args.len() == 1 {
                         let (val, ty) = self.translate_expr_typed(&args[0]);
@@ -451,7 +451,7 @@ impl<'a, 'b> FuncTranslator<'a, 'b> {
                 let val = self.call_runtime("__array_get", &[arr, idx]);
                 (val, CrType::I64)
             }
-            Expression::MethodCall { object, method, args: _ } if method == "довжина" => {
+            Expression::MethodCall { object, method, args: _, .. } if method == "довжина" => {
                 let arr = self.translate_expr(object);
                 let len = self.call_runtime("__array_len", &[arr]);
                 (len, CrType::I64)
@@ -599,7 +599,7 @@ impl<'a, 'b> FuncTranslator<'a, 'b> {
                 self.builder.declare_var(var, cl_ty);
                 self.builder.def_var(var, val);
             }
-            Statement::Assignment { target, value, op } => {
+            Statement::Assignment { target, value, op, .. } => {
                 if let Expression::Index { object, index } = target {
                     let arr = self.translate_expr(object);
                     let idx = self.translate_expr(index);
@@ -635,7 +635,7 @@ impl<'a, 'b> FuncTranslator<'a, 'b> {
                     }
                 }
             }
-            Statement::If { condition, then_branch, else_branch } => {
+            Statement::If { condition, then_branch, else_branch, .. } => {
                 let cond_val = self.translate_expr_typed(condition).0;
                 let then_block = self.builder.create_block();
                 let else_block = self.builder.create_block();
@@ -671,7 +671,7 @@ impl<'a, 'b> FuncTranslator<'a, 'b> {
                     self.builder.seal_block(merge_block);
                 }
             }
-            Statement::While { condition, body } => {
+            Statement::While { condition, body, .. } => {
                 let header = self.builder.create_block();
                 let body_block = self.builder.create_block();
                 let exit = self.builder.create_block();
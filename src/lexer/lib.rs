@@ -1,6 +1,8 @@
 use anyhow::Result;
 use thiserror::Error;
 use std::fmt;
+use unicode_normalization::UnicodeNormalization;
+use unicode_normalization::char::is_combining_mark;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Token {
@@ -24,6 +26,12 @@ pub enum TokenKind {
     // Ідентифікатори
     Ідентифікатор(String),
 
+    /// Коментар документації `/// текст` (парсер приєднує його до наступної декларації)
+    ДокКоментар(String),
+
+    /// Директива виконання `//@назва значення` (парсер збирає їх у `Program::directives`)
+    Директива(String, String),
+
     // ── Ключові слова: оголошення ──
     Змінна,
     Стала,
@@ -53,6 +61,7 @@ pub enum TokenKind {
     Через,
     Переривати,
     Продовжити,
+    Відкласти,     // defer
 
     // ── Ключові слова: модифікатори ──
     Приватний,
@@ -242,6 +251,85 @@ pub enum TokenKind {
     КінецьФайлу,
 }
 
+/// Категорія токена для підсвічування синтаксису в редакторах
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenCategory {
+    КлючовеСлово,
+    Тип,
+    /// Числа, рядки, символи та значення `істина`/`хиба`/`нуль`
+    Літерал,
+    Оператор,
+    Ідентифікатор,
+    /// Дужки, коми, крапки, стрілки та інші розділові знаки
+    Розділювач,
+    Коментар,
+    /// Перенос рядка та кінець файлу — не підсвічуються
+    Службовий,
+}
+
+impl TokenKind {
+    /// Категорія токена. Перелік повний без `_`, тож новий варіант
+    /// `TokenKind` не скомпілюється, доки його не класифікують
+    pub fn category(&self) -> TokenCategory {
+        match self {
+            TokenKind::ЦілеЧисло(_) | TokenKind::ДробовеЧисло(_) | TokenKind::Рядок(_)
+            | TokenKind::ФормРядок(_) | TokenKind::Символ(_) | TokenKind::Логічне(_)
+            | TokenKind::Нуль | TokenKind::Істина | TokenKind::Хиба => TokenCategory::Літерал,
+            TokenKind::Ідентифікатор(_) => TokenCategory::Ідентифікатор,
+            TokenKind::ДокКоментар(_) | TokenKind::Директива(..) => TokenCategory::Коментар,
+            TokenKind::Змінна | TokenKind::Стала | TokenKind::Функція | TokenKind::Повернути
+            | TokenKind::Структура | TokenKind::Модуль | TokenKind::Імпорт | TokenKind::Експорт
+            | TokenKind::Тип | TokenKind::Трейт | TokenKind::Реалізація | TokenKind::Інтерфейс
+            | TokenKind::Реалізує | TokenKind::Якщо | TokenKind::Інакше | TokenKind::Зіставити
+            | TokenKind::Поки | TokenKind::Для | TokenKind::В | TokenKind::Від | TokenKind::До
+            | TokenKind::Через | TokenKind::Переривати | TokenKind::Продовжити | TokenKind::Відкласти
+            | TokenKind::Приватний | TokenKind::Публічний | TokenKind::Статичний
+            | TokenKind::Асинхронний | TokenKind::Чекати | TokenKind::Спробувати
+            | TokenKind::Зловити | TokenKind::Нарешті | TokenKind::Новий | TokenKind::Це
+            | TokenKind::Себе | TokenKind::Супер | TokenKind::Для_Кого | TokenKind::Потік
+            | TokenKind::Канал | TokenKind::Небезпечний | TokenKind::Асемблер | TokenKind::Зовнішній
+            | TokenKind::Розмір | TokenKind::Зміщення | TokenKind::Вирівняний
+            | TokenKind::Упакований | TokenKind::Мінливий | TokenKind::Статичний_Змін
+            | TokenKind::Переривання | TokenKind::КомпЧас | TokenKind::Вбудований
+            | TokenKind::НеВбудований | TokenKind::Гарячий | TokenKind::Холодний | TokenKind::Макрос
+            | TokenKind::Чистий | TokenKind::Ефект | TokenKind::ЗОбробником | TokenKind::Вимагає
+            | TokenKind::Гарантує | TokenKind::Старе | TokenKind::Інваріант
+            | TokenKind::Персистентний | TokenKind::Летючий | TokenKind::Стійкий
+            | TokenKind::Спільний | TokenKind::Можливість | TokenKind::Дозвіл | TokenKind::Пісочниця
+            | TokenKind::Вікно | TokenKind::Стовпець | TokenKind::Рядок_UI | TokenKind::Сітка
+            | TokenKind::Кнопка | TokenKind::ГарячіКлавіші | TokenKind::Віддати | TokenKind::Взяти
+            | TokenKind::Відлагодити | TokenKind::Зупинка | TokenKind::Назад
+            | TokenKind::Розгалужити | TokenKind::ЗнайтиМомент | TokenKind::Тест | TokenKind::Фаз
+            | TokenKind::Бенчмарк | TokenKind::Перевірити | TokenKind::Виміряти | TokenKind::Як
+            | TokenKind::Де => TokenCategory::КлючовеСлово,
+            TokenKind::Цл8 | TokenKind::Цл16 | TokenKind::Цл32 | TokenKind::Цл64 | TokenKind::Чс8
+            | TokenKind::Чс16 | TokenKind::Чс32 | TokenKind::Чс64 | TokenKind::Дрб32
+            | TokenKind::Дрб64 | TokenKind::Лог | TokenKind::Сим | TokenKind::Тхт
+            | TokenKind::ЧсРозм | TokenKind::ЦлРозм | TokenKind::Вказівник | TokenKind::Пусто => TokenCategory::Тип,
+            TokenKind::Плюс | TokenKind::Мінус | TokenKind::Помножити | TokenKind::Поділити
+            | TokenKind::Залишок | TokenKind::Степінь | TokenKind::Дорівнює | TokenKind::НеДорівнює
+            | TokenKind::Менше | TokenKind::Більше | TokenKind::МеншеАбоДорівнює
+            | TokenKind::БільшеАбоДорівнює | TokenKind::І | TokenKind::Або | TokenKind::Не
+            | TokenKind::Присвоїти | TokenKind::ПлюсПрисвоїти | TokenKind::МінусПрисвоїти
+            | TokenKind::ПомножитиПрисвоїти | TokenKind::ПоділитиПрисвоїти
+            | TokenKind::ЗалишокПрисвоїти | TokenKind::БітІ | TokenKind::БітАбо
+            | TokenKind::БітВиключне | TokenKind::БітНе | TokenKind::ЗсувЛіво | TokenKind::ЗсувПраво
+            | TokenKind::БітІПрисвоїти | TokenKind::БітАбоПрисвоїти
+            | TokenKind::БітВиключнеПрисвоїти | TokenKind::ЗсувЛівоПрисвоїти
+            | TokenKind::ЗсувПравоПрисвоїти | TokenKind::Конвеєр | TokenKind::Діапазон
+            | TokenKind::ДіапазонВключ | TokenKind::ЗнакПитання | TokenKind::Зірочка
+            | TokenKind::Решітка | TokenKind::Собака => TokenCategory::Оператор,
+            TokenKind::ЛіваДужка | TokenKind::ПраваДужка | TokenKind::ЛіваФігурна
+            | TokenKind::ПраваФігурна | TokenKind::ЛіваКвадратна | TokenKind::ПраваКвадратна
+            | TokenKind::Крапка | TokenKind::Кома | TokenKind::КрапкаЗКомою | TokenKind::Двокрапка
+            | TokenKind::ПодвійнаДвокрапка | TokenKind::Стрілка | TokenKind::ПодвійнаСтрілка
+            | TokenKind::Амперсанд | TokenKind::Вертикальна | TokenKind::Підкреслення
+            | TokenKind::ДвіКрапки => TokenCategory::Розділювач,
+            TokenKind::НовийРядок | TokenKind::КінецьФайлу => TokenCategory::Службовий,
+        }
+    }
+}
+
 /// Частина форматованого рядка ф"..."
 #[derive(Debug, Clone, PartialEq)]
 pub enum StringPart {
@@ -267,25 +355,80 @@ pub enum LexerError {
     НезавершенаІнтерполяція(usize),
 }
 
+impl LexerError {
+    /// Рядок і позиція помилки; позиція відома лише для невідомого символу
+    pub fn position(&self) -> (usize, Option<usize>) {
+        match self {
+            LexerError::НевідомийСимвол(_, line, column) => (*line, Some(*column)),
+            LexerError::НезавершенийРядок(line)
+            | LexerError::НеправильнеЧисло(_, line)
+            | LexerError::НезавершенийКоментар(line)
+            | LexerError::НезавершенаІнтерполяція(line) => (*line, None),
+        }
+    }
+}
+
+/// Попередження лексера: не зупиняє розбір, лише повідомляє про підозрілий код
+#[derive(Debug, Clone, PartialEq)]
+pub struct LexerWarning {
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for LexerWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "рядок {}, позиція {}: {}", self.line, self.column, self.message)
+    }
+}
+
 pub struct Lexer {
     input: Vec<char>,
     current: usize,
     line: usize,
     column: usize,
     tokens: Vec<Token>,
+    /// Попереджати про ідентифікатори, що змішують кирилицю й латиницю
+    check_mixed_scripts: bool,
+    /// Видавати `НовийРядок` замість пропуску переносів рядка
+    emit_newlines: bool,
+    warnings: Vec<LexerWarning>,
 }
 
 impl Lexer {
+    /// Початковий BOM (U+FEFF) відкидається, а `\r\n` зводиться до `\n`, тож файли
+    /// з Windows дають ті самі токени, позиції й вміст рядкових літералів
     pub fn new(input: &str) -> Self {
+        let input = input.strip_prefix('\u{FEFF}').unwrap_or(input);
         Self {
-            input: input.chars().collect(),
+            input: input.replace("\r\n", "\n").chars().collect(),
             current: 0,
             line: 1,
             column: 1,
             tokens: Vec::new(),
+            check_mixed_scripts: false,
+            emit_newlines: false,
+            warnings: Vec::new(),
         }
     }
 
+    /// Вмикає попередження про ідентифікатори на кшталт `знaчення` з латинською `a`
+    pub fn warn_mixed_scripts(mut self, enabled: bool) -> Self {
+        self.check_mixed_scripts = enabled;
+        self
+    }
+
+    /// Вмикає токени `НовийРядок`, щоб парсер міг завершувати оператори переносом рядка.
+    /// Кілька переносів поспіль дають один токен
+    pub fn emit_newlines(mut self, enabled: bool) -> Self {
+        self.emit_newlines = enabled;
+        self
+    }
+
+    pub fn warnings(&self) -> &[LexerWarning] {
+        &self.warnings
+    }
+
     pub fn tokenize(&mut self) -> Result<Vec<Token>> {
         while !self.is_at_end() {
             self.skip_whitespace();
@@ -325,6 +468,13 @@ impl Lexer {
             ',' => Ok(Some(self.make_token(TokenKind::Кома, start_column))),
             ';' => Ok(Some(self.make_token(TokenKind::КрапкаЗКомою, start_column))),
             '?' => Ok(Some(self.make_token(TokenKind::ЗнакПитання, start_column))),
+            '\n' => {
+                let repeated = matches!(self.tokens.last(), None | Some(Token { kind: TokenKind::НовийРядок, .. }));
+                let token = self.make_token(TokenKind::НовийРядок, start_column);
+                self.line += 1;
+                self.column = 1;
+                Ok(if repeated { None } else { Some(token) })
+            }
             '#' => Ok(Some(self.make_token(TokenKind::Решітка, start_column))),
             '@' => Ok(Some(self.make_token(TokenKind::Собака, start_column))),
             '_' if !self.peek().is_alphanumeric() => {
@@ -381,6 +531,32 @@ impl Lexer {
             }
             '/' => {
                 if self.match_char('/') {
+                    // Документація: рівно три слеші (`////` — звичайний коментар)
+                    if self.peek() == '/' && self.peek_next() != '/' {
+                        self.advance();
+                        let mut text = String::new();
+                        while self.peek() != '\n' && !self.is_at_end() {
+                            text.push(self.advance());
+                        }
+                        let text = text.trim().to_string();
+                        let mut token = self.make_token(TokenKind::ДокКоментар(text.clone()), start_column);
+                        token.lexeme = format!("///{}", text);
+                        return Ok(Some(token));
+                    }
+                    // Директива: `//@назва значення`
+                    if self.peek() == '@' {
+                        self.advance();
+                        let mut text = String::new();
+                        while self.peek() != '\n' && !self.is_at_end() {
+                            text.push(self.advance());
+                        }
+                        let text = text.trim().to_string();
+                        let (name, value) = text.split_once(char::is_whitespace).unwrap_or((&text, ""));
+                        let kind = TokenKind::Директива(name.to_string(), value.trim().to_string());
+                        let mut token = self.make_token(kind, start_column);
+                        token.lexeme = format!("//@{}", text);
+                        return Ok(Some(token));
+                    }
                     // Однорядковий коментар
                     while self.peek() != '\n' && !self.is_at_end() {
                         self.advance();
@@ -499,36 +675,10 @@ impl Lexer {
     }
 
     fn scan_string(&mut self, start_column: usize) -> Result<Option<Token>> {
-        // Multi-line string: """..."""
         if self.peek() == '"' && self.peek_next() == '"' {
-            self.advance(); // skip 2nd "
-            self.advance(); // skip 3rd "
-            let mut value = String::new();
-            loop {
-                if self.is_at_end() {
-                    return Err(anyhow::anyhow!("Незакритий багаторядковий рядок на рядку {}", self.line));
-                }
-                if self.peek() == '"' && self.peek_next() == '"' {
-                    let saved = self.current;
-                    self.advance();
-                    if self.peek() == '"' {
-                        self.advance(); // skip 3rd closing "
-                        break;
-                    }
-                    self.current = saved;
-                }
-                if self.peek() == '\n' { self.line += 1; self.column = 0; }
-                value.push(self.advance());
-            }
-            // Strip leading newline and common indentation
-            let trimmed = if value.starts_with('\n') { &value[1..] } else { &value[..] };
-            let result = trimmed.to_string();
-            return Ok(Some(Token {
-                kind: TokenKind::Рядок(result.clone()),
-                lexeme: result,
-                line: self.line,
-                column: start_column,
-            }));
+            self.advance(); // Друга лапка
+            self.advance(); // Третя лапка
+            return self.scan_multiline_string(start_column);
         }
 
         let mut value = String::new();
@@ -538,7 +688,10 @@ impl Lexer {
                 self.line += 1;
                 self.column = 0;
             }
-            if self.peek() == '\\' {
+            if self.peek() == '\\' && self.at_line_continuation() {
+                self.advance();
+                self.skip_line_continuation();
+            } else if self.peek() == '\\' {
                 self.advance();
                 let escaped = match self.peek() {
                     'n' => '\n',
@@ -570,6 +723,67 @@ impl Lexer {
         }))
     }
 
+    /// Чи стоїть `\` просто перед переносом рядка (продовження рядка)
+    fn at_line_continuation(&self) -> bool {
+        match self.input.get(self.current + 1) {
+            Some('\n') => true,
+            Some('\r') => self.input.get(self.current + 2) == Some(&'\n'),
+            _ => false,
+        }
+    }
+
+    /// Пропускає перенос після `\` разом з початковими пробілами наступного рядка
+    fn skip_line_continuation(&mut self) {
+        if self.peek() == '\r' {
+            self.advance();
+        }
+        self.line += 1;
+        self.column = 0;
+        self.advance(); // '\n'
+        while matches!(self.peek(), ' ' | '\t') && !self.is_at_end() {
+            self.advance();
+        }
+    }
+
+    /// Сканує багаторядковий рядок """текст""" — вміст береться дослівно,
+    /// без екранування, крім `\` в кінці рядка, що склеює його з наступним
+    /// без початкових пробілів; перший перенос одразу після лапок відкидається
+    fn scan_multiline_string(&mut self, start_column: usize) -> Result<Option<Token>> {
+        let start_line = self.line;
+        let mut value = String::new();
+
+        loop {
+            if self.is_at_end() {
+                return Err(LexerError::НезавершенийРядок(start_line).into());
+            }
+            if self.peek() == '"' && self.peek_next() == '"' && self.input.get(self.current + 2) == Some(&'"') {
+                self.advance();
+                self.advance();
+                self.advance();
+                break;
+            }
+            if self.peek() == '\\' && self.at_line_continuation() {
+                self.advance();
+                self.skip_line_continuation();
+                continue;
+            }
+            if self.peek() == '\n' {
+                self.line += 1;
+                self.column = 0;
+            }
+            value.push(self.advance());
+        }
+
+        let value = value.strip_prefix('\n').map(str::to_string).unwrap_or(value);
+
+        Ok(Some(Token {
+            kind: TokenKind::Рядок(value.clone()),
+            lexeme: value,
+            line: start_line,
+            column: start_column,
+        }))
+    }
+
     /// Сканує форматований рядок ф"текст {вираз} текст"
     fn scan_format_string(&mut self, start_column: usize) -> Result<Option<Token>> {
         let mut parts = Vec::new();
@@ -823,10 +1037,20 @@ impl Lexer {
         let mut value = String::new();
         value.push(self.previous());
 
-        while self.peek().is_alphanumeric() || self.peek() == '_' || self.peek() == '\'' {
+        // Комбіновані знаки (бреве в декомпозитній `й`) — частина ідентифікатора
+        while self.peek().is_alphanumeric() || self.peek() == '_' || self.peek() == '\''
+            || is_combining_mark(self.peek())
+        {
             value.push(self.advance());
         }
 
+        // NFC: `й` як одна кодова точка і як `и` + U+0306 дають один ідентифікатор
+        let value: String = value.nfc().collect();
+
+        if self.check_mixed_scripts {
+            self.check_identifier_scripts(&value, start_column);
+        }
+
         let kind = match value.as_str() {
             // Оголошення
             "змінна" => TokenKind::Змінна,
@@ -857,6 +1081,7 @@ impl Lexer {
             "через" => TokenKind::Через,
             "переривати" => TokenKind::Переривати,
             "продовжити" => TokenKind::Продовжити,
+            "відкласти" => TokenKind::Відкласти,
 
             // Модифікатори
             "приватний" => TokenKind::Приватний,
@@ -990,7 +1215,7 @@ impl Lexer {
                 ' ' | '\r' | '\t' => {
                     self.advance();
                 }
-                '\n' => {
+                '\n' if !self.emit_newlines => {
                     self.line += 1;
                     self.column = 0;
                     self.advance();
@@ -1082,6 +1307,7 @@ impl Lexer {
             TokenKind::Амперсанд => "&",
             TokenKind::Вертикальна => "|",
             TokenKind::Підкреслення => "_",
+            TokenKind::НовийРядок => "\n",
             _ => "",
         }.to_string()
     }
@@ -1132,6 +1358,25 @@ impl Lexer {
     fn is_at_end(&self) -> bool {
         self.current >= self.input.len()
     }
+
+    fn check_identifier_scripts(&mut self, ident: &str, column: usize) {
+        let is_cyrillic = |c: char| ('\u{0400}'..='\u{052F}').contains(&c);
+        let is_latin = |c: char| c.is_ascii_alphabetic() || ('\u{00C0}'..='\u{024F}').contains(&c);
+        if !ident.chars().any(is_cyrillic) {
+            return;
+        }
+        let latin: String = ident.chars().filter(|&c| is_latin(c)).collect();
+        if !latin.is_empty() {
+            self.warnings.push(LexerWarning {
+                message: format!(
+                    "ідентифікатор '{}' змішує кириличні й латинські літери (латинські: {})",
+                    ident, latin
+                ),
+                line: self.line,
+                column,
+            });
+        }
+    }
 }
 
 pub fn tokenize(source: &str) -> Result<Vec<Token>> {
@@ -1149,6 +1394,106 @@ impl fmt::Display for Token {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_directive_comment() {
+        let tokens = tokenize("//@таймаут 5с\n//@ліміт_памʼяті  10 МБ\n// звичайний\nх").unwrap();
+        assert_eq!(tokens[0].kind, TokenKind::Директива("таймаут".to_string(), "5с".to_string()));
+        assert_eq!(tokens[1].kind, TokenKind::Директива("ліміт_памʼяті".to_string(), "10 МБ".to_string()));
+        assert_eq!(tokens[1].line, 2);
+        assert_eq!(tokens[2].kind, TokenKind::Ідентифікатор("х".to_string()));
+    }
+
+    #[test]
+    fn test_bom_is_ignored() {
+        let tokens = tokenize("\u{FEFF}змінна х = 1").unwrap();
+        assert_eq!(tokens[0].kind, TokenKind::Змінна);
+        assert_eq!((tokens[0].line, tokens[0].column), (1, 1));
+    }
+
+    #[test]
+    fn test_crlf_line_endings() {
+        let tokens = tokenize("змінна х = 1\r\nзмінна у = \"\"\"а\r\nб\"\"\"\r\n\r\nх").unwrap();
+        assert_eq!((tokens[4].line, tokens[4].column), (2, 1));
+        assert_eq!(tokens[7].kind, TokenKind::Рядок("а\nб".to_string()));
+        let last = &tokens[tokens.len() - 2];
+        assert_eq!(last.kind, TokenKind::Ідентифікатор("х".to_string()));
+        assert_eq!((last.line, last.column), (5, 1));
+    }
+
+    #[test]
+    fn test_token_categories() {
+        let tokens = tokenize("/// док\nфункція ф(х: цл64) -> лог { повернути х >= 1 && істина }").unwrap();
+        let categories: Vec<(String, TokenCategory)> = tokens.iter()
+            .map(|t| (t.lexeme.clone(), t.kind.category()))
+            .collect();
+        let category_of = |lexeme: &str| categories.iter()
+            .find(|(text, _)| text == lexeme)
+            .map(|(_, c)| *c)
+            .unwrap_or_else(|| panic!("Токен '{}' не знайдено", lexeme));
+        assert_eq!(category_of("функція"), TokenCategory::КлючовеСлово);
+        assert_eq!(category_of("повернути"), TokenCategory::КлючовеСлово);
+        assert_eq!(category_of("цл64"), TokenCategory::Тип);
+        assert_eq!(category_of("лог"), TokenCategory::Тип);
+        assert_eq!(category_of("1"), TokenCategory::Літерал);
+        assert_eq!(category_of("істина"), TokenCategory::Літерал);
+        assert_eq!(category_of(">="), TokenCategory::Оператор);
+        assert_eq!(category_of("&&"), TokenCategory::Оператор);
+        assert_eq!(category_of("х"), TokenCategory::Ідентифікатор);
+        assert_eq!(category_of("->"), TokenCategory::Розділювач);
+        assert_eq!(category_of("{"), TokenCategory::Розділювач);
+        assert_eq!(tokens[0].kind.category(), TokenCategory::Коментар);
+        assert_eq!(tokens.last().unwrap().kind.category(), TokenCategory::Службовий);
+        assert_eq!(TokenKind::Рядок("а".to_string()).category(), TokenCategory::Літерал);
+    }
+
+    #[test]
+    fn test_identifier_nfc() {
+        let composed = tokenize("змінна мій = 1").unwrap();
+        // `и` + комбіноване бреве U+0306
+        let decomposed = tokenize("змінна мі\u{0438}\u{0306} = 1").unwrap();
+        assert_eq!(composed[1].kind, TokenKind::Ідентифікатор("мій".to_string()));
+        assert_eq!(decomposed[1].kind, composed[1].kind);
+        assert_eq!(decomposed[1].lexeme, "мій");
+    }
+
+    #[test]
+    fn test_mixed_script_warning() {
+        // `a` у `знaчення` — латинська
+        let mut lexer = Lexer::new("змінна лічильник = 1\nзмінна знaчення = 2").warn_mixed_scripts(true);
+        lexer.tokenize().unwrap();
+        assert_eq!(lexer.warnings().len(), 1);
+        let warning = &lexer.warnings()[0];
+        assert_eq!((warning.line, warning.column), (2, 8));
+        assert!(warning.message.contains("знaчення"));
+
+        let mut lexer = Lexer::new("змінна значення = 2\nзмінна x = 1").warn_mixed_scripts(true);
+        lexer.tokenize().unwrap();
+        assert!(lexer.warnings().is_empty());
+
+        let mut lexer = Lexer::new("змінна знaчення = 2");
+        lexer.tokenize().unwrap();
+        assert!(lexer.warnings().is_empty());
+    }
+
+    #[test]
+    fn test_newline_tokens() {
+        let source = "змінна а = 1\n\n\nдрук(а)\n";
+        let kinds = |tokens: Vec<Token>| -> Vec<TokenKind> { tokens.into_iter().map(|t| t.kind).collect() };
+
+        let tokens = Lexer::new(source).emit_newlines(true).tokenize().unwrap();
+        let newlines: Vec<&Token> = tokens.iter().filter(|t| t.kind == TokenKind::НовийРядок).collect();
+        assert_eq!(newlines.len(), 2);
+        assert_eq!(newlines[0].line, 1);
+        assert_eq!(tokens[5].kind, TokenKind::Ідентифікатор("друк".to_string()));
+        assert_eq!(tokens[5].line, 4);
+
+        // Без прапора переноси пропускаються, як і раніше
+        let plain = kinds(tokenize(source).unwrap());
+        assert!(!plain.contains(&TokenKind::НовийРядок));
+        let filtered: Vec<TokenKind> = kinds(tokens).into_iter().filter(|k| *k != TokenKind::НовийРядок).collect();
+        assert_eq!(filtered, plain);
+    }
+
     #[test]
     fn test_basic_tokens() {
         let source = "змінна x = 10";
@@ -1163,6 +1508,35 @@ mod tests {
         assert_eq!(tokens.len(), 5);
     }
 
+    #[test]
+    fn test_multiline_string() {
+        let source = "змінна т = \"\"\"\nперший \\n\n  другий \"лапки\"\n\"\"\"\nзмінна к";
+        let tokens = tokenize(source).unwrap();
+        assert_eq!(tokens[3].kind, TokenKind::Рядок("перший \\n\n  другий \"лапки\"\n".to_string()));
+        assert_eq!(tokens[3].line, 1);
+        assert_eq!(tokens[4].kind, TokenKind::Змінна);
+        assert_eq!(tokens[4].line, 5);
+
+        let tokens = tokenize("\"\"\"\"\"\"").unwrap();
+        assert_eq!(tokens[0].kind, TokenKind::Рядок(String::new()));
+    }
+
+    #[test]
+    fn test_string_line_continuation() {
+        let source = "змінна т = \"\"\"\nперший \\\n    другий \\n\n\"\"\"\nзмінна р = \"а\\\r\n\tб\\t\"\nзмінна к";
+        let tokens = tokenize(source).unwrap();
+        assert_eq!(tokens[3].kind, TokenKind::Рядок("перший другий \\n\n".to_string()));
+        assert_eq!(tokens[7].kind, TokenKind::Рядок("аб\t".to_string()));
+        assert_eq!(tokens[8].kind, TokenKind::Змінна);
+        assert_eq!(tokens[8].line, 7);
+    }
+
+    #[test]
+    fn test_unterminated_multiline_string() {
+        let err = tokenize("змінна т = 1\nзмінна р = \"\"\"рядок\nбез кінця\"\"").unwrap_err();
+        assert!(matches!(err.downcast_ref::<LexerError>(), Some(LexerError::НезавершенийРядок(2))));
+    }
+
     #[test]
     fn test_function() {
         let source = "функція додати(а: цл32, б: цл32) -> цл32 { повернути а + б }";
@@ -1246,4 +1620,14 @@ mod tests {
         let tokens = tokenize("модуль::функція").unwrap();
         assert!(tokens.iter().any(|t| t.kind == TokenKind::ПодвійнаДвокрапка));
     }
+
+    #[test]
+    fn test_doc_comment() {
+        let tokens = tokenize("/// Документація\n// звичайний\n//// теж звичайний\nзмінна").unwrap();
+        let docs: Vec<_> = tokens.iter()
+            .filter_map(|t| match &t.kind { TokenKind::ДокКоментар(text) => Some(text.as_str()), _ => None })
+            .collect();
+        assert_eq!(docs, vec!["Документація"]);
+        assert!(tokens.iter().any(|t| t.kind == TokenKind::Змінна));
+    }
 }
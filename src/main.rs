@@ -8,6 +8,10 @@ use anyhow::Result;
 use std::path::PathBuf;
 use std::fs;
 
+mod build;
+mod report;
+use report::{ErrorKind, Location};
+
 #[cfg(feature = "cranelift-backend")]
 mod cranelift_backend;
 #[cfg(feature = "cranelift-backend")]
@@ -28,7 +32,7 @@ enum Commands {
     /// Запустити файл через VM
     #[command(name = "запустити")]
     Run {
-        /// Файл для запуску
+        /// Файл для запуску (`-` — читати програму зі stdin)
         #[arg(value_name = "ФАЙЛ")]
         file: PathBuf,
 
@@ -44,15 +48,43 @@ enum Commands {
         #[arg(long = "нативно", default_value = "false")]
         cranelift: bool,
 
+        /// Перезапускати програму при зміні файлу
+        #[arg(long = "стежити", default_value = "false")]
+        watch: bool,
+
+        /// Підрахувати виклики функцій і виконання рядків та вивести звіт
+        #[arg(long = "профіль", default_value = "false")]
+        profile: bool,
+
+        /// Друкувати кожен виконаний оператор і значення виразів
+        #[arg(long = "трасувати", default_value = "false")]
+        trace: bool,
+
         /// Аргументи програми
         #[arg(trailing_var_arg = true)]
         args: Vec<String>,
     },
 
+    /// Розібрати файл і показати AST або зберегти його у бінарний `.тризубс`
+    #[command(name = "розібрати")]
+    Parse {
+        /// Файл для розбору
+        #[arg(value_name = "ФАЙЛ")]
+        file: PathBuf,
+
+        /// Зберегти AST у `.тризубс`, який `запустити` виконує без повторного парсингу
+        #[arg(long = "зберегти-аст", default_value = "false")]
+        save_ast: bool,
+
+        /// Вихідний файл (за замовчуванням — поруч із вихідним)
+        #[arg(short = 'о', long = "вихід")]
+        output: Option<PathBuf>,
+    },
+
     /// Перевірити синтаксис файлу
     #[command(name = "перевірити")]
     Check {
-        /// Файл для перевірки
+        /// Файл для перевірки (`-` — читати програму зі stdin)
         #[arg(value_name = "ФАЙЛ")]
         file: PathBuf,
     },
@@ -103,13 +135,16 @@ enum Commands {
         iterations: u64,
     },
 
-    /// Генерувати HTML документацію
-    #[command(name = "док")]
+    /// Генерувати HTML або Markdown документацію
+    #[command(name = "док", alias = "документація")]
     Doc {
         #[arg(value_name = "ШЛЯХ")]
         path: PathBuf,
         #[arg(long = "вивід", default_value = "docs")]
         output: PathBuf,
+        /// Формат виводу: html або md
+        #[arg(long = "формат", default_value = "html")]
+        format: String,
     },
 
     /// Профілювання програми
@@ -170,6 +205,30 @@ enum Commands {
         /// Cranelift AOT компіляція в standalone бінарник
         #[arg(long = "cranelift", default_value = "false")]
         cranelift_aot: bool,
+
+        /// LLVM-компіляція з налагоджувальною інформацією DWARF (gdb/lldb)
+        #[arg(short = 'g', long = "налагодження", visible_alias = "g", default_value = "false")]
+        debug: bool,
+
+        /// Що збирати через LLVM: `виконуваний`, `ліба` (статична .a/.lib) чи `дліба` (динамічна .so/.dll)
+        #[arg(long = "тип", default_value = "виконуваний")]
+        kind: String,
+    },
+
+    /// Зібрати проект у `ціль/`, перезбираючи лише змінені файли
+    #[command(name = "зібрати")]
+    Build {
+        /// Тека з `проект.toml`
+        #[arg(value_name = "ТЕКА", default_value = ".")]
+        dir: PathBuf,
+    },
+
+    /// Згенерувати C-заголовок для FFI-функцій рантайму (tryzub_*)
+    #[command(name = "заголовок")]
+    Header {
+        /// Вихідний файл (за замовчуванням tryzub.h)
+        #[arg(short = 'о', long = "вихід")]
+        output: Option<PathBuf>,
     },
 
     /// Показати версію та інформацію
@@ -210,7 +269,7 @@ fn main() {
     if let Some(source) = extract_embedded_source() {
         let result = run_embedded_source(&source);
         if let Err(e) = result {
-            eprintln!("\x1b[1;31m[X] {}\x1b[0m", e);
+            report::report_failure(&e.to_string());
             std::process::exit(1);
         }
         return;
@@ -222,19 +281,35 @@ fn main() {
         Commands::Lsp => run_lsp(),
         Commands::Format { file, check_only } => run_format(file, check_only),
         Commands::Lint { file } => run_lint(file),
-        Commands::Doc { path, output } => run_doc(path, output),
+        Commands::Doc { path, output, format } => match format.as_str() {
+            "html" => run_doc(path, output),
+            "md" | "markdown" => run_doc_markdown(path, output),
+            other => Err(anyhow::anyhow!("Невідомий формат документації '{}': очікується html або md", other)),
+        },
         Commands::Install { package } => run_install(package),
         Commands::Update => run_update(),
-        Commands::Run { file, fast, jit, cranelift, args } => run_file(file, fast, jit, cranelift, args),
-        Commands::Watch { file } => watch_file(file),
-        Commands::Compile { file, output, native, kernel, cranelift_aot } => compile_file(file, output, native, kernel, cranelift_aot),
+        Commands::Run { file, watch: true, fast, jit, cranelift, profile, trace, args } => {
+            let flags = [(fast, "--швидко"), (jit, "--jit"), (cranelift, "--нативно"), (profile, "--профіль"), (trace, "--трасувати")]
+                .into_iter()
+                .filter_map(|(on, flag)| on.then_some(flag))
+                .collect();
+            watch_file(file, flags, args)
+        }
+        Commands::Run { file, fast, jit, cranelift, profile, trace, args, .. } => {
+            run_file(file, fast, jit, cranelift, profile, trace, args)
+        }
+        Commands::Watch { file } => watch_file(file, vec![], vec![]),
+        Commands::Compile { file, output, native, kernel, cranelift_aot, debug, kind } => {
+            compile_file(file, output, native, kernel, cranelift_aot, debug, &kind)
+        }
+        Commands::Parse { file, save_ast, output } => parse_file(file, save_ast, output),
         Commands::Check { file } => check_file(file),
         Commands::Test { file } => run_tests(file),
         Commands::New { name } => create_project(name),
         Commands::Repl => run_repl(),
         Commands::Web { action } => match action {
             WebCommands::New { name } => create_web_project(name),
-            WebCommands::Run { file, port } => run_file(file, false, false, false, vec![port.to_string()]),
+            WebCommands::Run { file, port } => run_file(file, false, false, false, false, false, vec![port.to_string()]),
             WebCommands::Playground { port } => run_playground(port),
         },
         Commands::Benchmark { iterations } => {
@@ -279,6 +354,8 @@ fn main() {
             Ok(())
         }
         Commands::Profile { file } => profile_file(file),
+        Commands::Build { dir } => build_project(dir),
+        Commands::Header { output } => write_c_header(output),
         Commands::Version => {
             println!("Тризуб v9.0.0");
             println!("Ліцензія: MIT");
@@ -288,41 +365,11 @@ fn main() {
     };
 
     if let Err(e) = result {
-        eprintln!("\x1b[1;31m[X] {}\x1b[0m", e);
+        report::report_failure(&e.to_string());
         std::process::exit(1);
     }
 }
 
-fn format_error_with_source(source: &str, file: &std::path::Path, error: &str) -> String {
-    let line_num = extract_line_number(error);
-    if line_num == 0 {
-        return error.to_string();
-    }
-    let lines: Vec<&str> = source.lines().collect();
-    let mut out = String::new();
-    out.push_str(&format!("\x1b[1;31mПомилка\x1b[0m: {}\n", error));
-    out.push_str(&format!(" \x1b[36m-->\x1b[0m {}:{}\n", file.display(), line_num));
-    out.push_str("  \x1b[36m|\x1b[0m\n");
-    let start = line_num.saturating_sub(2);
-    let end = std::cmp::min(line_num + 1, lines.len());
-    for i in start..end {
-        let marker = if i + 1 == line_num { "\x1b[1;31m>\x1b[0m" } else { " " };
-        let num_color = if i + 1 == line_num { "\x1b[1;31m" } else { "\x1b[36m" };
-        out.push_str(&format!("{} {}{:>4}\x1b[0m \x1b[36m|\x1b[0m {}\n", marker, num_color, i + 1, lines[i]));
-    }
-    out.push_str("  \x1b[36m|\x1b[0m\n");
-    out
-}
-
-fn extract_line_number(error: &str) -> usize {
-    if let Some(pos) = error.rfind("рядку ") {
-        let after = &error[pos + "рядку ".len()..];
-        let num_str: String = after.chars().take_while(|c| c.is_ascii_digit()).collect();
-        return num_str.parse().unwrap_or(0);
-    }
-    0
-}
-
 fn profile_file(file: PathBuf) -> Result<()> {
     let source = fs::read_to_string(&file)
         .map_err(|e| anyhow::anyhow!("Не вдалося прочитати файл {:?}: {}", file, e))?;
@@ -348,9 +395,27 @@ fn profile_file(file: PathBuf) -> Result<()> {
     Ok(())
 }
 
-fn run_file(file: PathBuf, fast: bool, jit: bool, cranelift: bool, args: Vec<String>) -> Result<()> {
-    let source = fs::read_to_string(&file)
-        .map_err(|e| anyhow::anyhow!("Не вдалося прочитати файл {:?}: {}", file, e))?;
+/// Текст програми з файлу або, якщо шлях `-`, зі stdin
+fn read_source(file: &std::path::Path) -> Result<String> {
+    if file.as_os_str() == "-" {
+        let mut source = String::new();
+        std::io::Read::read_to_string(&mut std::io::stdin(), &mut source)
+            .map_err(|e| anyhow::anyhow!("Не вдалося прочитати програму зі stdin: {}", e))?;
+        return Ok(source);
+    }
+    fs::read_to_string(file)
+        .map_err(|e| anyhow::anyhow!("Не вдалося прочитати файл {:?}: {}", file, e))
+}
+
+/// Розбирає програму для запуску; файл без `головна` загортається в неї.
+/// Збережений AST `.тризубс` завантажується без лексера й парсера
+fn load_program(file: &std::path::Path) -> Result<tryzub_parser::Program> {
+    if file.extension().is_some_and(|e| e == tryzub_parser::binary::EXTENSION) {
+        let bytes = fs::read(file)
+            .map_err(|e| anyhow::anyhow!("Не вдалося прочитати файл {:?}: {}", file, e))?;
+        return tryzub_parser::binary::deserialize(&bytes);
+    }
+    let source = read_source(file)?;
 
     let has_main = source.contains("функція головна(") || source.contains("функція головна (");
     let has_declarations = source.lines().any(|l| {
@@ -363,21 +428,32 @@ fn run_file(file: PathBuf, fast: bool, jit: bool, cranelift: bool, args: Vec<Str
         format!("функція головна() {{\n{}\n}}", source)
     };
 
-    let tokens = match tryzub_lexer::tokenize(&effective_source) {
+    // Обгортка `функція головна() {` зсуває рядки на один
+    let line_offset = if has_main || has_declarations { 0 } else { 1 };
+    let mut tokens = match tryzub_lexer::tokenize(&effective_source) {
         Ok(t) => t,
         Err(e) => {
-            eprint!("{}", format_error_with_source(&source, &file, &e.to_string()));
+            report::report_error(ErrorKind::Lexical, &e.to_string(), file, Location::of_syntax_error(&e), Some(&source), line_offset);
             std::process::exit(1);
         }
     };
+    // Рядки токенів повертаємо до рядків файлу, щоб позиції в AST (покриття,
+    // трасування, помилки) вказували на код користувача, а не на обгортку
+    for token in &mut tokens {
+        token.line = token.line.saturating_sub(line_offset);
+    }
 
-    let ast = match tryzub_parser::parse(tokens) {
-        Ok(a) => a,
+    match tryzub_parser::parse(tokens) {
+        Ok(a) => Ok(a),
         Err(e) => {
-            eprint!("{}", format_error_with_source(&source, &file, &e.to_string()));
+            report::report_error(ErrorKind::Syntax, &e.to_string(), file, Location::of_syntax_error(&e), Some(&source), 0);
             std::process::exit(1);
         }
-    };
+    }
+}
+
+fn run_file(file: PathBuf, fast: bool, jit: bool, cranelift: bool, profile: bool, trace: bool, args: Vec<String>) -> Result<()> {
+    let ast = load_program(&file)?.configured();
 
     if cranelift {
         #[cfg(feature = "cranelift-backend")]
@@ -427,30 +503,139 @@ fn run_file(file: PathBuf, fast: bool, jit: bool, cranelift: bool, args: Vec<Str
         Ok(())
     } else {
         let file_parent = file.parent().map(|p| p.to_path_buf());
+        let dependencies = match find_project_manifest(&file) {
+            Some(manifest) => read_local_dependencies(&manifest)?,
+            None => Vec::new(),
+        };
         let handle = std::thread::Builder::new()
             .name("tryzub-vm".into())
             .stack_size(64 * 1024 * 1024)
             .spawn(move || {
                 let mut vm = tryzub_vm::VM::new();
+                for (_, dir) in &dependencies {
+                    vm.add_module_path(dir.to_string_lossy().to_string());
+                }
                 if let Some(parent) = file_parent {
                     vm.add_module_path(parent.to_string_lossy().to_string());
                 }
-                vm.execute_program(ast, args)
+                if profile {
+                    vm.enable_coverage();
+                }
+                if trace {
+                    vm.enable_trace();
+                }
+                let result = vm.execute_program(ast, args);
+                if profile {
+                    eprint!("\n{}", vm.coverage_report());
+                }
+                result
             })
             .map_err(|e| anyhow::anyhow!("Не вдалося створити потік: {}", e))?;
-        handle.join().unwrap_or_else(|_| Err(anyhow::anyhow!("VM паніка")))
+        let result = handle.join().unwrap_or_else(|_| Err(anyhow::anyhow!("VM паніка")));
+        if let Err(e) = &result {
+            // Фрагмента коду немає для stdin і збереженого AST
+            let source = fs::read_to_string(&file).ok();
+            report::report_error(ErrorKind::Runtime, &e.to_string(), &file, None, source.as_deref(), 0);
+            std::process::exit(1);
+        }
+        result
     }
 }
 
-fn compile_file(file: PathBuf, output: Option<PathBuf>, native: bool, kernel: bool, cranelift_aot_flag: bool) -> Result<()> {
+/// Найближчий `проект.toml` у теці файлу або вище
+fn find_project_manifest(file: &std::path::Path) -> Option<PathBuf> {
+    file.parent()?
+        .ancestors()
+        .map(|dir| dir.join("проект.toml"))
+        .find(|manifest| manifest.is_file())
+}
+
+/// Локальні залежності з секції `[залежності]` маніфесту: `ім'я = { шлях = "../ліба" }`.
+/// Шляхи розв'язуються відносно теки маніфесту
+fn read_local_dependencies(manifest: &std::path::Path) -> Result<Vec<(String, PathBuf)>> {
+    let content = fs::read_to_string(manifest)
+        .map_err(|e| anyhow::anyhow!("Не вдалося прочитати {:?}: {}", manifest, e))?;
+    let base = manifest.parent().unwrap_or(std::path::Path::new("."));
+
+    let mut deps = Vec::new();
+    let mut in_deps = false;
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if trimmed.starts_with('[') {
+            in_deps = trimmed == "[залежності]" || trimmed == "[dependencies]";
+            continue;
+        }
+        if !in_deps {
+            continue;
+        }
+
+        let (name, spec) = trimmed.split_once('=')
+            .ok_or_else(|| anyhow::anyhow!("{:?}: очікується `ім'я = {{ шлях = \"...\" }}`, отримано: {}", manifest, trimmed))?;
+        let name = name.trim().trim_matches('"').to_string();
+        let table = spec.trim().strip_prefix('{').and_then(|s| s.strip_suffix('}'))
+            .ok_or_else(|| anyhow::anyhow!("Залежність '{}': підтримуються лише локальні шляхи `{{ шлях = \"...\" }}`", name))?;
+        let path = table.split(',')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(key, _)| matches!(key.trim(), "шлях" | "path"))
+            .map(|(_, value)| value.trim().trim_matches('"').to_string())
+            .ok_or_else(|| anyhow::anyhow!("Залежність '{}': не вказано `шлях`", name))?;
+
+        let dir = base.join(&path);
+        if !dir.is_dir() {
+            return Err(anyhow::anyhow!("Залежність '{}': тека {:?} не існує", name, dir));
+        }
+        deps.push((name, dir));
+    }
+    Ok(deps)
+}
+
+fn compile_file(file: PathBuf, output: Option<PathBuf>, native: bool, kernel: bool, cranelift_aot_flag: bool, debug: bool, kind: &str) -> Result<()> {
     let source = fs::read_to_string(&file)
         .map_err(|e| anyhow::anyhow!("Не вдалося прочитати {:?}: {}", file, e))?;
 
-    let tokens = tryzub_lexer::tokenize(&source)?;
-    let _ast = tryzub_parser::parse(tokens)?;
+    let tokens = match tryzub_lexer::tokenize(&source) {
+        Ok(tokens) => tokens,
+        Err(e) => {
+            report::report_error(ErrorKind::Lexical, &e.to_string(), &file, Location::of_syntax_error(&e), Some(&source), 0);
+            std::process::exit(1);
+        }
+    };
+    let _ast = match tryzub_parser::parse(tokens) {
+        Ok(ast) => ast.configured(),
+        Err(e) => {
+            report::report_error(ErrorKind::Syntax, &e.to_string(), &file, Location::of_syntax_error(&e), Some(&source), 0);
+            std::process::exit(1);
+        }
+    };
 
     let stem = file.file_stem().unwrap_or_default().to_string_lossy().to_string();
 
+    match kind {
+        "виконуваний" => {}
+        "ліба" | "дліба" => {
+            #[cfg(feature = "llvm")]
+            {
+                let (library_kind, default_name) = if kind == "ліба" {
+                    let name = if cfg!(windows) { format!("{}.lib", stem) } else { format!("lib{}.a", stem) };
+                    (tryzub_compiler::LibraryKind::Static, name)
+                } else {
+                    let name = format!("{}{}.{}", std::env::consts::DLL_PREFIX, stem, std::env::consts::DLL_EXTENSION);
+                    (tryzub_compiler::LibraryKind::Shared, name)
+                };
+                let out_name = output.unwrap_or_else(|| PathBuf::from(default_name));
+                tryzub_compiler::generate_library(_ast, out_name.clone(), library_kind)?;
+                println!("Бібліотеку скомпільовано: {}", out_name.display());
+                return Ok(());
+            }
+            #[cfg(not(feature = "llvm"))]
+            return Err(anyhow::anyhow!("Збирання бібліотек потребує LLVM-бекенду. Зберіть з: cargo build --features llvm"));
+        }
+        other => return Err(anyhow::anyhow!("Невідомий тип збірки '{}': очікується виконуваний, ліба або дліба", other)),
+    }
+
     if kernel {
         let out_name = output.unwrap_or_else(|| PathBuf::from(format!("{}.bin", stem)));
         tryzub_vm::native::NativeCompiler::compile_to_bootable(&source, &out_name.to_string_lossy())?;
@@ -474,6 +659,18 @@ fn compile_file(file: PathBuf, output: Option<PathBuf>, native: bool, kernel: bo
         return Err(anyhow::anyhow!("Cranelift не ввімкнено. Зберіть з: cargo build --features cranelift-backend"));
     }
 
+    if debug {
+        #[cfg(feature = "llvm")]
+        {
+            let out_name = output.unwrap_or_else(|| PathBuf::from(&stem));
+            tryzub_compiler::generate_executable(_ast, out_name.clone(), None, Some(&file))?;
+            println!("Скомпільовано з налагоджувальною інформацією: {}", out_name.display());
+            return Ok(());
+        }
+        #[cfg(not(feature = "llvm"))]
+        return Err(anyhow::anyhow!("Налагоджувальна інформація потребує LLVM-бекенду. Зберіть з: cargo build --features llvm"));
+    }
+
     if native {
         let out_name = output.unwrap_or_else(|| PathBuf::from(format!("{}.bin", stem)));
         tryzub_vm::native::NativeCompiler::compile_to_flat_binary(&source, &out_name.to_string_lossy())?;
@@ -499,14 +696,16 @@ fn compile_file(file: PathBuf, output: Option<PathBuf>, native: bool, kernel: bo
     Ok(())
 }
 
-fn watch_file(file: PathBuf) -> Result<()> {
+/// `flags` — прапорці `запустити`, що передаються кожному перезапуску
+fn watch_file(file: PathBuf, flags: Vec<&str>, args: Vec<String>) -> Result<()> {
     use notify::{Watcher, RecursiveMode};
     use std::sync::mpsc;
 
     let (tx, rx) = mpsc::channel::<Vec<std::path::PathBuf>>();
     let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
         if let Ok(event) = res {
-            if event.kind.is_modify() {
+            // Редактори часто зберігають через перейменування тимчасового файлу
+            if event.kind.is_modify() || event.kind.is_create() {
                 let tryzub_paths: Vec<std::path::PathBuf> = event.paths.iter()
                     .filter(|p| p.extension().map_or(false, |e| e == "тризуб" || e == "tryzub"))
                     .cloned()
@@ -522,7 +721,10 @@ fn watch_file(file: PathBuf) -> Result<()> {
         watcher.watch(&file, RecursiveMode::Recursive)?;
         file.clone()
     } else {
-        let watch_dir = file.parent().unwrap_or(std::path::Path::new("."));
+        let watch_dir = match file.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => std::path::Path::new("."),
+        };
         watcher.watch(watch_dir, RecursiveMode::Recursive)?;
         file.clone()
     };
@@ -541,22 +743,53 @@ fn watch_file(file: PathBuf) -> Result<()> {
         file.clone()
     };
 
+    // Програма виконується в окремому процесі: так її можна перервати при
+    // наступній зміні, а синтаксична помилка не завершує режим спостереження
+    let exe = std::env::current_exe()?;
+
     loop {
         println!("\x1b[33m▶ Запуск...\x1b[0m");
         let start = std::time::Instant::now();
-        match run_file(run_target.clone(), false, false, false, vec![]) {
-            Ok(_) => {
-                let elapsed = start.elapsed();
-                println!("\x1b[32m✓ Виконано за {:.1}мс\x1b[0m", elapsed.as_secs_f64() * 1000.0);
+        let mut child = std::process::Command::new(&exe)
+            .arg("запустити")
+            .args(&flags)
+            .arg(&run_target)
+            .args(&args)
+            .spawn()
+            .map_err(|e| anyhow::anyhow!("Не вдалося запустити {:?}: {}", run_target, e))?;
+
+        let mut finished = false;
+        let changed = loop {
+            match rx.recv_timeout(std::time::Duration::from_millis(100)) {
+                Ok(paths) => break paths,
+                Err(mpsc::RecvTimeoutError::Timeout) => {}
+                Err(mpsc::RecvTimeoutError::Disconnected) => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Ok(());
+                }
+            }
+            if finished {
+                continue;
             }
-            Err(e) => {
-                eprintln!("\x1b[31m✗ {}\x1b[0m", e);
+            if let Some(status) = child.try_wait()? {
+                finished = true;
+                if status.success() {
+                    let elapsed = start.elapsed();
+                    println!("\x1b[32m✓ Виконано за {:.1}мс\x1b[0m", elapsed.as_secs_f64() * 1000.0);
+                } else {
+                    eprintln!("\x1b[31m✗ Програма завершилась з помилкою\x1b[0m");
+                }
+                println!("\x1b[36m  Чекаю на зміни...\x1b[0m\n");
             }
+        };
+
+        if !finished {
+            let _ = child.kill();
+            let _ = child.wait();
+            println!("\x1b[33m⏹ Попереднє виконання перервано\x1b[0m");
         }
-        println!("\x1b[36m  Чекаю на зміни...\x1b[0m\n");
 
-        while rx.try_recv().is_ok() {}
-        let changed = rx.recv().unwrap_or_default();
         std::thread::sleep(std::time::Duration::from_millis(300));
         while rx.try_recv().is_ok() {}
 
@@ -573,18 +806,84 @@ fn watch_file(file: PathBuf) -> Result<()> {
     }
 }
 
+fn build_project(dir: PathBuf) -> Result<()> {
+    let summary = build::build_project(&dir)?;
+    for file in &summary.built {
+        println!("  [зібрано] {}", file.display());
+    }
+    println!("Зібрано: {}, без змін: {} (артефакти в {})",
+        summary.built.len(), summary.cached.len(), dir.join(build::TARGET_DIR).display());
+    Ok(())
+}
+
+fn write_c_header(output: Option<PathBuf>) -> Result<()> {
+    #[cfg(feature = "llvm")]
+    {
+        let out_name = output.unwrap_or_else(|| PathBuf::from(tryzub_runtime::header::HEADER_NAME));
+        fs::write(&out_name, tryzub_runtime::header::c_header()?)
+            .map_err(|e| anyhow::anyhow!("Не вдалося записати {}: {}", out_name.display(), e))?;
+        println!("Заголовок згенеровано: {}", out_name.display());
+        Ok(())
+    }
+    #[cfg(not(feature = "llvm"))]
+    {
+        let _ = output;
+        Err(anyhow::anyhow!("Генерація заголовка потребує LLVM-бекенду. Зберіть з: cargo build --features llvm"))
+    }
+}
+
+fn parse_file(file: PathBuf, save_ast: bool, output: Option<PathBuf>) -> Result<()> {
+    let ast = load_program(&file)?;
+    if !save_ast {
+        println!("{:#?}", ast);
+        return Ok(());
+    }
+    let out_name = output.unwrap_or_else(|| file.with_extension(tryzub_parser::binary::EXTENSION));
+    fs::write(&out_name, tryzub_parser::binary::serialize(&ast)?)
+        .map_err(|e| anyhow::anyhow!("Не вдалося записати {:?}: {}", out_name, e))?;
+    println!("AST збережено: {}", out_name.display());
+    Ok(())
+}
+
 fn check_file(file: PathBuf) -> Result<()> {
-    let source = fs::read_to_string(&file)
-        .map_err(|e| anyhow::anyhow!("Не вдалося прочитати файл {:?}: {}", file, e))?;
+    let source = read_source(&file)?;
 
     println!("Перевіряю: {:?}", file);
 
-    let tokens = tryzub_lexer::tokenize(&source)?;
+    let tokens = match tryzub_lexer::tokenize(&source) {
+        Ok(t) => t,
+        Err(e) => {
+            report::report_error(ErrorKind::Lexical, &e.to_string(), &file, Location::of_syntax_error(&e), Some(&source), 0);
+            return Err(anyhow::anyhow!("Лексичний аналіз не пройдено"));
+        }
+    };
     println!("  ✓ Лексичний аналіз: {} токенів", tokens.len());
 
-    let _ast = tryzub_parser::parse(tokens)?;
+    let bracket_hint = tryzub_parser::check_brackets(&tokens).err();
+    let (ast, errors) = tryzub_parser::parse_recover(tokens);
+    if !errors.is_empty() {
+        for e in &errors {
+            report::report_error(ErrorKind::Syntax, &e.to_string(), &file, Some(Location::of_parse_error(e)), Some(&source), 0);
+        }
+        if let Some(hint) = bracket_hint {
+            eprintln!("  Підказка: {}", hint);
+        }
+        return Err(anyhow::anyhow!("Знайдено синтаксичних помилок: {}", errors.len()));
+    }
     println!("  ✓ Синтаксичний аналіз: OK");
 
+    if let Some(ast) = ast {
+        let type_errors = tryzub_parser::semantic::type_check(&ast);
+        if !type_errors.is_empty() {
+            for e in &type_errors {
+                let location = Location { line: e.line(), column: None };
+                report::report_error(ErrorKind::Type, &e.to_string(), &file, Some(location), Some(&source), 0);
+            }
+            return Err(anyhow::anyhow!("Знайдено помилок типів: {}", type_errors.len()));
+        }
+        println!("  ✓ Перевірка типів: OK");
+    }
+
     println!("[OK] Файл синтаксично правильний");
     Ok(())
 }
@@ -594,7 +893,7 @@ fn run_tests(file: PathBuf) -> Result<()> {
         .map_err(|e| anyhow::anyhow!("Не вдалося прочитати файл {:?}: {}", file, e))?;
 
     let tokens = tryzub_lexer::tokenize(&source)?;
-    let ast = tryzub_parser::parse(tokens)?;
+    let ast = tryzub_parser::parse(tokens)?.configured();
 
     // Знаходимо всі тест-блоки
     let mut total = 0;
@@ -620,8 +919,13 @@ fn run_tests(file: PathBuf) -> Result<()> {
                         is_async: false,
                         visibility: tryzub_parser::Visibility::Public,
                         contract: None,
+                        is_static: false,
+                        span: tryzub_parser::Span::default(),
+                        doc: None,
                     }))
                     .collect(),
+                exports: vec![],
+                directives: ast.directives.clone(),
             };
 
             match tryzub_vm::execute(test_program, vec![]) {
@@ -653,8 +957,13 @@ fn run_tests(file: PathBuf) -> Result<()> {
                         body: body.clone(), is_async: false,
                         visibility: tryzub_parser::Visibility::Public,
                         contract: None,
+                        is_static: false,
+                        span: tryzub_parser::Span::default(),
+                        doc: None,
                     }))
                     .collect(),
+                exports: vec![],
+                directives: ast.directives.clone(),
             };
 
             let start = std::time::Instant::now();
@@ -685,8 +994,13 @@ fn run_tests(file: PathBuf) -> Result<()> {
                         body: body.clone(), is_async: false,
                         visibility: tryzub_parser::Visibility::Public,
                         contract: None,
+                        is_static: false,
+                        span: tryzub_parser::Span::default(),
+                        doc: None,
                     }))
                     .collect(),
+                exports: vec![],
+                directives: ast.directives.clone(),
             };
 
             // Запускаємо фаз-тест 50 разів з різними seed-ами
@@ -1585,18 +1899,42 @@ fn lsp_completions(source: &str) -> Vec<serde_json::Value> {
     let builtins = [
         ("друк", "Вивести значення в консоль"),
         ("довжина", "Довжина масиву або рядка"),
+        ("великі", "Рядок великими літерами"), ("малі", "Рядок малими літерами"),
+        ("врядок_байти", "UTF-8 байти рядка"), ("збайтів_рядок", "Рядок з масиву UTF-8 байтів"),
+        ("множина", "Множина унікальних значень: множина([1, 2, 2])"),
         ("діапазон", "Створити ліниве Range(від, до)"),
         ("фільтрувати", "Фільтрувати масив за предикатом"),
         ("перетворити", "Перетворити кожен елемент масиву"),
         ("згорнути", "Згорнути масив до одного значення"),
         ("сортувати", "Відсортувати масив"),
+        ("з'єднати", "З'єднати елементи масиву в рядок через роздільник"),
+        ("повторити", "Повторити рядок n разів"), ("повторити_масив", "Повторити елементи масиву n разів"),
+        ("друкф", "Друк за шаблоном: {:05}, {:.2}"), ("форматувати", "Рядок за шаблоном: {:05}, {:.2}"),
+        ("успіх", "Результат з успішним значенням"), ("помилка", "Результат з помилкою"),
+        ("щось", "Опція зі значенням"), ("ніщо", "Порожня опція"),
+        ("є_помилкою", "Чи містить Результат/Опція помилку"),
+        ("розгорнути", "Значення Результату/Опції або паніка"),
         ("корінь", "Квадратний корінь"), ("синус", "sin(x)"), ("косинус", "cos(x)"),
+        ("математика", "Модуль: ПІ, Е, корінь, степінь, логарифм"),
         ("абс", "Модуль числа"), ("мін", "Мінімум"), ("макс", "Максимум"),
         ("тип_значення", "Тип значення як рядок"),
+        ("тип", "Категорія значення: ціле, рядок, масив…"),
+        ("час_зараз", "Unix-час у мілісекундах"),
+        ("спати", "Пауза на задану кількість мілісекунд"),
+        ("влог", "Перетворити значення в лог"),
+        ("врядкувлог", "Розібрати \"істина\"/\"хиба\" в лог"),
         ("файл_прочитати", "Прочитати файл як рядок"),
         ("файл_записати", "Записати рядок у файл"),
+        ("читати_файл", "Прочитати файл (помилка вводу-виводу, якщо не вдалося)"),
+        ("записати_файл", "Записати вміст у файл (помилка вводу-виводу, якщо не вдалося)"),
+        ("файл_існує", "Чи існує файл"),
         ("json_розібрати", "Розібрати JSON рядок"),
         ("все", "Виконати всі функції паралельно"),
+        ("паралельно", "Виконати функції на потоках з пулу, результати в тому ж порядку"),
+        ("канал", "Створити канал: (відправник, отримувач)"),
+        ("надіслати", "Надіслати значення в канал"),
+        ("отримати", "Отримати значення з каналу (чекає)"),
+        ("закрити_канал", "Закрити канал для надсилання"),
         ("перегони", "Перший результат з масиву функцій"),
         ("потік", "Запустити функцію в потоці"),
     ];
@@ -1985,8 +2323,12 @@ fn run_lint(file: PathBuf) -> Result<()> {
     }
 
     // AST аналіз
-    match tryzub_lexer::tokenize(&source) {
+    let mut lexer = tryzub_lexer::Lexer::new(&source).warn_mixed_scripts(true);
+    match lexer.tokenize() {
         Ok(tokens) => {
+            for w in lexer.warnings() {
+                warnings.push(w.to_string());
+            }
             match tryzub_parser::parse(tokens) {
                 Ok(program) => lint_ast(&program, &source, &mut warnings),
                 Err(e) => warnings.push(format!("синтаксична помилка: {}", e)),
@@ -2212,7 +2554,7 @@ fn collect_used_idents_stmt(stmt: &tryzub_parser::Statement, used: &mut std::col
             collect_used_idents_stmt(then_branch, used);
             if let Some(eb) = else_branch { collect_used_idents_stmt(eb, used); }
         }
-        Statement::While { condition, body } => {
+        Statement::While { condition, body, .. } => {
             collect_used_idents_expr(condition, used);
             collect_used_idents_stmt(body, used);
         }
@@ -2226,10 +2568,17 @@ fn collect_used_idents_stmt(stmt: &tryzub_parser::Statement, used: &mut std::col
             collect_used_idents_stmt(body, used);
         }
         Statement::Yield(expr) => collect_used_idents_expr(expr, used),
+        Statement::Defer(body) => collect_used_idents_stmt(body, used),
         Statement::Assignment { target, value, .. } => {
             collect_used_idents_expr(target, used);
             collect_used_idents_expr(value, used);
         }
+        Statement::MultiAssign { targets, value, .. } => {
+            for target in targets {
+                collect_used_idents_expr(target, used);
+            }
+            collect_used_idents_expr(value, used);
+        }
         _ => {}
     }
 }
@@ -2243,7 +2592,7 @@ fn collect_used_idents_expr(expr: &tryzub_parser::Expression, used: &mut std::co
             collect_used_idents_expr(right, used);
         }
         Expression::Unary { operand, .. } => collect_used_idents_expr(operand, used),
-        Expression::Call { callee, args } => {
+        Expression::Call { callee, args, .. } => {
             collect_used_idents_expr(callee, used);
             for a in args { collect_used_idents_expr(a, used); }
         }
@@ -2308,19 +2657,22 @@ fn check_empty_catch(stmt: &tryzub_parser::Statement, fn_name: &str, warnings: &
 // Генерація документації
 // ════════════════════════════════════════════════════════════════════
 
+fn doc_source_files(path: PathBuf) -> Result<Vec<PathBuf>> {
+    if path.is_file() {
+        return Ok(vec![path]);
+    }
+    let mut f: Vec<PathBuf> = fs::read_dir(&path)?
+        .filter_map(|e| e.ok()).map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "тризуб" || ext == "tryzub"))
+        .collect();
+    f.sort();
+    Ok(f)
+}
+
 fn run_doc(path: PathBuf, output: PathBuf) -> Result<()> {
     fs::create_dir_all(&output)?;
 
-    let files: Vec<PathBuf> = if path.is_file() {
-        vec![path]
-    } else {
-        let mut f: Vec<PathBuf> = fs::read_dir(&path)?
-            .filter_map(|e| e.ok()).map(|e| e.path())
-            .filter(|p| p.extension().map_or(false, |ext| ext == "тризуб" || ext == "tryzub"))
-            .collect();
-        f.sort();
-        f
-    };
+    let files = doc_source_files(path)?;
 
     let mut nav = String::new();
     let mut content = String::new();
@@ -2335,7 +2687,7 @@ fn run_doc(path: PathBuf, output: PathBuf) -> Result<()> {
 
         if let Ok(tokens) = tryzub_lexer::tokenize(&source) {
             if let Ok(program) = tryzub_parser::parse(tokens) {
-                doc_generate_decls(&program.declarations, &module_id, &mut nav, &mut content, &mut all_items, &source);
+                doc_generate_decls(&program.declarations, &module_id, &mut nav, &mut content, &mut all_items);
             }
         }
 
@@ -2438,11 +2790,98 @@ function filterDocs(q) {{
     Ok(())
 }
 
-fn doc_generate_decls(decls: &[tryzub_parser::Declaration], module_id: &str, nav: &mut String, content: &mut String, items: &mut Vec<String>, source: &str) {
+fn run_doc_markdown(path: PathBuf, output: PathBuf) -> Result<()> {
+    fs::create_dir_all(&output)?;
+
+    let files = doc_source_files(path)?;
+    let mut md = String::from("# Тризуб — Документація API\n");
+
+    for file in &files {
+        let source = fs::read_to_string(file)?;
+        let filename = file.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let tokens = tryzub_lexer::tokenize(&source)
+            .map_err(|e| anyhow::anyhow!("{}: {}", file.display(), e))?;
+        let program = tryzub_parser::parse(tokens)
+            .map_err(|e| anyhow::anyhow!("{}: {}", file.display(), e))?;
+        md.push_str(&format!("\n## Модуль `{}`\n", filename));
+        doc_markdown_decls(&program.declarations, &mut md);
+    }
+
+    let out_file = output.join("документація.md");
+    fs::write(&out_file, md)?;
+    println!("Документація згенерована: {}", out_file.display());
+    println!("Файлів оброблено: {}", files.len());
+    Ok(())
+}
+
+fn doc_markdown_decls(decls: &[tryzub_parser::Declaration], md: &mut String) {
+    use std::collections::HashMap;
+    use tryzub_parser::{Declaration, Visibility};
+
+    let sig_params = |params: &[tryzub_parser::Parameter]| params.iter()
+        .filter(|p| p.name != "себе")
+        .map(|p| format!("{}: {}", p.name, type_to_string(&p.ty)))
+        .collect::<Vec<_>>().join(", ");
+    let sig_ret = |ret: &Option<tryzub_parser::Type>| ret.as_ref()
+        .map(|t| format!(" -> {}", type_to_string(t))).unwrap_or_default();
+
+    // Методи структур оголошуються окремо, у блоках `реалізація`
+    let mut impl_methods: HashMap<&str, Vec<&Declaration>> = HashMap::new();
+    for decl in decls {
+        match decl {
+            Declaration::Impl { type_name: name, methods }
+            | Declaration::TraitImpl { for_type: name, methods, .. } => impl_methods.entry(name).or_default().extend(methods),
+            _ => {}
+        }
+    }
+
+    for decl in decls {
+        let doc = decl.doc().map(|d| format!("{}\n\n", d.trim())).unwrap_or_default();
+        match decl {
+            Declaration::Function { name, params, return_type, is_async, visibility: Visibility::Public, .. } => {
+                let kw = if *is_async { "асинхронна функція" } else { "функція" };
+                md.push_str(&format!("\n### `{}`\n\n```тризуб\n{} {}({}){}\n```\n\n{}", name, kw, name, sig_params(params), sig_ret(return_type), doc));
+            }
+            Declaration::Struct { name, fields, methods, visibility: Visibility::Public, .. } => {
+                md.push_str(&format!("\n### `{}`\n\n```тризуб\nструктура {}\n```\n\n{}", name, name, doc));
+                for f in fields.iter().filter(|f| f.visibility == Visibility::Public) {
+                    md.push_str(&format!("- `{}: {}`\n", f.name, type_to_string(&f.ty)));
+                }
+                let own = methods.iter().filter(|m| matches!(m, Declaration::Function { visibility: Visibility::Public, .. }));
+                for m in own.chain(impl_methods.get(name.as_str()).into_iter().flatten().copied()) {
+                    if let Declaration::Function { name: mname, params, return_type, .. } = m {
+                        md.push_str(&format!("- `.{}({}){}`\n", mname, sig_params(params), sig_ret(return_type)));
+                    }
+                }
+            }
+            Declaration::Interface { name, methods, visibility: Visibility::Public } => {
+                md.push_str(&format!("\n### `{}`\n\n```тризуб\nінтерфейс {}\n```\n\n", name, name));
+                for m in methods {
+                    md.push_str(&format!("- `.{}({}){}`\n", m.name, sig_params(&m.params), sig_ret(&m.return_type)));
+                }
+            }
+            Declaration::Trait { name, methods, visibility: Visibility::Public, .. } => {
+                md.push_str(&format!("\n### `{}`\n\n```тризуб\nтрейт {}\n```\n\n{}", name, name, doc));
+                for m in methods {
+                    md.push_str(&format!("- `.{}({}){}`\n", m.name, sig_params(&m.params), sig_ret(&m.return_type)));
+                }
+            }
+            Declaration::Module { name, declarations, .. } => {
+                md.push_str(&format!("\n## Модуль `{}`\n", name));
+                doc_markdown_decls(declarations, md);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn doc_generate_decls(decls: &[tryzub_parser::Declaration], module_id: &str, nav: &mut String, content: &mut String, items: &mut Vec<String>) {
     use tryzub_parser::Declaration;
-    let doc_lines = collect_doc_comments(source);
 
     for decl in decls {
+        let doc_html = decl.doc()
+            .map(|doc| format!("<div class='doc'>{}</div>", doc_to_html(&doc.lines().map(String::from).collect::<Vec<_>>())))
+            .unwrap_or_default();
         match decl {
             Declaration::Function { name, params, return_type, is_async, visibility, .. } => {
                 let vis_badge = if *visibility == tryzub_parser::Visibility::Public { "<span class='badge badge-pub'>публічний</span>" } else { "" };
@@ -2451,8 +2890,6 @@ fn doc_generate_decls(decls: &[tryzub_parser::Declaration], module_id: &str, nav
                 let ret_str = return_type.as_ref().map(|t| format!(" -> {}", type_to_string(t))).unwrap_or_default();
                 let sig = format!("<span class='kw'>функція</span> <span class='name'>{}</span>({}){}", name, html_escape(&params_str), html_escape(&ret_str));
 
-                let doc_html = doc_lines.get(name.as_str()).map(|lines| format!("<div class='doc'>{}</div>", doc_to_html(lines))).unwrap_or_default();
-
                 let item_id = format!("{}_{}", module_id, name);
                 nav.push_str(&format!("<li class='nav-fn'><a href='#{}'>{}</a></li>\n", item_id, name));
                 content.push_str(&format!("<div class='item fn' id='{}'>{}{}{}<code class='sig'>{}</code></div>\n", item_id, vis_badge, async_badge, doc_html, sig));
@@ -2461,7 +2898,6 @@ fn doc_generate_decls(decls: &[tryzub_parser::Declaration], module_id: &str, nav
             Declaration::Struct { name, fields, methods, visibility, .. } => {
                 let vis_badge = if *visibility == tryzub_parser::Visibility::Public { "<span class='badge badge-pub'>публічний</span>" } else { "" };
                 let item_id = format!("{}_{}", module_id, name);
-                let doc_html = doc_lines.get(name.as_str()).map(|lines| format!("<div class='doc'>{}</div>", doc_to_html(lines))).unwrap_or_default();
 
                 nav.push_str(&format!("<li class='nav-struct'><a href='#{}'>{}</a></li>\n", item_id, name));
                 content.push_str(&format!("<div class='item struct' id='{}'>{}{}<code class='sig'><span class='kw'>структура</span> <span class='name'>{}</span></code>\n", item_id, vis_badge, doc_html, name));
@@ -2490,7 +2926,6 @@ fn doc_generate_decls(decls: &[tryzub_parser::Declaration], module_id: &str, nav
             Declaration::Trait { name, methods, visibility, .. } => {
                 let vis_badge = if *visibility == tryzub_parser::Visibility::Public { "<span class='badge badge-pub'>публічний</span>" } else { "" };
                 let item_id = format!("{}_{}", module_id, name);
-                let doc_html = doc_lines.get(name.as_str()).map(|lines| format!("<div class='doc'>{}</div>", doc_to_html(lines))).unwrap_or_default();
 
                 nav.push_str(&format!("<li class='nav-trait'><a href='#{}'>{}</a></li>\n", item_id, name));
                 content.push_str(&format!("<div class='item trait' id='{}'>{}{}<code class='sig'><span class='kw'>трейт</span> <span class='name'>{}</span></code>\n", item_id, vis_badge, doc_html, name));
@@ -2511,7 +2946,6 @@ fn doc_generate_decls(decls: &[tryzub_parser::Declaration], module_id: &str, nav
             Declaration::Enum { name, variants, visibility, .. } => {
                 let vis_badge = if *visibility == tryzub_parser::Visibility::Public { "<span class='badge badge-pub'>публічний</span>" } else { "" };
                 let item_id = format!("{}_{}", module_id, name);
-                let doc_html = doc_lines.get(name.as_str()).map(|lines| format!("<div class='doc'>{}</div>", doc_to_html(lines))).unwrap_or_default();
 
                 nav.push_str(&format!("<li class='nav-enum'><a href='#{}'>{}</a></li>\n", item_id, name));
                 content.push_str(&format!("<div class='item enum' id='{}'>{}{}<code class='sig'><span class='kw'>перелік</span> <span class='name'>{}</span></code>\n", item_id, vis_badge, doc_html, name));
@@ -2536,41 +2970,13 @@ fn doc_generate_decls(decls: &[tryzub_parser::Declaration], module_id: &str, nav
             }
             Declaration::Module { name, declarations, .. } => {
                 content.push_str(&format!("<h3>Модуль: {}</h3>\n", name));
-                doc_generate_decls(declarations, &format!("{}_{}", module_id, name), nav, content, items, source);
+                doc_generate_decls(declarations, &format!("{}_{}", module_id, name), nav, content, items);
             }
             _ => {}
         }
     }
 }
 
-fn collect_doc_comments(source: &str) -> std::collections::HashMap<&str, Vec<String>> {
-    let mut map = std::collections::HashMap::new();
-    let mut current_docs: Vec<String> = Vec::new();
-    for line in source.lines() {
-        let trimmed = line.trim();
-        if trimmed.starts_with("///") {
-            current_docs.push(trimmed[3..].trim().to_string());
-        } else if !current_docs.is_empty() {
-            let name_part = trimmed
-                .trim_start_matches("публічний ")
-                .trim_start_matches("асинхронна ")
-                .trim_start_matches("функція ")
-                .trim_start_matches("структура ")
-                .trim_start_matches("трейт ")
-                .trim_start_matches("перелік ");
-            if let Some(name) = name_part.split(|c: char| c == '(' || c == '{' || c == '<' || c.is_whitespace()).next() {
-                if !name.is_empty() {
-                    map.insert(name, current_docs.clone());
-                }
-            }
-            current_docs.clear();
-        } else {
-            current_docs.clear();
-        }
-    }
-    map
-}
-
 fn doc_to_html(lines: &[String]) -> String {
     let mut html = String::new();
     let mut in_list = false;
@@ -0,0 +1,89 @@
+// Бінарний формат AST `.тризубс` для завантаження без повторного парсингу.
+// Файл: сигнатура, версія формату, версія парсера, далі `Program` у bincode.
+
+use anyhow::Result;
+use crate::Program;
+
+/// Розширення файлів зі збереженим AST
+pub const EXTENSION: &str = "тризубс";
+
+/// Версія формату; збільшується, коли змінюється заголовок
+pub const FORMAT_VERSION: u32 = 1;
+
+const MAGIC: &[u8; 4] = b"TRZS";
+
+/// Кодує програму у формат `.тризубс`
+pub fn serialize(program: &Program) -> Result<Vec<u8>> {
+    let mut bytes = MAGIC.to_vec();
+    bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+    bincode::serialize_into(&mut bytes, env!("CARGO_PKG_VERSION"))?;
+    bincode::serialize_into(&mut bytes, program)?;
+    Ok(bytes)
+}
+
+/// Читає програму з `.тризубс`. Вузли AST між версіями парсера не сумісні,
+/// тож файл від іншої версії відхиляється, а не розбирається навмання
+pub fn deserialize(bytes: &[u8]) -> Result<Program> {
+    let rest = bytes.strip_prefix(MAGIC.as_slice())
+        .ok_or_else(|| anyhow::anyhow!("Файл не є збереженим AST Тризуба (.{})", EXTENSION))?;
+    let (version, rest) = rest.split_at(4.min(rest.len()));
+    let version = <[u8; 4]>::try_from(version)
+        .map(u32::from_le_bytes)
+        .map_err(|_| anyhow::anyhow!("Пошкоджений заголовок .{}", EXTENSION))?;
+    if version != FORMAT_VERSION {
+        return Err(anyhow::anyhow!(
+            "Несумісна версія формату .{}: {} (підтримується {})", EXTENSION, version, FORMAT_VERSION
+        ));
+    }
+
+    let mut reader = rest;
+    let parser_version: String = bincode::deserialize_from(&mut reader)
+        .map_err(|e| anyhow::anyhow!("Пошкоджений заголовок .{}: {}", EXTENSION, e))?;
+    if parser_version != env!("CARGO_PKG_VERSION") {
+        return Err(anyhow::anyhow!(
+            "AST збережено парсером {}, а поточна версія {}; розберіть вихідний файл знову",
+            parser_version, env!("CARGO_PKG_VERSION")
+        ));
+    }
+    bincode::deserialize_from(&mut reader)
+        .map_err(|e| anyhow::anyhow!("Пошкоджений AST у .{}: {}", EXTENSION, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+    use tryzub_lexer::tokenize;
+
+    #[test]
+    fn test_round_trip() {
+        let source = r#"
+//@таймаут 5с
+/// Точка на площині
+структура Точка { х: дрб64, у: дрб64 }
+
+функція головна() {
+    стала т = Точка { х: 1.5, у: -2.0 }
+    для і від 0 до 3 {
+        друк(ф"{і}: {т.х}")
+    }
+    зіставити т.х { 1.5 => друк("так"), _ => друк("ні") }
+}
+"#;
+        let program = parse(tokenize(source).unwrap()).unwrap();
+        let bytes = serialize(&program).unwrap();
+        assert_eq!(deserialize(&bytes).unwrap(), program);
+    }
+
+    #[test]
+    fn test_rejects_other_format_version() {
+        let program = parse(tokenize("функція головна() {}").unwrap()).unwrap();
+        let mut bytes = serialize(&program).unwrap();
+        bytes[4..8].copy_from_slice(&(FORMAT_VERSION + 1).to_le_bytes());
+        let err = deserialize(&bytes).unwrap_err().to_string();
+        assert!(err.contains("Несумісна версія формату"), "{}", err);
+
+        let err = deserialize(b"not an ast").unwrap_err().to_string();
+        assert!(err.contains("не є збереженим AST"), "{}", err);
+    }
+}
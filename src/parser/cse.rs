@@ -0,0 +1,381 @@
+// Усунення спільних підвиразів (CSE) на рівні AST.
+// Повторний чистий підвираз інструкції обчислюється один раз у тимчасову
+// сталу перед нею. Інструкції з викликами не чіпаємо: виклик може змінити
+// змінні, від яких залежить повтор, тож порядок обчислення вже не байдужий.
+// Між інструкціями блоку значення сталих і тимчасових лишаються доступними,
+// поки жоден з їхніх операндів не перепризначено: `стала а = х*у; стала б = х*у + 1`
+// дає `б = а + 1`.
+
+use crate::*;
+
+/// Префікс тимчасових сталих; лексер не пропускає `__` на початку імені,
+/// тож з іменами користувача вони не перетнуться
+const TEMP_PREFIX: &str = "__спільне";
+
+/// Виносить однакові чисті підвирази в тимчасові сталі в межах кожного блоку
+pub fn eliminate_common_subexpressions(mut program: Program) -> Program {
+    let mut pass = Cse::default();
+    for decl in &mut program.declarations {
+        pass.declaration(decl);
+    }
+    program
+}
+
+#[derive(Default)]
+struct Cse {
+    next_temp: usize,
+}
+
+impl Cse {
+    fn declaration(&mut self, decl: &mut Declaration) {
+        match decl {
+            Declaration::Function { body, .. }
+            | Declaration::Test { body, .. }
+            | Declaration::FuzzTest { body, .. }
+            | Declaration::Benchmark { body, .. } => self.block(body),
+            Declaration::Struct { methods, .. }
+            | Declaration::TraitImpl { methods, .. }
+            | Declaration::Impl { methods, .. } => {
+                for method in methods {
+                    self.declaration(method);
+                }
+            }
+            Declaration::Module { declarations, .. } => {
+                for decl in declarations {
+                    self.declaration(decl);
+                }
+            }
+            Declaration::Attributed { declaration, .. } => self.declaration(declaration),
+            _ => {}
+        }
+    }
+
+    fn block(&mut self, stmts: &mut Vec<Statement>) {
+        let mut out = Vec::with_capacity(stmts.len());
+        let mut available: Vec<Available> = Vec::new();
+        for mut stmt in stmts.drain(..) {
+            self.nested(&mut stmt);
+            reuse(&mut stmt, &available);
+            let start = out.len();
+            self.hoist(stmt, &mut out);
+            for stmt in &out[start..] {
+                track(stmt, &mut available);
+            }
+        }
+        *stmts = out;
+    }
+
+    /// Вкладені блоки інструкції
+    fn nested(&mut self, stmt: &mut Statement) {
+        match stmt {
+            Statement::Block(stmts) | Statement::CompTime(stmts) | Statement::Unsafe(stmts) => self.block(stmts),
+            Statement::If { then_branch, else_branch, .. } => {
+                self.branch(then_branch);
+                if let Some(else_branch) = else_branch {
+                    self.branch(else_branch);
+                }
+            }
+            Statement::While { body, .. }
+            | Statement::For { body, .. }
+            | Statement::ForIn { body, .. }
+            | Statement::WithHandler { body, .. }
+            | Statement::Defer(body) => self.branch(body),
+            Statement::TryCatch { try_body, catch_body, finally_body, .. } => {
+                self.branch(try_body);
+                if let Some(body) = catch_body {
+                    self.branch(body);
+                }
+                if let Some(body) = finally_body {
+                    self.branch(body);
+                }
+            }
+            Statement::Declaration(decl) => self.declaration(decl),
+            _ => {}
+        }
+    }
+
+    /// Гілка з однієї інструкції стає блоком, якщо перед нею з'явилися тимчасові сталі
+    fn branch(&mut self, stmt: &mut Statement) {
+        let mut stmts = vec![std::mem::replace(stmt, Statement::Block(Vec::new()))];
+        self.block(&mut stmts);
+        *stmt = if stmts.len() == 1 { stmts.remove(0) } else { Statement::Block(stmts) };
+    }
+
+    fn hoist(&mut self, mut stmt: Statement, out: &mut Vec<Statement>) {
+        while let Some(common) = repeated(&mut stmt) {
+            let name = format!("{}{}", TEMP_PREFIX, self.next_temp);
+            self.next_temp += 1;
+            let common_key = key(&common).unwrap_or_default();
+            for root in roots(&mut stmt) {
+                replace(root, &common_key, &name);
+            }
+            // Сам винесений вираз теж може містити повтори: `(a*b + a*b) * (a*b + a*b)`
+            let temp = Statement::Declaration(Declaration::Variable {
+                name,
+                ty: None,
+                value: Some(common),
+                is_mutable: false,
+                span: Span::default(),
+            });
+            self.hoist(temp, out);
+        }
+        out.push(stmt);
+    }
+}
+
+/// Чистий вираз, уже обчислений у сталу `name` раніше в блоці
+struct Available {
+    key: String,
+    name: String,
+    /// Імена, від яких залежить значення, разом із самою сталою
+    operands: Vec<String>,
+}
+
+/// Заміняє вже обчислені вирази іменами їхніх сталих, найбільші першими
+fn reuse(stmt: &mut Statement, available: &[Available]) {
+    if available.is_empty() || roots(stmt).iter().any(|root| key(root).is_none()) {
+        return;
+    }
+    let mut by_size: Vec<&Available> = available.iter().collect();
+    by_size.sort_by_key(|a| std::cmp::Reverse(a.key.len()));
+    for entry in by_size {
+        for root in roots(stmt) {
+            replace(root, &entry.key, &entry.name);
+        }
+    }
+}
+
+/// Оновлює доступні вирази після інструкції: перепризначене ім'я скасовує всі
+/// вирази з ним, а інструкція з викликом чи вкладеним блоком — усі одразу
+fn track(stmt: &Statement, available: &mut Vec<Available>) {
+    let changed = match stmt {
+        Statement::Declaration(Declaration::Variable { name, value, .. })
+            if value.as_ref().is_none_or(|value| key(value).is_some()) => name,
+        Statement::Assignment { target: Expression::Identifier(name), value, .. } if key(value).is_some() => name,
+        Statement::Expression(expr) | Statement::Assert(expr) | Statement::Yield(expr) if key(expr).is_some() => return,
+        _ => {
+            available.clear();
+            return;
+        }
+    };
+    available.retain(|a| !a.operands.contains(changed));
+    if let Statement::Declaration(Declaration::Variable { name, value: Some(value), is_mutable: false, .. }) = stmt {
+        if let (Expression::Binary { .. } | Expression::Unary { .. }, Some(key)) = (value, key(value)) {
+            let mut operands = vec![name.clone()];
+            identifiers(value, &mut operands);
+            available.push(Available { key, name: name.clone(), operands });
+        }
+    }
+}
+
+fn identifiers(expr: &Expression, out: &mut Vec<String>) {
+    match expr {
+        Expression::Identifier(name) => out.push(name.clone()),
+        Expression::SelfRef => out.push("себе".to_string()),
+        Expression::Binary { left, right, .. } => {
+            identifiers(left, out);
+            identifiers(right, out);
+        }
+        Expression::Unary { operand, .. } => identifiers(operand, out),
+        Expression::Index { object, index } => {
+            identifiers(object, out);
+            identifiers(index, out);
+        }
+        Expression::MemberAccess { object, .. } => identifiers(object, out),
+        Expression::Array(items) | Expression::Tuple(items) => {
+            for item in items {
+                identifiers(item, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Вирази, які інструкція обчислює одразу, до будь-якого вкладеного блоку
+fn roots(stmt: &mut Statement) -> Vec<&mut Expression> {
+    match stmt {
+        Statement::Expression(expr)
+        | Statement::Return(Some(expr))
+        | Statement::Assert(expr)
+        | Statement::Yield(expr)
+        | Statement::If { condition: expr, .. }
+        | Statement::Declaration(Declaration::Variable { value: Some(expr), .. }) => vec![expr],
+        Statement::Assignment { target, value, .. } => vec![target, value],
+        // Умова `поки` обчислюється щоітерації, тож її не можна винести перед циклом
+        _ => Vec::new(),
+    }
+}
+
+/// Найбільший підвираз, що трапляється щонайменше двічі й хоча б раз обчислюється
+/// безумовно; інакше винесення могло б обчислити те, що `&&`/`||` пропустили б
+fn repeated(stmt: &mut Statement) -> Option<Expression> {
+    let roots = roots(stmt);
+    if roots.is_empty() || roots.iter().any(|root| key(root).is_none()) {
+        return None;
+    }
+    let mut seen: Vec<Occurrence> = Vec::new();
+    for root in roots {
+        collect(root, false, &mut seen);
+    }
+    // Ключ надвиразу містить ключ підвиразу, тож найдовший ключ — найбільший вираз
+    seen.into_iter()
+        .filter(|o| o.count >= 2 && o.unconditional)
+        .fold(None, |best: Option<Occurrence>, o| match best {
+            Some(b) if b.key.len() >= o.key.len() => Some(b),
+            _ => Some(o),
+        })
+        .map(|o| o.expr)
+}
+
+struct Occurrence {
+    key: String,
+    expr: Expression,
+    count: usize,
+    unconditional: bool,
+}
+
+fn collect(expr: &Expression, conditional: bool, seen: &mut Vec<Occurrence>) {
+    if matches!(expr, Expression::Binary { .. } | Expression::Unary { .. }) {
+        if let Some(k) = key(expr) {
+            match seen.iter_mut().find(|o| o.key == k) {
+                Some(o) => {
+                    o.count += 1;
+                    o.unconditional |= !conditional;
+                }
+                None => seen.push(Occurrence { key: k, expr: expr.clone(), count: 1, unconditional: !conditional }),
+            }
+        }
+    }
+    match expr {
+        Expression::Binary { left, op, right, .. } => {
+            collect(left, conditional, seen);
+            collect(right, conditional || matches!(op, BinaryOp::And | BinaryOp::Or), seen);
+        }
+        Expression::Unary { operand, .. } => collect(operand, conditional, seen),
+        Expression::Index { object, index } => {
+            collect(object, conditional, seen);
+            collect(index, conditional, seen);
+        }
+        Expression::MemberAccess { object, .. } => collect(object, conditional, seen),
+        Expression::Array(items) | Expression::Tuple(items) => {
+            for item in items {
+                collect(item, conditional, seen);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Заміна всіх входжень, зокрема умовних: значення вже обчислене, а вираз чистий
+fn replace(expr: &mut Expression, target: &str, name: &str) {
+    if matches!(expr, Expression::Binary { .. } | Expression::Unary { .. }) && key(expr).as_deref() == Some(target) {
+        *expr = Expression::Identifier(name.to_string());
+        return;
+    }
+    match expr {
+        Expression::Binary { left, right, .. } => {
+            replace(left, target, name);
+            replace(right, target, name);
+        }
+        Expression::Unary { operand, .. } => replace(operand, target, name),
+        Expression::Index { object, index } => {
+            replace(object, target, name);
+            replace(index, target, name);
+        }
+        Expression::MemberAccess { object, .. } => replace(object, target, name),
+        Expression::Array(items) | Expression::Tuple(items) => {
+            for item in items {
+                replace(item, target, name);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Структурний ключ виразу без позицій у коді; `None`, якщо вираз не чистий
+fn key(expr: &Expression) -> Option<String> {
+    let list = |items: &[Expression]| items.iter().map(key).collect::<Option<Vec<_>>>().map(|k| k.join(" "));
+    Some(match expr {
+        Expression::Literal(literal) => format!("{:?}", literal),
+        Expression::Identifier(name) => name.clone(),
+        Expression::SelfRef => "себе".to_string(),
+        Expression::Binary { left, op, right, .. } => format!("({:?} {} {})", op, key(left)?, key(right)?),
+        Expression::Unary { op, operand } => format!("({:?} {})", op, key(operand)?),
+        Expression::Index { object, index } => format!("([] {} {})", key(object)?, key(index)?),
+        Expression::MemberAccess { object, member } => format!("(. {} {})", key(object)?, member),
+        Expression::Array(items) => format!("[{}]", list(items)?),
+        Expression::Tuple(items) => format!("(, {})", list(items)?),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+    use tryzub_lexer::tokenize;
+
+    fn optimized_body(source: &str) -> Vec<Statement> {
+        let program = eliminate_common_subexpressions(parse(tokenize(source).unwrap()).unwrap());
+        match program.declarations.into_iter().next() {
+            Some(Declaration::Function { body, .. }) => body,
+            other => panic!("очікувалась функція, отримано {:?}", other),
+        }
+    }
+
+    fn count(stmts: &[Statement], expr: &str) -> usize {
+        format!("{:?}", stmts).matches(expr).count()
+    }
+
+    #[test]
+    fn test_common_product_computed_once() {
+        let body = optimized_body("функція f(а: цл64, б: цл64) -> цл64 { стала с = а * б + а * б\n повернути с }");
+        let product = "Binary { left: Identifier(\"а\"), op: Mul, right: Identifier(\"б\")";
+        assert_eq!(count(&body, product), 1, "{:#?}", body);
+        match &body[..] {
+            [Statement::Declaration(Declaration::Variable { name, value: Some(value), .. }), Statement::Declaration(Declaration::Variable { value: Some(sum), .. }), Statement::Return(_)] => {
+                assert_eq!(name, "__спільне0");
+                assert!(format!("{:?}", value).starts_with(product));
+                let temp = Expression::Identifier("__спільне0".to_string());
+                assert!(matches!(sum, Expression::Binary { left, op: BinaryOp::Add, right, .. } if **left == temp && **right == temp));
+            }
+            other => panic!("неочікуване тіло: {:#?}", other),
+        }
+    }
+
+    #[test]
+    fn test_keeps_calls_and_short_circuit() {
+        // Виклик може мати побічні ефекти — інструкцію не змінюємо
+        let body = optimized_body("функція f(а: цл64) { друк(а * 2 + а * 2) }");
+        assert_eq!(count(&body, "op: Mul"), 2);
+
+        // `б / а` обчислюється лише після перевірки `а != 0`, тож не виноситься
+        let body = optimized_body("функція f(а: цл64, б: цл64) -> лог { повернути а != 0 && б / а > 1 && б / а < 5 }");
+        assert_eq!(count(&body, "op: Div"), 2);
+        assert_eq!(body.len(), 1);
+
+        // Безумовне входження дозволяє замінити й умовне
+        let body = optimized_body("функція f(а: цл64) -> лог { повернути а * а > 1 && а * а < 9 }");
+        assert_eq!(count(&body, "op: Mul"), 1);
+    }
+
+    #[test]
+    fn test_reuses_value_across_statements() {
+        let body = optimized_body("функція f(х: цл64, у: цл64) -> цл64 { стала а = х * у\n стала б = х * у + 1\n повернути б }");
+        assert_eq!(count(&body, "op: Mul"), 1, "{:#?}", body);
+        match &body[1] {
+            Statement::Declaration(Declaration::Variable { value: Some(Expression::Binary { left, .. }), .. }) => {
+                assert_eq!(**left, Expression::Identifier("а".to_string()));
+            }
+            other => panic!("неочікувана інструкція: {:#?}", other),
+        }
+
+        // Присвоєння операнду скасовує збережене значення
+        let body = optimized_body("функція f(х: цл64, у: цл64) -> цл64 { стала а = х * у\n х = 2\n стала б = х * у\n повернути а + б }");
+        assert_eq!(count(&body, "op: Mul"), 2, "{:#?}", body);
+
+        // Виклик між інструкціями теж: він міг змінити що завгодно
+        let body = optimized_body("функція f(х: цл64, у: цл64) -> цл64 { стала а = х * у\n друк(а)\n стала б = х * у\n повернути а + б }");
+        assert_eq!(count(&body, "op: Mul"), 2, "{:#?}", body);
+    }
+}
@@ -1,25 +1,51 @@
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use thiserror::Error;
 use tryzub_lexer::{Token, TokenKind, StringPart};
 
+pub mod binary;
+pub mod cse;
+pub mod semantic;
+
 // ════════════════════════════════════════════════════════════════════
 // AST — Абстрактне синтаксичне дерево мови Тризуб v2.0
 // ════════════════════════════════════════════════════════════════════
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Program {
     pub declarations: Vec<Declaration>,
+    /// Імена декларацій, позначених `експорт` — інтерфейс модуля
+    pub exports: Vec<String>,
+    /// Директиви виконання `//@назва значення` у порядку появи
+    pub directives: Vec<Directive>,
+}
+
+/// Директива виконання з коментаря `//@назва значення`; тлумачить її VM
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Directive {
+    pub name: String,
+    pub value: String,
+    pub line: usize,
+}
+
+/// Позиція у вихідному коді (рядок і стовпець рахуються з 1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
 }
 
 // ── Декларації ──
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Declaration {
     Variable {
         name: String,
         ty: Option<Type>,
         value: Option<Expression>,
         is_mutable: bool,
+        span: Span,
     },
     Function {
         name: String,
@@ -30,13 +56,22 @@ pub enum Declaration {
         is_async: bool,
         visibility: Visibility,
         contract: Option<Contract>,
+        /// Статичний метод (`статичний функція`) — без `себе`, викликається через ім'я типу
+        is_static: bool,
+        span: Span,
+        /// Документація з коментарів `///` перед оголошенням
+        doc: Option<String>,
     },
     Struct {
         name: String,
         generic_params: Vec<String>,
         fields: Vec<Field>,
         methods: Vec<Declaration>,
+        /// Інтерфейси зі списку `реалізує А, Б`
+        interfaces: Vec<String>,
         visibility: Visibility,
+        doc: Option<String>,
+        span: Span,
     },
     /// Алгебраїчний тип (enum / sum type)
     Enum {
@@ -44,6 +79,7 @@ pub enum Declaration {
         generic_params: Vec<String>,
         variants: Vec<EnumVariant>,
         visibility: Visibility,
+        doc: Option<String>,
     },
     /// Трейт (типаж)
     Trait {
@@ -51,6 +87,7 @@ pub enum Declaration {
         generic_params: Vec<String>,
         methods: Vec<TraitMethod>,
         visibility: Visibility,
+        doc: Option<String>,
     },
     /// Реалізація трейта для типу: реалізація Трейт для Тип { ... }
     TraitImpl {
@@ -113,10 +150,83 @@ pub enum Declaration {
         sizes: Vec<Expression>,
         body: Vec<Statement>,
     },
+    /// Декларація з атрибутами `@назва(аргументи)`; `Program::configured`
+    /// розгортає її або відкидає за умовою `@якщо(ціль)`
+    Attributed {
+        attributes: Vec<Attribute>,
+        declaration: Box<Declaration>,
+    },
+}
+
+impl Declaration {
+    /// Ім'я декларації (якщо вона іменована)
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            Declaration::Variable { name, .. }
+            | Declaration::Function { name, .. }
+            | Declaration::Struct { name, .. }
+            | Declaration::Enum { name, .. }
+            | Declaration::Trait { name, .. }
+            | Declaration::Module { name, .. }
+            | Declaration::TypeAlias { name, .. }
+            | Declaration::Interface { name, .. }
+            | Declaration::Effect { name, .. }
+            | Declaration::Macro { name, .. } => Some(name),
+            Declaration::Attributed { declaration, .. } => declaration.name(),
+            _ => None,
+        }
+    }
+
+    /// Документація з коментарів `///` (для функцій, структур, переліків і трейтів)
+    pub fn doc(&self) -> Option<&str> {
+        match self {
+            Declaration::Function { doc, .. }
+            | Declaration::Struct { doc, .. }
+            | Declaration::Enum { doc, .. }
+            | Declaration::Trait { doc, .. } => doc.as_deref(),
+            Declaration::Attributed { declaration, .. } => declaration.doc(),
+            _ => None,
+        }
+    }
+
+    fn set_doc(&mut self, text: Option<String>) {
+        match self {
+            Declaration::Function { doc, .. }
+            | Declaration::Struct { doc, .. }
+            | Declaration::Enum { doc, .. }
+            | Declaration::Trait { doc, .. } => *doc = text,
+            Declaration::Attributed { declaration, .. } => declaration.set_doc(text),
+            _ => {}
+        }
+    }
+}
+
+impl Program {
+    /// Застосовує умовну компіляцію для поточної платформи: декларації з
+    /// увімкненим `@якщо(...)` розгортаються, решта відкидається
+    pub fn configured(self) -> Program {
+        Program { declarations: configure_declarations(self.declarations), ..self }
+    }
+}
+
+fn configure_declarations(declarations: Vec<Declaration>) -> Vec<Declaration> {
+    declarations.into_iter().filter_map(|decl| match decl {
+        Declaration::Attributed { attributes, declaration } => attributes
+            .iter()
+            .all(Attribute::is_enabled)
+            .then(|| configure_declarations(vec![*declaration]).pop())
+            .flatten(),
+        Declaration::Module { name, declarations, visibility } => Some(Declaration::Module {
+            name,
+            declarations: configure_declarations(declarations),
+            visibility,
+        }),
+        other => Some(other),
+    }).collect()
 }
 
 /// Вхідний параметр для фаз-тесту
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FuzzInput {
     pub name: String,
     pub ty: Type,
@@ -124,7 +234,7 @@ pub struct FuzzInput {
 }
 
 /// Контракт функції (вимагає/гарантує)
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Contract {
     pub preconditions: Vec<Expression>,    // вимагає { ... }
     pub postconditions: Vec<Expression>,   // гарантує { ... }
@@ -132,32 +242,48 @@ pub struct Contract {
 }
 
 /// Ефекти функції [ввід_вивід, мережа]
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct EffectAnnotation {
     pub effects: Vec<String>,
     pub is_pure: bool,  // чистий
 }
 
-/// Атрибут #[назва(аргументи)]
-#[derive(Debug, Clone, PartialEq)]
+/// Атрибут `@назва(аргументи)`; кожен аргумент — лексеми через пробіл
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Attribute {
     pub name: String,
     pub args: Vec<String>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+impl Attribute {
+    /// Чи лишається декларація на поточній платформі. Лише `@якщо(ціль)`
+    /// і `@якщо(не ціль)` щось вимикають; ціль — ОС (`linux`, `windows`,
+    /// `macos`) або сімейство (`unix`, `windows`)
+    pub fn is_enabled(&self) -> bool {
+        if self.name != "якщо" {
+            return true;
+        }
+        let matches = |target: &str| target == std::env::consts::OS || target == std::env::consts::FAMILY;
+        self.args.iter().all(|arg| match arg.strip_prefix("не ") {
+            Some(target) => !matches(target.trim()),
+            None => matches(arg),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct EnumVariant {
     pub name: String,
     pub fields: Vec<EnumField>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct EnumField {
     pub name: Option<String>, // Іменовані або позиційні
     pub ty: Type,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TraitMethod {
     pub name: String,
     pub params: Vec<Parameter>,
@@ -166,28 +292,28 @@ pub struct TraitMethod {
     pub has_self: bool,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Parameter {
     pub name: String,
     pub ty: Type,
     pub default: Option<Expression>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Field {
     pub name: String,
     pub ty: Type,
     pub visibility: Visibility,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct InterfaceMethod {
     pub name: String,
     pub params: Vec<Parameter>,
     pub return_type: Option<Type>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Visibility {
     Public,
     Private,
@@ -195,7 +321,7 @@ pub enum Visibility {
 
 // ── Типи ──
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Type {
     Цл8, Цл16, Цл32, Цл64,
     Чс8, Чс16, Чс32, Чс64,
@@ -217,7 +343,7 @@ pub enum Type {
 
 // ── Інструкції (Statements) ──
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Statement {
     Expression(Expression),
     Block(Vec<Statement>),
@@ -226,10 +352,12 @@ pub enum Statement {
         condition: Expression,
         then_branch: Box<Statement>,
         else_branch: Option<Box<Statement>>,
+        span: Span,
     },
     While {
         condition: Expression,
         body: Box<Statement>,
+        span: Span,
     },
     For {
         variable: String,
@@ -237,6 +365,7 @@ pub enum Statement {
         to: Expression,
         step: Option<Expression>,
         body: Box<Statement>,
+        span: Span,
     },
     /// Новий for...в (for-in) для ітерації по колекціях/діапазонах
     ForIn {
@@ -245,11 +374,20 @@ pub enum Statement {
         body: Box<Statement>,
     },
     Break,
+    /// `переривати значення` — достроковий вихід з найближчого блоку-виразу з цим значенням
+    BreakValue(Expression),
     Continue,
     Assignment {
         target: Expression,
         value: Expression,
         op: AssignmentOp,
+        span: Span,
+    },
+    /// Множинне присвоєння: а, б = б, а
+    MultiAssign {
+        targets: Vec<Expression>,
+        value: Expression,
+        span: Span,
     },
     Declaration(Declaration),
     /// Деструктуризація: змінна { a, b, ..rest } = expr
@@ -278,9 +416,11 @@ pub enum Statement {
     Unsafe(Vec<Statement>),
     /// Yield: віддати вираз
     Yield(Expression),
+    /// Відкладена дія: відкласти { ... } — виконується при виході з функції, у порядку LIFO
+    Defer(Box<Statement>),
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum AssignmentOp {
     Assign,
     AddAssign,
@@ -292,7 +432,7 @@ pub enum AssignmentOp {
 
 // ── Зразки (Patterns) для pattern matching та деструктуризації ──
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Pattern {
     /// Wildcard: _
     Wildcard,
@@ -328,7 +468,7 @@ pub enum Pattern {
 
 // ── Вирази ──
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Expression {
     Literal(Literal),
     Identifier(String),
@@ -337,6 +477,7 @@ pub enum Expression {
         left: Box<Expression>,
         op: BinaryOp,
         right: Box<Expression>,
+        span: Span,
     },
     Unary {
         op: UnaryOp,
@@ -345,6 +486,7 @@ pub enum Expression {
     Call {
         callee: Box<Expression>,
         args: Vec<Expression>,
+        span: Span,
     },
     Index {
         object: Box<Expression>,
@@ -359,6 +501,7 @@ pub enum Expression {
         object: Box<Expression>,
         method: String,
         args: Vec<Expression>,
+        span: Span,
     },
     Array(Vec<Expression>),
     Tuple(Vec<Expression>),
@@ -418,27 +561,29 @@ pub enum Expression {
     Path {
         segments: Vec<String>,
     },
+    /// Блок-вираз `{ інструкції; результат }`: значення — останній вираз без `;`
+    Block(Vec<Statement>, Option<Box<Expression>>),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct LambdaParam {
     pub name: String,
     pub ty: Option<Type>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MatchArm {
     pub pattern: Pattern,
     pub body: Expression,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum FormatPart {
     Text(String),
     Expr(Expression),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Literal {
     Integer(i64),
     Float(f64),
@@ -448,7 +593,7 @@ pub enum Literal {
     Null,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum BinaryOp {
     Add, Sub, Mul, Div, Mod, Pow,
     Eq, Ne, Lt, Le, Gt, Ge,
@@ -460,7 +605,7 @@ pub enum BinaryOp {
     In, // x в масив
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum UnaryOp {
     Neg, Not, BitNot,
 }
@@ -471,50 +616,206 @@ pub enum UnaryOp {
 
 #[derive(Error, Debug)]
 pub enum ParseError {
-    #[error("Несподіваний токен: очікувався {expected}, отримано {found} на рядку {line}")]
+    #[error("Несподіваний токен: очікувався {expected}, отримано {found} на рядку {line}, стовпці {column}")]
     UnexpectedToken {
         expected: String,
         found: String,
         line: usize,
+        column: usize,
     },
 
-    #[error("Несподіваний кінець файлу")]
-    UnexpectedEof,
+    #[error("Несподіваний кінець файлу на рядку {line}, стовпці {column}")]
+    UnexpectedEof {
+        line: usize,
+        column: usize,
+    },
+
+    #[error("Невалідний вираз на рядку {0}, стовпці {1}")]
+    InvalidExpression(usize, usize),
+
+    #[error("Невалідне оголошення на рядку {0}, стовпці {1}")]
+    InvalidDeclaration(usize, usize),
 
-    #[error("Невалідний вираз на рядку {0}")]
-    InvalidExpression(usize),
+    #[error("Невалідний зразок на рядку {0}, стовпці {1}")]
+    InvalidPattern(usize, usize),
 
-    #[error("Невалідне оголошення на рядку {0}")]
-    InvalidDeclaration(usize),
+    #[error("Незакрита дужка '{bracket}', відкрита на рядку {line}, стовпці {column}")]
+    UnclosedBracket { bracket: char, line: usize, column: usize },
+
+    #[error("Зайва дужка '{bracket}' на рядку {line}, стовпці {column}")]
+    UnmatchedBracket { bracket: char, line: usize, column: usize },
+
+    #[error("Дужка '{found}' на рядку {line}, стовпці {column} не закриває '{open}', відкриту на рядку {open_line}, стовпці {open_column}")]
+    MismatchedBracket {
+        found: char,
+        line: usize,
+        column: usize,
+        open: char,
+        open_line: usize,
+        open_column: usize,
+    },
+}
 
-    #[error("Невалідний зразок на рядку {0}")]
-    InvalidPattern(usize),
+impl ParseError {
+    /// Рядок і стовпець помилки; для непарної дужки — позиція самої дужки
+    pub fn position(&self) -> (usize, usize) {
+        match self {
+            ParseError::UnexpectedToken { line, column, .. }
+            | ParseError::UnexpectedEof { line, column }
+            | ParseError::UnclosedBracket { line, column, .. }
+            | ParseError::UnmatchedBracket { line, column, .. }
+            | ParseError::MismatchedBracket { line, column, .. } => (*line, *column),
+            ParseError::InvalidExpression(line, column)
+            | ParseError::InvalidDeclaration(line, column)
+            | ParseError::InvalidPattern(line, column) => (*line, *column),
+        }
+    }
 }
 
 pub struct Parser {
     tokens: Vec<Token>,
     current: usize,
+    exports: Vec<String>,
+    /// Цілочисельні сталі, відомі на етапі парсингу (для розмірів масивів)
+    constants: HashMap<String, i64>,
+    /// Документація `///`: індекс токена, перед яким вона стояла → текст
+    docs: HashMap<usize, String>,
+    directives: Vec<Directive>,
 }
 
 impl Parser {
     pub fn new(tokens: Vec<Token>) -> Self {
-        Self { tokens, current: 0 }
+        // Коментарі `///` вилучаємо з потоку й запам'ятовуємо за індексом наступного токена,
+        // директиви `//@` — збираємо окремо
+        let mut filtered = Vec::with_capacity(tokens.len());
+        let mut docs = HashMap::new();
+        let mut directives = Vec::new();
+        let mut pending: Vec<String> = Vec::new();
+        for token in tokens {
+            match token.kind {
+                TokenKind::ДокКоментар(text) => {
+                    pending.push(text);
+                    continue;
+                }
+                TokenKind::Директива(name, value) => {
+                    directives.push(Directive { name, value, line: token.line });
+                    continue;
+                }
+                _ => {}
+            }
+            if !pending.is_empty() {
+                docs.insert(filtered.len(), pending.join("\n"));
+                pending.clear();
+            }
+            filtered.push(token);
+        }
+        Self { tokens: filtered, current: 0, exports: Vec::new(), constants: HashMap::new(), docs, directives }
     }
 
     pub fn parse(&mut self) -> Result<Program> {
         let mut declarations = Vec::new();
 
         while !self.is_at_end() {
-            declarations.push(self.declaration()?);
+            match self.declaration() {
+                Ok(decl) => declarations.push(decl),
+                // Незбалансовані дужки — найчастіша причина; вказуємо, де відкрито непарну
+                Err(e) => return Err(match check_brackets(&self.tokens) {
+                    Err(hint) => {
+                        let message = format!("{}\nПідказка: {}", e, hint);
+                        e.context(message)
+                    }
+                    Ok(()) => e,
+                }),
+            }
         }
 
-        Ok(Program { declarations })
+        Ok(Program {
+            declarations,
+            exports: std::mem::take(&mut self.exports),
+            directives: std::mem::take(&mut self.directives),
+        })
+    }
+
+    /// Розбір з відновленням після помилок: збирає всі діагностики замість
+    /// зупинки на першій. Програма повертається лише якщо помилок не було.
+    pub fn parse_recover(&mut self) -> (Option<Program>, Vec<ParseError>) {
+        let mut declarations = Vec::new();
+        let mut errors = Vec::new();
+
+        while !self.is_at_end() {
+            let start = self.current;
+            match self.declaration() {
+                Ok(decl) => declarations.push(decl),
+                Err(e) => {
+                    let (line, column) = (self.peek().line, self.peek().column);
+                    errors.push(match e.downcast::<ParseError>() {
+                        Ok(pe) => pe,
+                        Err(_) => ParseError::InvalidExpression(line, column),
+                    });
+                    // Повертаємось на початок декларації, щоб коректно пропустити її блок
+                    self.current = start;
+                    self.synchronize();
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            let program = Program {
+                declarations,
+                exports: std::mem::take(&mut self.exports),
+                directives: std::mem::take(&mut self.directives),
+            };
+            (Some(program), errors)
+        } else {
+            (None, errors)
+        }
+    }
+
+    /// Пропускає токени до наступної точки синхронізації: після `}`, що закриває
+    /// блок декларації, або перед `функція`/`змінна`/іншою декларацією верхнього рівня.
+    fn synchronize(&mut self) {
+        let mut depth = 0usize;
+        let mut first = true;
+
+        while !self.is_at_end() {
+            if depth == 0 && !first {
+                match self.peek().kind {
+                    TokenKind::Функція | TokenKind::Змінна | TokenKind::Стала
+                    | TokenKind::Структура | TokenKind::Тип | TokenKind::Трейт
+                    | TokenKind::Реалізація | TokenKind::Модуль | TokenKind::Імпорт
+                    | TokenKind::Публічний | TokenKind::Приватний | TokenKind::Асинхронний
+                    | TokenKind::Тест => return,
+                    _ => {}
+                }
+            }
+            first = false;
+
+            match self.advance().kind {
+                TokenKind::ЛіваФігурна => depth += 1,
+                TokenKind::ПраваФігурна => {
+                    if depth <= 1 {
+                        return;
+                    }
+                    depth -= 1;
+                }
+                _ => {}
+            }
+        }
     }
 
     // ── Декларації ──
 
     fn declaration(&mut self) -> Result<Declaration> {
-        let visibility = if self.match_token(&TokenKind::Публічний) {
+        let doc = self.docs.remove(&self.current);
+        let attributes = self.attributes()?;
+        if !attributes.is_empty() {
+            let mut declaration = self.declaration()?;
+            declaration.set_doc(doc);
+            return Ok(Declaration::Attributed { attributes, declaration: Box::new(declaration) });
+        }
+        // експорт — публічна видимість + запис в інтерфейс модуля
+        let is_exported = self.match_token(&TokenKind::Експорт);
+        let visibility = if is_exported || self.match_token(&TokenKind::Публічний) {
             Visibility::Public
         } else if self.match_token(&TokenKind::Приватний) {
             Visibility::Private
@@ -522,6 +823,49 @@ impl Parser {
             Visibility::Private
         };
 
+        let mut decl = self.declaration_body(visibility)?;
+        decl.set_doc(doc);
+        if is_exported {
+            match decl.name() {
+                Some(name) => self.exports.push(name.to_string()),
+                None => return Err(ParseError::InvalidDeclaration(self.previous().line, self.previous().column).into()),
+            }
+        }
+        Ok(decl)
+    }
+
+    /// Атрибути `@якщо(не windows)` перед декларацією
+    fn attributes(&mut self) -> Result<Vec<Attribute>> {
+        let mut attributes = Vec::new();
+        while self.match_token(&TokenKind::Собака) {
+            // якщо — ключове слово, тож ім'я атрибута беремо з лексеми
+            let name = if self.match_token(&TokenKind::Якщо) {
+                "якщо".to_string()
+            } else {
+                self.consume_identifier("Очікувалось ім'я атрибута після '@'")?
+            };
+            let mut args = Vec::new();
+            if self.match_token(&TokenKind::ЛіваДужка) {
+                let mut current: Vec<String> = Vec::new();
+                while !self.check(&TokenKind::ПраваДужка) && !self.is_at_end() {
+                    let token = self.advance();
+                    if token.kind == TokenKind::Кома {
+                        args.push(std::mem::take(&mut current).join(" "));
+                    } else {
+                        current.push(token.lexeme.clone());
+                    }
+                }
+                if !current.is_empty() {
+                    args.push(current.join(" "));
+                }
+                self.consume(&TokenKind::ПраваДужка, "Очікувалась ')' після аргументів атрибута")?;
+            }
+            attributes.push(Attribute { name, args });
+        }
+        Ok(attributes)
+    }
+
+    fn declaration_body(&mut self, visibility: Visibility) -> Result<Declaration> {
         if self.match_token(&TokenKind::Змінна) || self.match_token(&TokenKind::Стала) {
             let is_mutable = self.previous().kind == TokenKind::Змінна;
             self.variable_declaration(is_mutable)
@@ -555,11 +899,12 @@ impl Parser {
         } else if self.match_token(&TokenKind::Бенчмарк) {
             self.benchmark_declaration()
         } else {
-            Err(ParseError::InvalidDeclaration(self.peek().line).into())
+            Err(ParseError::InvalidDeclaration(self.peek().line, self.peek().column).into())
         }
     }
 
     fn variable_declaration(&mut self, is_mutable: bool) -> Result<Declaration> {
+        let span = self.previous_span();
         let name = self.consume_identifier("Очікувалось ім'я змінної")?;
 
         let ty = if self.match_token(&TokenKind::Двокрапка) {
@@ -574,10 +919,17 @@ impl Parser {
             None
         };
 
-        Ok(Declaration::Variable { name, ty, value, is_mutable })
+        if !is_mutable {
+            if let Some(n) = value.as_ref().and_then(|v| self.const_eval(v)) {
+                self.constants.insert(name.clone(), n);
+            }
+        }
+
+        Ok(Declaration::Variable { name, ty, value, is_mutable, span })
     }
 
     fn function_declaration(&mut self, is_async: bool, visibility: Visibility) -> Result<Declaration> {
+        let span = self.previous_span();
         let name = self.consume_identifier("Очікувалось ім'я функції")?;
 
         let generic_params = self.parse_generic_params()?;
@@ -616,7 +968,7 @@ impl Parser {
                     });
                 }
 
-                if !self.match_token(&TokenKind::Кома) {
+                if !self.list_separator(&TokenKind::ПраваДужка) {
                     break;
                 }
             }
@@ -682,14 +1034,60 @@ impl Parser {
             is_async,
             visibility,
             contract,
+            is_static: false,
+            span,
+            doc: None,
         })
     }
 
+    /// Член блоку реалізації: `функція`, `статичний функція` або `статичний змінна/стала`
+    fn impl_member(&mut self) -> Result<Declaration> {
+        let doc = self.docs.remove(&self.current);
+        let mut decl = self.impl_member_body()?;
+        decl.set_doc(doc);
+        Ok(decl)
+    }
+
+    fn impl_member_body(&mut self) -> Result<Declaration> {
+        if self.match_token(&TokenKind::Статичний) {
+            if self.match_token(&TokenKind::Змінна) || self.match_token(&TokenKind::Стала) {
+                let is_mutable = self.previous().kind == TokenKind::Змінна;
+                return self.variable_declaration(is_mutable);
+            }
+            self.consume(&TokenKind::Функція, "Очікувалась 'функція' або 'змінна' після 'статичний'")?;
+            let (line, column) = (self.peek().line, self.peek().column);
+            let mut decl = self.function_declaration(false, Visibility::Public)?;
+            if let Declaration::Function { params, is_static, .. } = &mut decl {
+                // Статичний метод не має доступу до екземпляра
+                if params.iter().any(|p| p.name == "себе") {
+                    return Err(ParseError::InvalidDeclaration(line, column).into());
+                }
+                *is_static = true;
+            }
+            return Ok(decl);
+        }
+
+        self.consume(&TokenKind::Функція, "Очікувалась 'функція'")?;
+        self.function_declaration(false, Visibility::Public)
+    }
+
     fn struct_declaration(&mut self, visibility: Visibility) -> Result<Declaration> {
+        let span = self.previous_span();
         let name = self.consume_identifier("Очікувалось ім'я структури")?;
 
         let generic_params = self.parse_generic_params()?;
 
+        // структура Назва реалізує Інтерфейс1, Інтерфейс2 { ... }
+        let mut interfaces = Vec::new();
+        if self.match_token(&TokenKind::Реалізує) {
+            loop {
+                interfaces.push(self.consume_identifier("Очікувалось ім'я інтерфейсу після 'реалізує'")?);
+                if !self.match_token(&TokenKind::Кома) {
+                    break;
+                }
+            }
+        }
+
         self.consume(&TokenKind::ЛіваФігурна, "Очікувалась '{'")?;
 
         let mut fields = Vec::new();
@@ -717,7 +1115,7 @@ impl Parser {
 
         self.consume(&TokenKind::ПраваФігурна, "Очікувалась '}'")?;
 
-        Ok(Declaration::Struct { name, generic_params, fields, methods: Vec::new(), visibility })
+        Ok(Declaration::Struct { name, generic_params, fields, methods: Vec::new(), interfaces, visibility, doc: None, span })
     }
 
     /// тип Назва<Т> { Варіант1(поля), Варіант2 }
@@ -776,7 +1174,7 @@ impl Parser {
 
         self.consume(&TokenKind::ПраваФігурна, "Очікувалась '}'")?;
 
-        Ok(Declaration::Enum { name, generic_params, variants, visibility })
+        Ok(Declaration::Enum { name, generic_params, variants, visibility, doc: None })
     }
 
     /// трейт Назва<Т> { функція метод(себе) -> Тип ... }
@@ -807,7 +1205,7 @@ impl Parser {
                         params.push(Parameter { name: param_name, ty: param_type, default: None });
                     }
 
-                    if !self.match_token(&TokenKind::Кома) {
+                    if !self.list_separator(&TokenKind::ПраваДужка) {
                         break;
                     }
                 }
@@ -845,7 +1243,7 @@ impl Parser {
 
         self.consume(&TokenKind::ПраваФігурна, "Очікувалась '}'")?;
 
-        Ok(Declaration::Trait { name, generic_params, methods, visibility })
+        Ok(Declaration::Trait { name, generic_params, methods, visibility, doc: None })
     }
 
     /// реалізація Трейт для Тип { ... } або реалізація Тип { ... }
@@ -860,9 +1258,7 @@ impl Parser {
             self.consume(&TokenKind::ЛіваФігурна, "Очікувалась '{'")?;
             let mut methods = Vec::new();
             while !self.check(&TokenKind::ПраваФігурна) && !self.is_at_end() {
-                let vis = Visibility::Public;
-                self.consume(&TokenKind::Функція, "Очікувалась 'функція'")?;
-                methods.push(self.function_declaration(false, vis)?);
+                methods.push(self.impl_member()?);
             }
             self.consume(&TokenKind::ПраваФігурна, "Очікувалась '}'")?;
 
@@ -877,9 +1273,7 @@ impl Parser {
             self.consume(&TokenKind::ЛіваФігурна, "Очікувалась '{'")?;
             let mut methods = Vec::new();
             while !self.check(&TokenKind::ПраваФігурна) && !self.is_at_end() {
-                let vis = Visibility::Public;
-                self.consume(&TokenKind::Функція, "Очікувалась 'функція'")?;
-                methods.push(self.function_declaration(false, vis)?);
+                methods.push(self.impl_member()?);
             }
             self.consume(&TokenKind::ПраваФігурна, "Очікувалась '}'")?;
 
@@ -947,11 +1341,15 @@ impl Parser {
             let mut params = Vec::new();
             if !self.check(&TokenKind::ПраваДужка) {
                 loop {
-                    let param_name = self.consume_identifier("Очікувалось ім'я параметра")?;
-                    self.consume(&TokenKind::Двокрапка, "Очікувалась ':'")?;
-                    let param_type = self.parse_type()?;
-                    params.push(Parameter { name: param_name, ty: param_type, default: None });
-                    if !self.match_token(&TokenKind::Кома) { break; }
+                    if self.match_token(&TokenKind::Себе) {
+                        params.push(Parameter { name: "себе".to_string(), ty: Type::SelfType, default: None });
+                    } else {
+                        let param_name = self.consume_identifier("Очікувалось ім'я параметра")?;
+                        self.consume(&TokenKind::Двокрапка, "Очікувалась ':'")?;
+                        let param_type = self.parse_type()?;
+                        params.push(Parameter { name: param_name, ty: param_type, default: None });
+                    }
+                    if !self.list_separator(&TokenKind::ПраваДужка) { break; }
                 }
             }
             self.consume(&TokenKind::ПраваДужка, "Очікувалась ')'")?;
@@ -1118,7 +1516,17 @@ impl Parser {
             let value = if self.check(&TokenKind::ПраваФігурна) || self.check(&TokenKind::КрапкаЗКомою) {
                 None
             } else {
-                Some(self.expression()?)
+                let first = self.expression()?;
+                if self.check(&TokenKind::Кома) {
+                    // повернути а, б — кілька значень повертаються як масив
+                    let mut values = vec![first];
+                    while self.match_token(&TokenKind::Кома) {
+                        values.push(self.expression()?);
+                    }
+                    Some(Expression::Array(values))
+                } else {
+                    Some(first)
+                }
             };
             Ok(Statement::Return(value))
         } else if self.match_token(&TokenKind::Якщо) {
@@ -1128,9 +1536,18 @@ impl Parser {
         } else if self.match_token(&TokenKind::Для) {
             self.for_statement()
         } else if self.match_token(&TokenKind::Переривати) {
-            Ok(Statement::Break)
+            // Значення має стояти на тому ж рядку, інакше це звичайне `переривати` циклу
+            let line = self.previous().line;
+            if self.peek().line == line && !self.is_at_end()
+                && !self.check(&TokenKind::ПраваФігурна) && !self.check(&TokenKind::КрапкаЗКомою) {
+                Ok(Statement::BreakValue(self.expression()?))
+            } else {
+                Ok(Statement::Break)
+            }
         } else if self.match_token(&TokenKind::Продовжити) {
             Ok(Statement::Continue)
+        } else if self.match_token(&TokenKind::Відкласти) {
+            Ok(Statement::Defer(Box::new(self.statement()?)))
         } else if self.match_token(&TokenKind::ЛіваФігурна) {
             self.block_statement()
         } else if self.match_token(&TokenKind::Спробувати) {
@@ -1170,6 +1587,7 @@ impl Parser {
     }
 
     fn if_statement(&mut self) -> Result<Statement> {
+        let span = self.previous_span();
         let has_parens = self.match_token(&TokenKind::ЛіваДужка);
         let condition = self.expression()?;
         if has_parens { self.consume(&TokenKind::ПраваДужка, "Очікувалась ')'")?; }
@@ -1181,19 +1599,21 @@ impl Parser {
             None
         };
 
-        Ok(Statement::If { condition, then_branch, else_branch })
+        Ok(Statement::If { condition, then_branch, else_branch, span })
     }
 
     fn while_statement(&mut self) -> Result<Statement> {
+        let span = self.previous_span();
         let has_parens = self.match_token(&TokenKind::ЛіваДужка);
         let condition = self.expression()?;
         if has_parens { self.consume(&TokenKind::ПраваДужка, "Очікувалась ')'")?; }
         let body = Box::new(self.statement()?);
 
-        Ok(Statement::While { condition, body })
+        Ok(Statement::While { condition, body, span })
     }
 
     fn for_statement(&mut self) -> Result<Statement> {
+        let span = self.previous_span();
         let has_parens = self.match_token(&TokenKind::ЛіваДужка);
 
         let variable = self.consume_identifier("Очікувалось ім'я змінної циклу")?;
@@ -1223,7 +1643,7 @@ impl Parser {
             if has_parens { self.consume(&TokenKind::ПраваДужка, "Очікувалась ')'")?; }
             let body = Box::new(self.statement()?);
 
-            Ok(Statement::For { variable, from, to, step, body })
+            Ok(Statement::For { variable, from, to, step, body, span })
         }
     }
 
@@ -1260,11 +1680,31 @@ impl Parser {
     }
 
     fn expression_statement(&mut self) -> Result<Statement> {
+        let span = self.peek_span();
         let expr = self.expression()?;
 
+        if self.check(&TokenKind::Кома) {
+            let mut targets = vec![expr];
+            while self.match_token(&TokenKind::Кома) {
+                targets.push(self.expression()?);
+            }
+            self.consume(&TokenKind::Присвоїти, "Очікувалось '=' після списку цілей присвоєння")?;
+            let first = self.expression()?;
+            let value = if self.check(&TokenKind::Кома) {
+                let mut values = vec![first];
+                while self.match_token(&TokenKind::Кома) {
+                    values.push(self.expression()?);
+                }
+                Expression::Tuple(values)
+            } else {
+                first
+            };
+            return Ok(Statement::MultiAssign { targets, value, span });
+        }
+
         if let Some(op) = self.match_assignment_op() {
             let value = self.expression()?;
-            Ok(Statement::Assignment { target: expr, value, op })
+            Ok(Statement::Assignment { target: expr, value, op, span })
         } else {
             Ok(Statement::Expression(expr))
         }
@@ -1278,10 +1718,10 @@ impl Parser {
 
     /// Pipeline: вираз |> функція |> функція
     fn pipeline_expression(&mut self) -> Result<Expression> {
-        let mut expr = self.or_expression()?;
+        let mut expr = self.conditional_expression()?;
 
         while self.match_token(&TokenKind::Конвеєр) {
-            let right = self.or_expression()?;
+            let right = self.conditional_expression()?;
             expr = Expression::Pipeline {
                 left: Box::new(expr),
                 right: Box::new(right),
@@ -1291,14 +1731,72 @@ impl Parser {
         Ok(expr)
     }
 
+    /// Тернарний оператор: умова ? значення1 : значення2 (правоасоціативний)
+    fn conditional_expression(&mut self) -> Result<Expression> {
+        let condition = self.or_expression()?;
+        if !self.match_token(&TokenKind::ЗнакПитання) {
+            return Ok(condition);
+        }
+        let then_expr = self.conditional_expression()?;
+        self.consume(&TokenKind::Двокрапка, "Очікувалась ':' у тернарному операторі")?;
+        let else_expr = self.conditional_expression()?;
+        Ok(Expression::If {
+            condition: Box::new(condition),
+            then_expr: Box::new(then_expr),
+            else_expr: Box::new(else_expr),
+        })
+    }
+
+    /// Чи `?` на поточній позиції відкриває тернарний оператор, а не поширює помилку:
+    /// після нього на тому ж рядку має починатися вираз, а далі на тій самій глибині
+    /// дужок стояти `:`. Інакше `ф(5)? - 1` читалося б як тернарник без другої гілки
+    fn question_starts_ternary(&self) -> bool {
+        let question = self.peek();
+        let next = match self.tokens.get(self.current + 1) {
+            Some(token) if token.line == question.line => token,
+            _ => return false,
+        };
+        let starts_expression = matches!(next.kind,
+            TokenKind::ЦілеЧисло(_) | TokenKind::ДробовеЧисло(_) | TokenKind::Рядок(_) |
+            TokenKind::ФормРядок(_) | TokenKind::Символ(_) | TokenKind::Логічне(_) |
+            TokenKind::Ідентифікатор(_) | TokenKind::Істина | TokenKind::Хиба | TokenKind::Нуль |
+            TokenKind::ЛіваДужка | TokenKind::ЛіваКвадратна | TokenKind::Мінус | TokenKind::Не |
+            TokenKind::Вертикальна | TokenKind::Себе | TokenKind::Це);
+        if !starts_expression {
+            return false;
+        }
+
+        let mut depth = 0usize;
+        for token in &self.tokens[self.current + 1..] {
+            match token.kind {
+                TokenKind::ЛіваДужка | TokenKind::ЛіваКвадратна | TokenKind::ЛіваФігурна => depth += 1,
+                TokenKind::ПраваДужка | TokenKind::ПраваКвадратна | TokenKind::ПраваФігурна => {
+                    if depth == 0 {
+                        return false;
+                    }
+                    depth -= 1;
+                }
+                TokenKind::Двокрапка if depth == 0 => return true,
+                TokenKind::Кома | TokenKind::КрапкаЗКомою | TokenKind::НовийРядок | TokenKind::КінецьФайлу
+                    if depth == 0 => return false,
+                // Новий рядок поза дужками — вже наступна інструкція
+                _ if depth == 0 && token.line != question.line => return false,
+                _ => {}
+            }
+        }
+        false
+    }
+
     fn or_expression(&mut self) -> Result<Expression> {
         let mut expr = self.and_expression()?;
         while self.match_token(&TokenKind::Або) {
+            let span = self.previous_span();
             let right = self.and_expression()?;
             expr = Expression::Binary {
                 left: Box::new(expr),
                 op: BinaryOp::Or,
                 right: Box::new(right),
+                span,
             };
         }
         Ok(expr)
@@ -1307,11 +1805,13 @@ impl Parser {
     fn and_expression(&mut self) -> Result<Expression> {
         let mut expr = self.equality_expression()?;
         while self.match_token(&TokenKind::І) {
+            let span = self.previous_span();
             let right = self.equality_expression()?;
             expr = Expression::Binary {
                 left: Box::new(expr),
                 op: BinaryOp::And,
                 right: Box::new(right),
+                span,
             };
         }
         Ok(expr)
@@ -1320,8 +1820,9 @@ impl Parser {
     fn equality_expression(&mut self) -> Result<Expression> {
         let mut expr = self.relational_expression()?;
         while let Some(op) = self.match_equality_op() {
+            let span = self.previous_span();
             let right = self.relational_expression()?;
-            expr = Expression::Binary { left: Box::new(expr), op, right: Box::new(right) };
+            expr = Expression::Binary { left: Box::new(expr), op, right: Box::new(right), span };
         }
         Ok(expr)
     }
@@ -1329,8 +1830,9 @@ impl Parser {
     fn relational_expression(&mut self) -> Result<Expression> {
         let mut expr = self.range_expression()?;
         while let Some(op) = self.match_relational_op() {
+            let span = self.previous_span();
             let right = self.range_expression()?;
-            expr = Expression::Binary { left: Box::new(expr), op, right: Box::new(right) };
+            expr = Expression::Binary { left: Box::new(expr), op, right: Box::new(right), span };
         }
         Ok(expr)
     }
@@ -1360,11 +1862,15 @@ impl Parser {
         Ok(expr)
     }
 
-    /// Побітове OR: a | b (тільки коли не лямбда і не pipeline)
+    /// Побітове OR: a | b (інфіксна `|` після операнда; лямбда `|x|` починає вираз,
+    /// а `||` та `|>` лексер видає окремими токенами)
     fn bitwise_or_expression(&mut self) -> Result<Expression> {
-        let expr = self.bitwise_xor_expression()?;
-        // Note: | conflicts with lambda and pipeline, so we skip bitwise | here
-        // Use explicit бітАбо() function instead
+        let mut expr = self.bitwise_xor_expression()?;
+        while self.match_token(&TokenKind::Вертикальна) {
+            let span = self.previous_span();
+            let right = self.bitwise_xor_expression()?;
+            expr = Expression::Binary { left: Box::new(expr), op: BinaryOp::BitOr, right: Box::new(right), span };
+        }
         Ok(expr)
     }
 
@@ -1372,16 +1878,21 @@ impl Parser {
     fn bitwise_xor_expression(&mut self) -> Result<Expression> {
         let mut expr = self.bitwise_and_expression()?;
         while self.match_token(&TokenKind::БітВиключне) {
+            let span = self.previous_span();
             let right = self.bitwise_and_expression()?;
-            expr = Expression::Binary { left: Box::new(expr), op: BinaryOp::BitXor, right: Box::new(right) };
+            expr = Expression::Binary { left: Box::new(expr), op: BinaryOp::BitXor, right: Box::new(right), span };
         }
         Ok(expr)
     }
 
-    /// Побітове AND: a & b (тільки коли не посилання)
+    /// Побітове AND: a & b (`&` як посилання трапляється лише в типах, `&&` — логічне І)
     fn bitwise_and_expression(&mut self) -> Result<Expression> {
-        let expr = self.shift_expression()?;
-        // Note: & conflicts with references, skip bitwise & here
+        let mut expr = self.shift_expression()?;
+        while self.match_token(&TokenKind::Амперсанд) {
+            let span = self.previous_span();
+            let right = self.shift_expression()?;
+            expr = Expression::Binary { left: Box::new(expr), op: BinaryOp::BitAnd, right: Box::new(right), span };
+        }
         Ok(expr)
     }
 
@@ -1390,11 +1901,13 @@ impl Parser {
         let mut expr = self.additive_expression()?;
         loop {
             if self.match_token(&TokenKind::ЗсувЛіво) {
+                let span = self.previous_span();
                 let right = self.additive_expression()?;
-                expr = Expression::Binary { left: Box::new(expr), op: BinaryOp::Shl, right: Box::new(right) };
+                expr = Expression::Binary { left: Box::new(expr), op: BinaryOp::Shl, right: Box::new(right), span };
             } else if self.match_token(&TokenKind::ЗсувПраво) {
+                let span = self.previous_span();
                 let right = self.additive_expression()?;
-                expr = Expression::Binary { left: Box::new(expr), op: BinaryOp::Shr, right: Box::new(right) };
+                expr = Expression::Binary { left: Box::new(expr), op: BinaryOp::Shr, right: Box::new(right), span };
             } else {
                 break;
             }
@@ -1405,8 +1918,9 @@ impl Parser {
     fn additive_expression(&mut self) -> Result<Expression> {
         let mut expr = self.multiplicative_expression()?;
         while let Some(op) = self.match_additive_op() {
+            let span = self.previous_span();
             let right = self.multiplicative_expression()?;
-            expr = Expression::Binary { left: Box::new(expr), op, right: Box::new(right) };
+            expr = Expression::Binary { left: Box::new(expr), op, right: Box::new(right), span };
         }
         Ok(expr)
     }
@@ -1414,8 +1928,9 @@ impl Parser {
     fn multiplicative_expression(&mut self) -> Result<Expression> {
         let mut expr = self.power_expression()?;
         while let Some(op) = self.match_multiplicative_op() {
+            let span = self.previous_span();
             let right = self.power_expression()?;
-            expr = Expression::Binary { left: Box::new(expr), op, right: Box::new(right) };
+            expr = Expression::Binary { left: Box::new(expr), op, right: Box::new(right), span };
         }
         Ok(expr)
     }
@@ -1423,11 +1938,13 @@ impl Parser {
     fn power_expression(&mut self) -> Result<Expression> {
         let mut expr = self.unary_expression()?;
         if self.match_token(&TokenKind::Степінь) {
+            let span = self.previous_span();
             let right = self.power_expression()?; // Правоасоціативний
             expr = Expression::Binary {
                 left: Box::new(expr),
                 op: BinaryOp::Pow,
                 right: Box::new(right),
+                span,
             };
         }
         Ok(expr)
@@ -1436,7 +1953,16 @@ impl Parser {
     fn unary_expression(&mut self) -> Result<Expression> {
         if let Some(op) = self.match_unary_op() {
             let operand = self.unary_expression()?;
-            Ok(Expression::Unary { op, operand: Box::new(operand) })
+            // Префіксний мінус перед числовим літералом — це від'ємний літерал
+            match (op, operand) {
+                (UnaryOp::Neg, Expression::Literal(Literal::Integer(n))) => {
+                    Ok(Expression::Literal(Literal::Integer(-n)))
+                }
+                (UnaryOp::Neg, Expression::Literal(Literal::Float(f))) => {
+                    Ok(Expression::Literal(Literal::Float(-f)))
+                }
+                (op, operand) => Ok(Expression::Unary { op, operand: Box::new(operand) }),
+            }
         } else {
             self.postfix_expression()
         }
@@ -1444,6 +1970,7 @@ impl Parser {
 
     /// Постфіксні оператори: виклик, індексація, доступ до полів, ?
     fn postfix_expression(&mut self) -> Result<Expression> {
+        let span = self.peek_span();
         let mut expr = self.primary()?;
 
         loop {
@@ -1453,17 +1980,25 @@ impl Parser {
                 if !self.check(&TokenKind::ПраваДужка) {
                     loop {
                         args.push(self.expression()?);
-                        if !self.match_token(&TokenKind::Кома) { break; }
+                        if !self.list_separator(&TokenKind::ПраваДужка) { break; }
                     }
                 }
                 self.consume(&TokenKind::ПраваДужка, "Очікувалась ')'")?;
-                expr = Expression::Call { callee: Box::new(expr), args };
+                expr = Expression::Call { callee: Box::new(expr), args, span };
             } else if self.match_token(&TokenKind::ЛіваКвадратна) {
                 // Індексація
                 let index = self.expression()?;
                 self.consume(&TokenKind::ПраваКвадратна, "Очікувалась ']'")?;
                 expr = Expression::Index { object: Box::new(expr), index: Box::new(index) };
             } else if self.match_token(&TokenKind::Крапка) {
+                // Елемент кортежу: т.0 — те саме, що т[0]
+                if let Some(indices) = self.tuple_indices() {
+                    for index in indices {
+                        let index = Box::new(Expression::Literal(Literal::Integer(index)));
+                        expr = Expression::Index { object: Box::new(expr), index };
+                    }
+                    continue;
+                }
                 // Доступ до поля або виклик методу
                 // Дозволяємо ключові слова як імена методів (напр. .взяти(), .тип())
                 let member = if let TokenKind::Ідентифікатор(name) = &self.peek().kind {
@@ -1483,16 +2018,17 @@ impl Parser {
                     if !self.check(&TokenKind::ПраваДужка) {
                         loop {
                             args.push(self.expression()?);
-                            if !self.match_token(&TokenKind::Кома) { break; }
+                            if !self.list_separator(&TokenKind::ПраваДужка) { break; }
                         }
                     }
                     self.consume(&TokenKind::ПраваДужка, "Очікувалась ')'")?;
-                    expr = Expression::MethodCall { object: Box::new(expr), method: member, args };
+                    expr = Expression::MethodCall { object: Box::new(expr), method: member, args, span };
                 } else {
                     expr = Expression::MemberAccess { object: Box::new(expr), member };
                 }
-            } else if self.match_token(&TokenKind::ЗнакПитання) {
+            } else if self.check(&TokenKind::ЗнакПитання) && !self.question_starts_ternary() {
                 // Поширення помилки
+                self.advance();
                 expr = Expression::ErrorPropagation(Box::new(expr));
             } else if self.match_token(&TokenKind::Як) {
                 // Приведення типів
@@ -1506,11 +2042,37 @@ impl Parser {
         Ok(expr)
     }
 
+    /// Тіло блок-виразу після `{`. Останній вираз без `;` стає значенням блоку.
+    fn block_expression(&mut self) -> Result<Expression> {
+        let mut statements = Vec::new();
+        let mut terminated = false;
+        while !self.check(&TokenKind::ПраваФігурна) && !self.is_at_end() {
+            statements.push(self.statement()?);
+            terminated = self.match_token(&TokenKind::КрапкаЗКомою);
+        }
+        self.consume(&TokenKind::ПраваФігурна, "Очікувалась '}'")?;
+
+        let result = match statements.pop() {
+            Some(Statement::Expression(expr)) if !terminated => Some(Box::new(expr)),
+            Some(last) => {
+                statements.push(last);
+                None
+            }
+            None => None,
+        };
+        Ok(Expression::Block(statements, result))
+    }
+
     /// Первинні вирази
     fn primary(&mut self) -> Result<Expression> {
+        // Блок як вираз: змінна x = { змінна t = f(); t + 1 }
+        if self.match_token(&TokenKind::ЛіваФігурна) {
+            return self.block_expression();
+        }
+
         // Чекати (await)
         if self.match_token(&TokenKind::Чекати) {
-            let expr = self.primary()?;
+            let expr = self.unary_expression()?;
             return Ok(Expression::Await(Box::new(expr)));
         }
 
@@ -1574,7 +2136,7 @@ impl Parser {
                 let mut elements = vec![expr];
                 loop {
                     elements.push(self.expression()?);
-                    if !self.match_token(&TokenKind::Кома) { break; }
+                    if !self.list_separator(&TokenKind::ПраваДужка) { break; }
                 }
                 self.consume(&TokenKind::ПраваДужка, "Очікувалась ')'")?;
                 return Ok(Expression::Tuple(elements));
@@ -1589,13 +2151,23 @@ impl Parser {
             if !self.check(&TokenKind::ПраваКвадратна) {
                 loop {
                     elements.push(self.expression()?);
-                    if !self.match_token(&TokenKind::Кома) { break; }
+                    if !self.list_separator(&TokenKind::ПраваКвадратна) { break; }
                 }
             }
             self.consume(&TokenKind::ПраваКвадратна, "Очікувалась ']'")?;
             return Ok(Expression::Array(elements));
         }
 
+        // Ключові слова `тип` і `канал` перед `(` — виклики вбудованих функцій
+        if self.check_type_call() {
+            self.advance();
+            return Ok(Expression::Identifier("тип".to_string()));
+        }
+        if self.check(&TokenKind::Канал) && self.peek_next_kind() == Some(TokenKind::ЛіваДужка) {
+            self.advance();
+            return Ok(Expression::Identifier("канал".to_string()));
+        }
+
         // Ідентифікатор, конструктор структури/enum
         if self.check_identifier() {
             let name = self.consume_identifier("Очікувався ідентифікатор")?;
@@ -1610,7 +2182,7 @@ impl Parser {
                         self.consume(&TokenKind::Двокрапка, "Очікувалась ':'")?;
                         let field_value = self.expression()?;
                         fields.push((field_name, field_value));
-                        if !self.match_token(&TokenKind::Кома) { break; }
+                        if !self.list_separator(&TokenKind::ПраваФігурна) { break; }
                     }
                 }
                 self.consume(&TokenKind::ПраваФігурна, "Очікувалась '}'")?;
@@ -1629,7 +2201,7 @@ impl Parser {
             return Ok(Expression::Identifier(name));
         }
 
-        Err(ParseError::InvalidExpression(self.peek().line).into())
+        Err(ParseError::InvalidExpression(self.peek().line, self.peek().column).into())
     }
 
     /// Лямбда: |x, y| вираз  або  |x, y| { блок }
@@ -1677,20 +2249,9 @@ impl Parser {
             let pattern = self.parse_pattern()?;
             self.consume(&TokenKind::ПодвійнаСтрілка, "Очікувалась '=>'")?;
 
-            let body = if self.check(&TokenKind::ЛіваФігурна) {
-                // Блок як тіло
-                self.match_token(&TokenKind::ЛіваФігурна);
-                let mut stmts = Vec::new();
-                while !self.check(&TokenKind::ПраваФігурна) && !self.is_at_end() {
-                    stmts.push(self.statement()?);
-                }
-                self.consume(&TokenKind::ПраваФігурна, "Очікувалась '}'")?;
-                // Останній вираз як результат
-                if let Some(Statement::Expression(expr)) = stmts.last().cloned() {
-                    expr
-                } else {
-                    Expression::Literal(Literal::Null)
-                }
+            let body = if self.match_token(&TokenKind::ЛіваФігурна) {
+                // Блок як тіло: останній вираз — результат гілки
+                self.block_expression()?
             } else {
                 self.expression()?
             };
@@ -1825,7 +2386,7 @@ impl Parser {
             return Ok(Pattern::Binding(name));
         }
 
-        Err(ParseError::InvalidPattern(self.peek().line).into())
+        Err(ParseError::InvalidPattern(self.peek().line, self.peek().column).into())
     }
 
     fn parse_format_string(&mut self) -> Result<Expression> {
@@ -1846,7 +2407,7 @@ impl Parser {
 
             Ok(Expression::FormatString(format_parts))
         } else {
-            Err(ParseError::InvalidExpression(self.peek().line).into())
+            Err(ParseError::InvalidExpression(self.peek().line, self.peek().column).into())
         }
     }
 
@@ -1858,6 +2419,65 @@ impl Parser {
             return Ok(Type::SelfType);
         }
 
+        let base = self.parse_base_type()?;
+        self.parse_array_suffix(base)
+    }
+
+    /// Масив фіксованого розміру після типу: цл32[4], цл32[РОЗМІР], цл32[]
+    fn parse_array_suffix(&mut self, mut ty: Type) -> Result<Type> {
+        // Розмір має бути на тому ж рядку, щоб не сплутати з літералом масиву
+        while self.check(&TokenKind::ЛіваКвадратна) && self.peek().line == self.previous().line {
+            self.advance();
+            if self.match_token(&TokenKind::ПраваКвадратна) {
+                ty = Type::Slice(Box::new(ty));
+                continue;
+            }
+            let size = self.array_size()?;
+            self.consume(&TokenKind::ПраваКвадратна, "Очікувалась ']' після розміру масиву")?;
+            ty = Type::Array(Box::new(ty), size);
+        }
+        Ok(ty)
+    }
+
+    /// Розмір масиву — константний вираз з літералів та раніше оголошених сталих
+    fn array_size(&mut self) -> Result<usize> {
+        let (line, column) = (self.peek().line, self.peek().column);
+        let expr = self.expression()?;
+        match self.const_eval(&expr) {
+            Some(n) if n >= 0 => Ok(n as usize),
+            _ => Err(ParseError::UnexpectedToken {
+                expected: "невід'ємний константний розмір масиву".to_string(),
+                found: format!("{:?}", expr),
+                line,
+                column,
+            }.into()),
+        }
+    }
+
+    /// Обчислює цілочисельний константний вираз на етапі парсингу
+    fn const_eval(&self, expr: &Expression) -> Option<i64> {
+        match expr {
+            Expression::Literal(Literal::Integer(n)) => Some(*n),
+            Expression::Identifier(name) => self.constants.get(name).copied(),
+            Expression::Unary { op: UnaryOp::Neg, operand } => self.const_eval(operand)?.checked_neg(),
+            Expression::Binary { left, op, right, .. } => {
+                let (a, b) = (self.const_eval(left)?, self.const_eval(right)?);
+                match op {
+                    BinaryOp::Add => a.checked_add(b),
+                    BinaryOp::Sub => a.checked_sub(b),
+                    BinaryOp::Mul => a.checked_mul(b),
+                    BinaryOp::Div => a.checked_div(b),
+                    BinaryOp::Mod => a.checked_rem(b),
+                    BinaryOp::Shl => a.checked_shl(b as u32),
+                    BinaryOp::Shr => a.checked_shr(b as u32),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn parse_base_type(&mut self) -> Result<Type> {
         // Примітивні типи
         if self.match_token(&TokenKind::Цл8) { return Ok(Type::Цл8); }
         if self.match_token(&TokenKind::Цл16) { return Ok(Type::Цл16); }
@@ -1873,9 +2493,14 @@ impl Parser {
         if self.match_token(&TokenKind::Сим) { return Ok(Type::Сим); }
         if self.match_token(&TokenKind::Тхт) { return Ok(Type::Тхт); }
 
-        // Масив: [Тип]
+        // Масив: [Тип] або [Тип; РОЗМІР]
         if self.match_token(&TokenKind::ЛіваКвадратна) {
             let elem_type = self.parse_type()?;
+            if self.match_token(&TokenKind::КрапкаЗКомою) {
+                let size = self.array_size()?;
+                self.consume(&TokenKind::ПраваКвадратна, "Очікувалась ']'")?;
+                return Ok(Type::Array(Box::new(elem_type), size));
+            }
             self.consume(&TokenKind::ПраваКвадратна, "Очікувалась ']'")?;
             return Ok(Type::Slice(Box::new(elem_type)));
         }
@@ -1930,14 +2555,35 @@ impl Parser {
                     type_params.push(self.parse_type()?);
                     if !self.match_token(&TokenKind::Кома) { break; }
                 }
-                self.consume(&TokenKind::Більше, "Очікувалась '>'")?;
+                self.consume_type_args_close()?;
                 return Ok(Type::Generic(name, type_params));
             }
 
             return Ok(Type::Named(name));
         }
 
-        Err(ParseError::InvalidExpression(self.peek().line).into())
+        Err(ParseError::InvalidExpression(self.peek().line, self.peek().column).into())
+    }
+
+    /// Закриваюча `>` аргументів типу. Лексер склеює `>` з наступними символами:
+    /// `>>` у `масив<словник<тхт, цл32>>` стає зсувом, а `>=` чи `>>=` з'являються
+    /// перед `=` без пробілу. Такий токен розщеплюється: перша `>` закриває список,
+    /// решта лишається в потоці окремим токеном
+    fn consume_type_args_close(&mut self) -> Result<()> {
+        let (rest, lexeme) = match self.peek().kind {
+            TokenKind::ЗсувПраво => (TokenKind::Більше, ">"),
+            TokenKind::ЗсувПравоПрисвоїти => (TokenKind::БільшеАбоДорівнює, ">="),
+            TokenKind::БільшеАбоДорівнює => (TokenKind::Присвоїти, "="),
+            _ => {
+                self.consume(&TokenKind::Більше, "Очікувалась '>'")?;
+                return Ok(());
+            }
+        };
+        let token = &mut self.tokens[self.current];
+        token.kind = rest;
+        token.lexeme = lexeme.to_string();
+        token.column += 1;
+        Ok(())
     }
 
     fn parse_generic_params(&mut self) -> Result<Vec<String>> {
@@ -1947,7 +2593,7 @@ impl Parser {
                 params.push(self.consume_identifier("Очікувалось ім'я generic параметра")?);
                 if !self.match_token(&TokenKind::Кома) { break; }
             }
-            self.consume(&TokenKind::Більше, "Очікувалась '>'")?;
+            self.consume_type_args_close()?;
         }
         Ok(params)
     }
@@ -2026,7 +2672,12 @@ impl Parser {
             TokenKind::Публічний | TokenKind::Приватний | TokenKind::Асинхронний |
             TokenKind::Ефект | TokenKind::Макрос | TokenKind::Тест |
             TokenKind::Фаз | TokenKind::Бенчмарк | TokenKind::Чистий
-        )
+        ) && !self.check_type_call()
+    }
+
+    /// `тип(значення)` — виклик вбудованої функції, а не оголошення типу
+    fn check_type_call(&self) -> bool {
+        self.check(&TokenKind::Тип) && self.peek_next_kind() == Some(TokenKind::ЛіваДужка)
     }
 
     fn check_identifier(&self) -> bool {
@@ -2081,6 +2732,38 @@ impl Parser {
         }
     }
 
+    /// Номери елементів кортежу після `.`. Вкладений доступ `т.0.1` лексер
+    /// читає як дробове `0.1`, тож такий токен розбирається за текстом
+    fn tuple_indices(&mut self) -> Option<Vec<i64>> {
+        let indices = match &self.peek().kind {
+            TokenKind::ЦілеЧисло(n) => vec![*n],
+            TokenKind::ДробовеЧисло(_) => self.peek().lexeme
+                .split('.')
+                .map(|part| part.parse().ok())
+                .collect::<Option<Vec<i64>>>()?,
+            _ => return None,
+        };
+        self.advance();
+        Some(indices)
+    }
+
+    /// Кома між елементами списку. Кома перед закриваючою дужкою — трейлінг,
+    /// після неї список завершено
+    fn list_separator(&mut self, close: &TokenKind) -> bool {
+        self.match_token(&TokenKind::Кома) && !self.check(close)
+    }
+
+    /// Позиція щойно прочитаного токена (ключового слова декларації)
+    fn previous_span(&self) -> Span {
+        let token = self.previous();
+        Span { line: token.line, column: token.column }
+    }
+
+    fn peek_span(&self) -> Span {
+        let token = self.peek();
+        Span { line: token.line, column: token.column }
+    }
+
     fn consume(&mut self, kind: &TokenKind, message: &str) -> Result<&Token> {
         if self.check(kind) {
             Ok(self.advance())
@@ -2089,6 +2772,7 @@ impl Parser {
                 expected: message.to_string(),
                 found: format!("{:?}", self.peek().kind),
                 line: self.peek().line,
+                column: self.peek().column,
             }.into())
         }
     }
@@ -2103,6 +2787,7 @@ impl Parser {
                 expected: message.to_string(),
                 found: format!("{:?}", self.peek().kind),
                 line: self.peek().line,
+                column: self.peek().column,
             }.into())
         }
     }
@@ -2113,6 +2798,55 @@ pub fn parse(tokens: Vec<Token>) -> Result<Program> {
     parser.parse()
 }
 
+/// Окремий прохід над токенами: перевіряє баланс `()`, `[]`, `{}` і вказує,
+/// де відкрито непарну дужку
+pub fn check_brackets(tokens: &[Token]) -> std::result::Result<(), ParseError> {
+    let mut stack: Vec<(char, usize, usize)> = Vec::new();
+    for token in tokens {
+        let (bracket, closes) = match token.kind {
+            TokenKind::ЛіваДужка => ('(', None),
+            TokenKind::ЛіваКвадратна => ('[', None),
+            TokenKind::ЛіваФігурна => ('{', None),
+            TokenKind::ПраваДужка => (')', Some('(')),
+            TokenKind::ПраваКвадратна => (']', Some('[')),
+            TokenKind::ПраваФігурна => ('}', Some('{')),
+            _ => continue,
+        };
+        let Some(expected_open) = closes else {
+            stack.push((bracket, token.line, token.column));
+            continue;
+        };
+        // Закриваюча дужка без жодної відповідної відкритої — зайва
+        if !stack.iter().any(|(open, _, _)| *open == expected_open) {
+            return Err(ParseError::UnmatchedBracket { bracket, line: token.line, column: token.column });
+        }
+        match stack.pop() {
+            Some((open, _, _)) if open == expected_open => {}
+            Some((open, open_line, open_column)) => {
+                return Err(ParseError::MismatchedBracket {
+                    found: bracket,
+                    line: token.line,
+                    column: token.column,
+                    open,
+                    open_line,
+                    open_column,
+                });
+            }
+            None => unreachable!(),
+        }
+    }
+    match stack.pop() {
+        Some((bracket, line, column)) => Err(ParseError::UnclosedBracket { bracket, line, column }),
+        None => Ok(()),
+    }
+}
+
+/// Розбір з відновленням — повертає AST (якщо без помилок) та всі знайдені помилки
+pub fn parse_recover(tokens: Vec<Token>) -> (Option<Program>, Vec<ParseError>) {
+    let mut parser = Parser::new(tokens);
+    parser.parse_recover()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2202,4 +2936,528 @@ mod tests {
         let program = parse(tokens).unwrap();
         assert_eq!(program.declarations.len(), 1);
     }
+
+    #[test]
+    fn test_parse_recover_multiple_errors() {
+        let source = r#"
+функція перша() {
+    змінна а = (1 + 
+}
+
+функція друга() {
+    друк("ок")
+}
+
+функція третя() {
+    змінна б = * 2
+}
+"#;
+        let tokens = tokenize(source).unwrap();
+        let (program, errors) = parse_recover(tokens);
+        assert!(program.is_none());
+        assert_eq!(errors.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_error_column() {
+        let tokens = tokenize("змінна х = (1 + 2").unwrap();
+        let err = parse(tokens).unwrap_err();
+        match err.downcast_ref::<ParseError>() {
+            Some(ParseError::UnexpectedToken { line, column, .. }) => {
+                assert_eq!(*line, 1);
+                assert_eq!(*column, 18);
+            }
+            other => panic!("Очікувалась UnexpectedToken, отримано {:?}", other),
+        }
+
+        let tokens = tokenize("функція ф() {\n    змінна а = * 2\n}").unwrap();
+        let err = parse(tokens).unwrap_err();
+        match err.downcast_ref::<ParseError>() {
+            Some(ParseError::InvalidExpression(line, column)) => {
+                assert_eq!(*line, 2);
+                assert_eq!(*column, 16);
+            }
+            other => panic!("Очікувалась InvalidExpression, отримано {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_export() {
+        let source = r#"
+експорт функція додати(а: цл64, б: цл64) -> цл64 { повернути а + б }
+експорт структура Точка { х: цл64, у: цл64 }
+функція прихована() {}
+"#;
+        let tokens = tokenize(source).unwrap();
+        let program = parse(tokens).unwrap();
+        assert_eq!(program.declarations.len(), 3);
+        assert_eq!(program.exports, vec!["додати".to_string(), "Точка".to_string()]);
+        assert!(matches!(program.declarations[0], Declaration::Function { visibility: Visibility::Public, .. }));
+        assert!(matches!(program.declarations[1], Declaration::Struct { visibility: Visibility::Public, .. }));
+        assert!(matches!(program.declarations[2], Declaration::Function { visibility: Visibility::Private, .. }));
+    }
+
+    #[test]
+    fn test_parse_conditional_attribute() {
+        let source = r#"
+@якщо(windows)
+функція шлях() -> рядок { повернути "C:\\" }
+@якщо(не windows)
+функція шлях() -> рядок { повернути "/" }
+"#;
+        let tokens = tokenize(source).unwrap();
+        let program = parse(tokens).unwrap();
+        assert_eq!(program.declarations.len(), 2);
+        match &program.declarations[1] {
+            Declaration::Attributed { attributes, declaration } => {
+                assert_eq!(attributes[0].name, "якщо");
+                assert_eq!(attributes[0].args, vec!["не windows".to_string()]);
+                assert_eq!(declaration.name(), Some("шлях"));
+            }
+            other => panic!("Очікувалось атрибутоване оголошення, отримано {:?}", other),
+        }
+
+        let configured = program.configured();
+        assert_eq!(configured.declarations.len(), 1);
+        assert!(matches!(configured.declarations[0], Declaration::Function { .. }));
+    }
+
+    #[test]
+    fn test_parse_static_method() {
+        let source = r#"
+реалізація Точка {
+    статичний стала НУЛЬ = 0
+    статичний функція нова(х: цл64, у: цл64) -> Точка { повернути Точка { х: х, у: у } }
+    функція довжина(себе) -> цл64 { повернути себе.х }
+}
+"#;
+        let tokens = tokenize(source).unwrap();
+        let program = parse(tokens).unwrap();
+        match &program.declarations[0] {
+            Declaration::Impl { methods, .. } => {
+                assert!(matches!(methods[0], Declaration::Variable { .. }));
+                assert!(matches!(methods[1], Declaration::Function { is_static: true, .. }));
+                assert!(matches!(methods[2], Declaration::Function { is_static: false, .. }));
+            }
+            other => panic!("Очікувалась реалізація, отримано {:?}", other),
+        }
+
+        let tokens = tokenize("реалізація Т { статичний функція ф(себе) {} }").unwrap();
+        assert!(parse(tokens).is_err());
+    }
+
+    #[test]
+    fn test_parse_array_type_size() {
+        let source = r#"
+стала РОЗМІР = 4
+стала ПОДВІЙНИЙ = РОЗМІР * 2
+змінна а: цл32[РОЗМІР]
+змінна б: цл32[3]
+змінна г: [дрб64; ПОДВІЙНИЙ]
+"#;
+        let tokens = tokenize(source).unwrap();
+        let program = parse(tokens).unwrap();
+        let types: Vec<Option<Type>> = program.declarations.iter().skip(2).map(|d| match d {
+            Declaration::Variable { ty, .. } => ty.clone(),
+            _ => None,
+        }).collect();
+        assert_eq!(types[0], Some(Type::Array(Box::new(Type::Цл32), 4)));
+        assert_eq!(types[1], Some(Type::Array(Box::new(Type::Цл32), 3)));
+        assert_eq!(types[2], Some(Type::Array(Box::new(Type::Дрб64), 8)));
+
+        let tokens = tokenize("змінна н = 3\nзмінна а: цл32[н]").unwrap();
+        assert!(parse(tokens).is_err());
+    }
+
+    #[test]
+    fn test_parse_multiple_return_values() {
+        let tokens = tokenize("функція ф(а, б) { повернути а, б + 1 }\nфункція г(а) { повернути а }").unwrap();
+        let program = parse(tokens).unwrap();
+        let returned: Vec<&Expression> = program.declarations.iter().map(|decl| match decl {
+            Declaration::Function { body, .. } => match &body[0] {
+                Statement::Return(Some(value)) => value,
+                other => panic!("Очікувалось повернення, отримано {:?}", other),
+            },
+            other => panic!("Очікувалась функція, отримано {:?}", other),
+        }).collect();
+        match returned[0] {
+            Expression::Array(values) => {
+                assert_eq!(values.len(), 2);
+                assert_eq!(values[0], Expression::Identifier("а".to_string()));
+                assert!(matches!(values[1], Expression::Binary { op: BinaryOp::Add, .. }));
+            }
+            other => panic!("Очікувався масив значень, отримано {:?}", other),
+        }
+        assert_eq!(returned[1], &Expression::Identifier("а".to_string()));
+    }
+
+    #[test]
+    fn test_parse_ternary() {
+        let tokens = tokenize("функція ф(а, б) { повернути а > б ? а : б }").unwrap();
+        let program = parse(tokens).unwrap();
+        let value = match &program.declarations[0] {
+            Declaration::Function { body, .. } => match &body[0] {
+                Statement::Return(Some(value)) => value.clone(),
+                other => panic!("Очікувалось повернення, отримано {:?}", other),
+            },
+            other => panic!("Очікувалась функція, отримано {:?}", other),
+        };
+        match value {
+            Expression::If { condition, then_expr, else_expr } => {
+                assert!(matches!(*condition, Expression::Binary { op: BinaryOp::Gt, .. }));
+                assert_eq!(*then_expr, Expression::Identifier("а".to_string()));
+                assert_eq!(*else_expr, Expression::Identifier("б".to_string()));
+            }
+            other => panic!("Очікувався тернарний вираз, отримано {:?}", other),
+        }
+
+        // Вкладений тернарник правоасоціативний: а ? 1 : (б ? 2 : 3)
+        let tokens = tokenize("функція ф(а, б) { повернути а || б ? 1 : б ? 2 : 3 }").unwrap();
+        let program = parse(tokens).unwrap();
+        let value = match &program.declarations[0] {
+            Declaration::Function { body, .. } => match &body[0] {
+                Statement::Return(Some(value)) => value.clone(),
+                other => panic!("Очікувалось повернення, отримано {:?}", other),
+            },
+            other => panic!("Очікувалась функція, отримано {:?}", other),
+        };
+        match value {
+            Expression::If { condition, then_expr, else_expr } => {
+                assert!(matches!(*condition, Expression::Binary { op: BinaryOp::Or, .. }));
+                assert_eq!(*then_expr, Expression::Literal(Literal::Integer(1)));
+                assert!(matches!(*else_expr, Expression::If { .. }));
+            }
+            other => panic!("Очікувався тернарний вираз, отримано {:?}", other),
+        }
+
+        // Постфіксний `?` і далі поширює помилку, зокрема перед бінарним оператором
+        for source in ["функція ф(р) { змінна х = р?\n повернути х }", "функція ф(р) { змінна х = р(5)? - 1 }"] {
+            let program = parse(tokenize(source).unwrap()).unwrap();
+            match &program.declarations[0] {
+                Declaration::Function { body, .. } => match &body[0] {
+                    Statement::Declaration(Declaration::Variable { value: Some(value), .. }) => assert!(
+                        matches!(value, Expression::ErrorPropagation(_))
+                            || matches!(value, Expression::Binary { left, op: BinaryOp::Sub, .. } if matches!(**left, Expression::ErrorPropagation(_))),
+                        "{:?}", value
+                    ),
+                    other => panic!("Очікувалась змінна, отримано {:?}", other),
+                },
+                other => panic!("Очікувалась функція, отримано {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_negative_literals() {
+        let source = r#"
+функція головна() {
+    змінна а = -5
+    змінна б = а - 5
+    змінна д = -2.5 * а
+    змінна г = -а
+}
+"#;
+        let tokens = tokenize(source).unwrap();
+        let program = parse(tokens).unwrap();
+        let body = match &program.declarations[0] {
+            Declaration::Function { body, .. } => body,
+            other => panic!("Очікувалась функція, отримано {:?}", other),
+        };
+        let values: Vec<&Expression> = body.iter().map(|stmt| match stmt {
+            Statement::Declaration(Declaration::Variable { value: Some(value), .. }) => value,
+            other => panic!("Очікувалось оголошення змінної, отримано {:?}", other),
+        }).collect();
+
+        assert_eq!(values[0], &Expression::Literal(Literal::Integer(-5)));
+        match values[1] {
+            Expression::Binary { left, op, right, .. } => {
+                assert_eq!(left.as_ref(), &Expression::Identifier("а".to_string()));
+                assert_eq!(op, &BinaryOp::Sub);
+                assert_eq!(right.as_ref(), &Expression::Literal(Literal::Integer(5)));
+            }
+            other => panic!("Очікувалось віднімання, отримано {:?}", other),
+        }
+        match values[2] {
+            Expression::Binary { left, op: BinaryOp::Mul, .. } => {
+                assert_eq!(left.as_ref(), &Expression::Literal(Literal::Float(-2.5)));
+            }
+            other => panic!("Очікувалось множення, отримано {:?}", other),
+        }
+        assert!(matches!(values[3], Expression::Unary { op: UnaryOp::Neg, .. }));
+    }
+
+    #[test]
+    fn test_parse_for_negative_and_fractional_step() {
+        let source = r#"
+функція головна() {
+    для (і від 10 до 0 через -1) {}
+    для (х від 0 до 1 через 0.25) {}
+}
+"#;
+        let tokens = tokenize(source).unwrap();
+        let program = parse(tokens).unwrap();
+        let body = match &program.declarations[0] {
+            Declaration::Function { body, .. } => body,
+            other => panic!("Очікувалась функція, отримано {:?}", other),
+        };
+        let steps: Vec<&Expression> = body.iter().map(|stmt| match stmt {
+            Statement::For { step: Some(step), .. } => step,
+            other => panic!("Очікувався цикл з кроком, отримано {:?}", other),
+        }).collect();
+        assert_eq!(steps[0], &Expression::Literal(Literal::Integer(-1)));
+        assert_eq!(steps[1], &Expression::Literal(Literal::Float(0.25)));
+    }
+
+    #[test]
+    fn test_parse_generic_declarations() {
+        let source = r#"
+функція перший<Т>(масив: Т[]) -> Т { повернути масив[0] }
+структура Пара<К, З> {
+    ключ: К,
+    значення: З
+}
+"#;
+        let tokens = tokenize(source).unwrap();
+        let program = parse(tokens).unwrap();
+        match &program.declarations[0] {
+            Declaration::Function { generic_params, params, return_type, .. } => {
+                assert_eq!(generic_params, &vec!["Т".to_string()]);
+                assert_eq!(params[0].ty, Type::Slice(Box::new(Type::Named("Т".to_string()))));
+                assert_eq!(return_type, &Some(Type::Named("Т".to_string())));
+            }
+            other => panic!("Очікувалась функція, отримано {:?}", other),
+        }
+        match &program.declarations[1] {
+            Declaration::Struct { generic_params, fields, .. } => {
+                assert_eq!(generic_params, &vec!["К".to_string(), "З".to_string()]);
+                assert_eq!(fields[1].ty, Type::Named("З".to_string()));
+            }
+            other => panic!("Очікувалась структура, отримано {:?}", other),
+        }
+
+        let tokens = tokenize("функція ф<>() {}").unwrap();
+        assert!(parse(tokens).is_err());
+    }
+
+    #[test]
+    fn test_parse_nested_generic_types() {
+        let source = r#"
+функція ф(п1: масив<цл32>, п2: масив<словник<тхт, цл32>>, п3: Обгортка<масив<масив<дрб64>>>) {}
+"#;
+        let program = parse(tokenize(source).unwrap()).unwrap();
+        let Declaration::Function { params, .. } = &program.declarations[0] else {
+            panic!("очікувалась функція");
+        };
+        let generic = |name: &str, args: Vec<Type>| Type::Generic(name.to_string(), args);
+        assert_eq!(params[0].ty, generic("масив", vec![Type::Цл32]));
+        assert_eq!(params[1].ty, generic("масив", vec![generic("словник", vec![Type::Тхт, Type::Цл32])]));
+        assert_eq!(
+            params[2].ty,
+            generic("Обгортка", vec![generic("масив", vec![generic("масив", vec![Type::Дрб64])])])
+        );
+
+        // Зсув у виразах не розщеплюється
+        let program = parse(tokenize("стала х: масив<цл32> = [8 >> 1]").unwrap()).unwrap();
+        let Declaration::Variable { value: Some(Expression::Array(items)), .. } = &program.declarations[0] else {
+            panic!("очікувався масив");
+        };
+        assert!(matches!(items[0], Expression::Binary { op: BinaryOp::Shr, .. }));
+    }
+
+    #[test]
+    fn test_parse_type_args_split_closing() {
+        let generic = |name: &str, args: Vec<Type>| Type::Generic(name.to_string(), args);
+        let program = parse(tokenize("функція ф(д: словник<тхт, масив<цл32>>) {}").unwrap()).unwrap();
+        let Declaration::Function { params, .. } = &program.declarations[0] else {
+            panic!("очікувалась функція");
+        };
+        assert_eq!(params[0].ty, generic("словник", vec![Type::Тхт, generic("масив", vec![Type::Цл32])]));
+
+        // `>>=` і `>=` після типу: `>` закриває аргументи, решта — присвоєння
+        let source = "змінна с: словник<тхт, масив<цл32>>= створити()\nстала м: масив<цл32>= [1]\nтип Список<Т>= масив<Т>";
+        let program = parse(tokenize(source).unwrap()).unwrap();
+        let Declaration::Variable { ty: Some(ty), value: Some(Expression::Call { .. }), .. } = &program.declarations[0] else {
+            panic!("очікувалась змінна з викликом");
+        };
+        assert_eq!(ty, &generic("словник", vec![Type::Тхт, generic("масив", vec![Type::Цл32])]));
+        assert!(matches!(&program.declarations[1], Declaration::Variable { value: Some(Expression::Array(_)), .. }));
+        assert!(matches!(&program.declarations[2], Declaration::TypeAlias { generic_params, .. } if generic_params.len() == 1));
+    }
+
+    #[test]
+    fn test_parse_spans() {
+        let source = "функція головна() {\n    змінна x = 1 + 2\n    друк(x)\n}";
+        let tokens = tokenize(source).unwrap();
+        let program = parse(tokens).unwrap();
+        let Declaration::Function { span, body, .. } = &program.declarations[0] else {
+            panic!("очікувалась функція");
+        };
+        assert_eq!(*span, Span { line: 1, column: 1 });
+
+        let Statement::Declaration(Declaration::Variable { value: Some(value), span, .. }) = &body[0] else {
+            panic!("очікувалась змінна");
+        };
+        assert_eq!(*span, Span { line: 2, column: 5 });
+        let Expression::Binary { span, .. } = value else {
+            panic!("очікувався бінарний вираз");
+        };
+        assert_eq!(*span, Span { line: 2, column: 18 });
+
+        let Statement::Expression(Expression::Call { span, .. }) = &body[1] else {
+            panic!("очікувався виклик");
+        };
+        assert_eq!(*span, Span { line: 3, column: 5 });
+    }
+
+    #[test]
+    fn test_parse_bitwise_operators() {
+        let tokens = tokenize("змінна а = 1 | 2 & 3 << 1").unwrap();
+        let program = parse(tokens).unwrap();
+        let Declaration::Variable { value: Some(Expression::Binary { op, right, .. }), .. } = &program.declarations[0] else {
+            panic!("очікувався бінарний вираз");
+        };
+        assert_eq!(*op, BinaryOp::BitOr);
+        let Expression::Binary { op, right, .. } = right.as_ref() else {
+            panic!("очікувалось побітове І");
+        };
+        assert_eq!(*op, BinaryOp::BitAnd);
+        assert!(matches!(right.as_ref(), Expression::Binary { op: BinaryOp::Shl, .. }));
+
+        // Подвійні символи лишаються логічними операторами
+        let tokens = tokenize("змінна б = істина && хиба || істина").unwrap();
+        let program = parse(tokens).unwrap();
+        assert!(matches!(
+            &program.declarations[0],
+            Declaration::Variable { value: Some(Expression::Binary { op: BinaryOp::Or, .. }), .. }
+        ));
+    }
+
+    #[test]
+    fn test_parse_block_expression() {
+        let tokens = tokenize("змінна а = { змінна т = 2; т + 1 }").unwrap();
+        let program = parse(tokens).unwrap();
+        let Declaration::Variable { value: Some(Expression::Block(statements, result)), .. } = &program.declarations[0] else {
+            panic!("очікувався блок-вираз");
+        };
+        assert_eq!(statements.len(), 1);
+        assert!(matches!(result.as_deref(), Some(Expression::Binary { op: BinaryOp::Add, .. })));
+
+        // `;` після останнього виразу — блок без значення
+        let tokens = tokenize("змінна б = { друк(1); }").unwrap();
+        let program = parse(tokens).unwrap();
+        assert!(matches!(
+            &program.declarations[0],
+            Declaration::Variable { value: Some(Expression::Block(statements, None)), .. } if statements.len() == 1
+        ));
+    }
+
+    #[test]
+    fn test_parse_break_with_value() {
+        let tokens = tokenize("змінна а = { якщо х > 0 { переривати 1 }\n 2 }").unwrap();
+        let program = parse(tokens).unwrap();
+        let Declaration::Variable { value: Some(Expression::Block(statements, _)), .. } = &program.declarations[0] else {
+            panic!("очікувався блок-вираз");
+        };
+        let Statement::If { then_branch, .. } = &statements[0] else {
+            panic!("очікувалось якщо");
+        };
+        assert!(matches!(
+            then_branch.as_ref(),
+            Statement::Block(body) if matches!(body[0], Statement::BreakValue(Expression::Literal(Literal::Integer(1))))
+        ));
+
+        // Вираз на наступному рядку не належить до `переривати`
+        let tokens = tokenize("функція ф() { поки істина { переривати\n друк(1) } }").unwrap();
+        let program = parse(tokens).unwrap();
+        let Declaration::Function { body, .. } = &program.declarations[0] else { unreachable!() };
+        let Statement::While { body: loop_body, .. } = &body[0] else { panic!("очікувався цикл") };
+        assert!(matches!(loop_body.as_ref(), Statement::Block(items) if matches!(items[0], Statement::Break) && items.len() == 2));
+    }
+
+    #[test]
+    fn test_parse_trailing_commas() {
+        let with_commas = r#"
+функція f(a: цл64, b: цл64,) -> цл64 { повернути a + b }
+функція головна() {
+    f(a, b,)
+    стала м = [1, 2,]
+    стала к = Крапка { x: 1, y: 2, }
+    к.зсунути(1, 2,)
+}
+"#;
+        // Кома замінюється пробілом, щоб позиції вузлів збіглися
+        let without = with_commas.replace(",)", " )").replace(",]", " ]").replace(", }", "  }");
+        assert_ne!(with_commas, without);
+        let expected = parse(tokenize(&without).unwrap()).unwrap();
+        assert_eq!(parse(tokenize(with_commas).unwrap()).unwrap(), expected);
+
+        // Кома без елемента між ними лишається помилкою
+        assert!(parse(tokenize("стала м = [1, , 2]").unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_parse_tuple_and_element_access() {
+        let tokens = tokenize("стала т = (1, \"текст\", істина)\nстала г = (1 + 2)\nстала е = т.1\nстала вк = т.0.1").unwrap();
+        let program = parse(tokens).unwrap();
+        let value = |i: usize| match &program.declarations[i] {
+            Declaration::Variable { value: Some(value), .. } => value.clone(),
+            other => panic!("очікувалась змінна, отримано {:?}", other),
+        };
+        assert!(matches!(value(0), Expression::Tuple(items) if items.len() == 3));
+        assert!(matches!(value(1), Expression::Binary { op: BinaryOp::Add, .. }));
+
+        let index = |object: Expression, i: i64| Expression::Index {
+            object: Box::new(object),
+            index: Box::new(Expression::Literal(Literal::Integer(i))),
+        };
+        let т = || Expression::Identifier("т".to_string());
+        assert_eq!(value(2), index(т(), 1));
+        // `0.1` лексер читає як дробове, але після `.` це два індекси
+        assert_eq!(value(3), index(index(т(), 0), 1));
+    }
+
+    #[test]
+    fn test_parse_doc_comments() {
+        let source = r#"
+/// Додає два числа.
+/// Повертає суму.
+функція додати(а: цл32, б: цл32) -> цл32 {
+    /// всередині тіла ігнорується
+    повернути а + б
+}
+
+// звичайний коментар
+функція без_документації() {}
+
+/// Точка на площині
+структура Точка { х: цл32 }
+"#;
+        let tokens = tokenize(source).unwrap();
+        let program = parse(tokens).unwrap();
+        assert_eq!(program.declarations[0].doc(), Some("Додає два числа.\nПовертає суму."));
+        assert_eq!(program.declarations[1].doc(), None);
+        assert_eq!(program.declarations[2].doc(), Some("Точка на площині"));
+    }
+
+    #[test]
+    fn test_unbalanced_brackets() {
+        let source = "функція головна() {\n    якщо істина {\n        друк(1)\n}\n";
+        let err = parse(tokenize(source).unwrap()).unwrap_err().to_string();
+        assert!(err.contains("Незакрита дужка '{'"), "{}", err);
+        assert!(err.contains("рядку 1, стовпці 19"), "{}", err);
+
+        let source = "функція головна() {\n    друк(1))\n}";
+        let err = parse(tokenize(source).unwrap()).unwrap_err().to_string();
+        assert!(err.contains("Зайва дужка ')' на рядку 2"), "{}", err);
+
+        let source = "функція головна() {\n    друк([1, 2)\n}";
+        let err = parse(tokenize(source).unwrap()).unwrap_err().to_string();
+        assert!(err.contains("не закриває '['"), "{}", err);
+
+        // Помилка парсера лишається доступною під підказкою
+        let err = parse(tokenize("змінна х = (1 + 2").unwrap()).unwrap_err();
+        assert!(err.to_string().contains("Незакрита дужка '('"), "{}", err);
+        assert!(matches!(err.downcast_ref::<ParseError>(), Some(ParseError::UnexpectedToken { .. })));
+        assert!(check_brackets(&tokenize("друк((1), [2]) { }").unwrap()).is_ok());
+    }
 }
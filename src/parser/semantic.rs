@@ -0,0 +1,1077 @@
+// Семантичний аналіз для Тризуб
+// Виведення типів неанотованих змінних (змінна x = 10) для компілятора
+// та базова перевірка типів присвоєнь і аргументів до виконання
+
+use std::collections::{HashMap, HashSet};
+use thiserror::Error;
+use crate::*;
+
+/// Виведені типи змінних за іменем і позицією оголошення. Позиція розрізняє
+/// затінені змінні з тим самим іменем у вкладених областях однієї функції
+#[derive(Debug, Clone, Default)]
+pub struct InferredTypes {
+    pub variables: HashMap<(String, Span), Type>,
+}
+
+impl InferredTypes {
+    pub fn get(&self, variable: &str, declared_at: Span) -> Option<&Type> {
+        self.variables.get(&(variable.to_string(), declared_at))
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum TypeError {
+    #[error("Невідповідність типів у '{function}' на рядку {line}: змінній '{variable}' типу {expected} не можна присвоїти {found}")]
+    Assignment {
+        function: String,
+        variable: String,
+        expected: String,
+        found: String,
+        line: usize,
+    },
+
+    #[error("Невідповідність типів у '{function}' на рядку {line}: аргумент '{param}' функції '{callee}' очікує {expected}, отримано {found}")]
+    Argument {
+        function: String,
+        callee: String,
+        param: String,
+        expected: String,
+        found: String,
+        line: usize,
+    },
+
+    #[error("Структура '{type_name}' на рядку {line} оголошує невідомий інтерфейс '{interface}'")]
+    UnknownInterface {
+        type_name: String,
+        interface: String,
+        line: usize,
+    },
+
+    #[error("Структура '{type_name}' на рядку {line} не реалізує метод '{method}' інтерфейсу '{interface}'")]
+    MissingMethod {
+        type_name: String,
+        interface: String,
+        method: String,
+        line: usize,
+    },
+
+    #[error("Метод '{method}' структури '{type_name}' на рядку {line} не відповідає інтерфейсу '{interface}': очікується {expected}, знайдено {found}")]
+    MethodSignature {
+        type_name: String,
+        interface: String,
+        method: String,
+        expected: String,
+        found: String,
+        line: usize,
+    },
+
+    #[error("Змінна '{variable}' у '{function}' читається до ініціалізації на рядку {line}")]
+    Uninitialized {
+        function: String,
+        variable: String,
+        line: usize,
+    },
+}
+
+impl TypeError {
+    /// Рядок у вихідному коді, до якого належить помилка
+    pub fn line(&self) -> usize {
+        match self {
+            TypeError::Assignment { line, .. }
+            | TypeError::Argument { line, .. }
+            | TypeError::UnknownInterface { line, .. }
+            | TypeError::MissingMethod { line, .. }
+            | TypeError::MethodSignature { line, .. }
+            | TypeError::Uninitialized { line, .. } => *line,
+        }
+    }
+}
+
+/// Тип змінної в області видимості. Перевіряються лише анотовані змінні:
+/// неанотована динамічна й може змінити тип присвоєнням
+#[derive(Clone)]
+struct Binding {
+    ty: Type,
+    annotated: bool,
+}
+
+pub struct TypeInference {
+    /// Сигнатури функцій: ім'я → (параметри, тип результату)
+    functions: HashMap<String, (Vec<Parameter>, Option<Type>)>,
+    scopes: Vec<HashMap<String, Binding>>,
+    current_function: String,
+    result: InferredTypes,
+    errors: Vec<TypeError>,
+}
+
+impl Default for TypeInference {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TypeInference {
+    pub fn new() -> Self {
+        Self {
+            functions: HashMap::new(),
+            scopes: vec![HashMap::new()],
+            current_function: String::new(),
+            result: InferredTypes::default(),
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn infer_program(mut self, program: &Program) -> InferredTypes {
+        self.analyze(program);
+        self.result
+    }
+
+    pub fn check_program(mut self, program: &Program) -> Vec<TypeError> {
+        self.analyze(program);
+        self.errors
+    }
+
+    fn analyze(&mut self, program: &Program) {
+        // Декларації, вимкнені через `@якщо`, не аналізуються
+        let program = &program.clone().configured();
+        // Спочатку збираємо сигнатури, щоб виклики функцій, оголошених нижче, теж виводились
+        for decl in &program.declarations {
+            if let Declaration::Function { name, params, return_type, .. } = decl {
+                self.functions.insert(name.clone(), (params.clone(), return_type.clone()));
+            }
+        }
+
+        for decl in &program.declarations {
+            self.declaration(decl);
+        }
+
+        self.check_interfaces(program);
+        self.check_initialization(program);
+    }
+
+    /// Читання змінних, оголошених без значення (`змінна х: цл32`), до першого присвоєння
+    fn check_initialization(&mut self, program: &Program) {
+        for decl in &program.declarations {
+            let functions: Vec<&Declaration> = match decl {
+                Declaration::Struct { methods, .. }
+                | Declaration::Impl { methods, .. }
+                | Declaration::TraitImpl { methods, .. } => methods.iter().collect(),
+                other => vec![other],
+            };
+            for function in functions {
+                if let Declaration::Function { name, body, .. } = function {
+                    self.errors.extend(InitCheck::function(name, body));
+                }
+            }
+        }
+    }
+
+    /// Звіряє методи структур із `реалізує` з методами оголошених інтерфейсів
+    fn check_interfaces(&mut self, program: &Program) {
+        let interfaces: HashMap<&str, &Vec<InterfaceMethod>> = program.declarations.iter()
+            .filter_map(|decl| match decl {
+                Declaration::Interface { name, methods, .. } => Some((name.as_str(), methods)),
+                _ => None,
+            })
+            .collect();
+
+        // Методи можуть бути оголошені в кількох блоках реалізації одного типу
+        let mut methods: HashMap<&str, HashMap<&str, (String, Span)>> = HashMap::new();
+        for decl in &program.declarations {
+            let (type_name, decls) = match decl {
+                Declaration::Struct { name, methods, .. } => (name, methods),
+                Declaration::Impl { type_name, methods } => (type_name, methods),
+                Declaration::TraitImpl { for_type, methods, .. } => (for_type, methods),
+                _ => continue,
+            };
+            let entry = methods.entry(type_name.as_str()).or_default();
+            for method in decls {
+                if let Declaration::Function { name, params, return_type, span, .. } = method {
+                    entry.insert(name.as_str(), (signature(params, return_type), *span));
+                }
+            }
+        }
+
+        for decl in &program.declarations {
+            let Declaration::Struct { name: type_name, interfaces: implemented, span, .. } = decl else { continue };
+            for interface in implemented {
+                let Some(required) = interfaces.get(interface.as_str()) else {
+                    self.errors.push(TypeError::UnknownInterface {
+                        type_name: type_name.clone(),
+                        interface: interface.clone(),
+                        line: span.line,
+                    });
+                    continue;
+                };
+                for method in required.iter() {
+                    let found = methods.get(type_name.as_str()).and_then(|m| m.get(method.name.as_str()));
+                    let Some((found, method_span)) = found else {
+                        self.errors.push(TypeError::MissingMethod {
+                            type_name: type_name.clone(),
+                            interface: interface.clone(),
+                            method: method.name.clone(),
+                            line: span.line,
+                        });
+                        continue;
+                    };
+                    let expected = signature(&method.params, &method.return_type);
+                    if &expected != found {
+                        self.errors.push(TypeError::MethodSignature {
+                            type_name: type_name.clone(),
+                            interface: interface.clone(),
+                            method: method.name.clone(),
+                            expected,
+                            found: found.clone(),
+                            line: method_span.line,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    fn declaration(&mut self, decl: &Declaration) {
+        match decl {
+            Declaration::Variable { name, ty, value, span, .. } => {
+                if let Some(v) = value {
+                    self.check_expression(v);
+                    if let (Some(expected), Some(found)) = (ty, self.checked_type(v)) {
+                        self.check_assignable(name, expected, &found, span.line);
+                    }
+                }
+                let inferred = ty.clone().or_else(|| value.as_ref().and_then(|v| self.infer_expression(v)));
+                if let Some(t) = inferred {
+                    self.result.variables.insert((name.clone(), *span), t.clone());
+                    self.define(name, t, ty.is_some());
+                }
+            }
+            Declaration::Function { name, params, body, .. } => {
+                let prev_function = std::mem::replace(&mut self.current_function, name.clone());
+                self.scopes.push(HashMap::new());
+                for param in params {
+                    self.define(&param.name, param.ty.clone(), true);
+                }
+                for stmt in body {
+                    self.statement(stmt);
+                }
+                self.scopes.pop();
+                self.current_function = prev_function;
+            }
+            _ => {}
+        }
+    }
+
+    fn statement(&mut self, stmt: &Statement) {
+        match stmt {
+            Statement::Expression(expr) | Statement::Assert(expr) | Statement::Yield(expr) => {
+                self.check_expression(expr);
+            }
+            Statement::Return(Some(expr)) => self.check_expression(expr),
+            Statement::Assignment { target, value, op, span } => {
+                self.check_expression(value);
+                if let (Expression::Identifier(name), AssignmentOp::Assign) = (target, op) {
+                    if let (Some(expected), Some(found)) = (self.lookup_annotated(name), self.checked_type(value)) {
+                        self.check_assignable(name, &expected, &found, span.line);
+                    }
+                }
+            }
+            Statement::MultiAssign { value, .. } => self.check_expression(value),
+            Statement::Declaration(decl) => self.declaration(decl),
+            Statement::Block(stmts) => self.block(stmts),
+            Statement::If { condition, then_branch, else_branch, .. } => {
+                self.check_expression(condition);
+                self.block(std::slice::from_ref(then_branch));
+                if let Some(else_stmt) = else_branch {
+                    self.block(std::slice::from_ref(else_stmt));
+                }
+            }
+            Statement::While { condition, body, .. } => {
+                self.check_expression(condition);
+                self.block(std::slice::from_ref(body));
+            }
+            Statement::For { variable, from, body, .. } => {
+                self.scopes.push(HashMap::new());
+                if let Some(t) = self.infer_expression(from) {
+                    self.define(variable, t, false);
+                }
+                self.statement(body);
+                self.scopes.pop();
+            }
+            Statement::ForIn { pattern, iterable, body } => {
+                self.scopes.push(HashMap::new());
+                if let Pattern::Binding(name) = pattern {
+                    let elem = match self.infer_expression(iterable) {
+                        Some(Type::Slice(elem)) | Some(Type::Array(elem, _)) => Some(*elem),
+                        Some(Type::Тхт) => Some(Type::Сим),
+                        _ => match iterable {
+                            Expression::Range { from, .. } => self.infer_expression(from),
+                            _ => None,
+                        },
+                    };
+                    if let Some(t) = elem {
+                        self.define(name, t, false);
+                    }
+                }
+                self.statement(body);
+                self.scopes.pop();
+            }
+            Statement::TryCatch { try_body, catch_body, finally_body, .. } => {
+                self.block(std::slice::from_ref(try_body));
+                if let Some(body) = catch_body {
+                    self.block(std::slice::from_ref(body));
+                }
+                if let Some(body) = finally_body {
+                    self.block(std::slice::from_ref(body));
+                }
+            }
+            Statement::CompTime(stmts) | Statement::Unsafe(stmts) => self.block(stmts),
+            Statement::Defer(body) => self.block(std::slice::from_ref(body)),
+            _ => {}
+        }
+    }
+
+    fn block(&mut self, stmts: &[Statement]) {
+        self.scopes.push(HashMap::new());
+        for stmt in stmts {
+            self.statement(stmt);
+        }
+        self.scopes.pop();
+    }
+
+    fn define(&mut self, name: &str, ty: Type, annotated: bool) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.to_string(), Binding { ty, annotated });
+        }
+    }
+
+    fn binding(&self, name: &str) -> Option<&Binding> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name))
+    }
+
+    fn lookup(&self, name: &str) -> Option<Type> {
+        self.binding(name).map(|binding| binding.ty.clone())
+    }
+
+    /// Тип змінної, лише якщо найближче оголошення з цим іменем анотоване
+    fn lookup_annotated(&self, name: &str) -> Option<Type> {
+        self.binding(name).filter(|binding| binding.annotated).map(|binding| binding.ty.clone())
+    }
+
+    fn check_assignable(&mut self, variable: &str, expected: &Type, found: &Type, line: usize) {
+        if !Self::compatible(expected, found) {
+            self.errors.push(TypeError::Assignment {
+                function: self.current_function.clone(),
+                variable: variable.to_string(),
+                expected: type_name(expected),
+                found: type_name(found),
+                line,
+            });
+        }
+    }
+
+    /// Перевіряє виклики відомих функцій у виразі (рекурсивно)
+    fn check_expression(&mut self, expr: &Expression) {
+        match expr {
+            Expression::Call { callee, args, span } => {
+                for arg in args {
+                    self.check_expression(arg);
+                }
+                let Expression::Identifier(callee_name) = callee.as_ref() else { return };
+                // Локальна змінна з тим самим ім'ям перекриває функцію
+                if self.lookup(callee_name).is_some() {
+                    return;
+                }
+                let Some((params, _)) = self.functions.get(callee_name).cloned() else { return };
+                for (param, arg) in params.iter().zip(args) {
+                    if let Some(found) = self.checked_type(arg) {
+                        if !Self::compatible(&param.ty, &found) {
+                            self.errors.push(TypeError::Argument {
+                                function: self.current_function.clone(),
+                                callee: callee_name.clone(),
+                                param: param.name.clone(),
+                                expected: type_name(&param.ty),
+                                found: type_name(&found),
+                                line: span.line,
+                            });
+                        }
+                    }
+                }
+            }
+            Expression::Binary { left, right, .. } => {
+                self.check_expression(left);
+                self.check_expression(right);
+            }
+            Expression::Unary { operand, .. } => self.check_expression(operand),
+            Expression::MethodCall { object, args, .. } => {
+                self.check_expression(object);
+                for arg in args {
+                    self.check_expression(arg);
+                }
+            }
+            Expression::Array(items) | Expression::Tuple(items) => {
+                for item in items {
+                    self.check_expression(item);
+                }
+            }
+            Expression::Index { object, index } => {
+                self.check_expression(object);
+                self.check_expression(index);
+            }
+            Expression::If { condition, then_expr, else_expr } => {
+                self.check_expression(condition);
+                self.check_expression(then_expr);
+                self.check_expression(else_expr);
+            }
+            Expression::Struct { fields, .. } => {
+                for (_, value) in fields {
+                    self.check_expression(value);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Чи можна значення типу `found` використати там, де очікується `expected`.
+    /// Цілі типи взаємосумісні, ціле неявно приводиться до дробового.
+    fn compatible(expected: &Type, found: &Type) -> bool {
+        let is_int = |t: &Type| matches!(t,
+            Type::Цл8 | Type::Цл16 | Type::Цл32 | Type::Цл64 |
+            Type::Чс8 | Type::Чс16 | Type::Чс32 | Type::Чс64);
+        let is_float = |t: &Type| matches!(t, Type::Дрб32 | Type::Дрб64);
+
+        match (expected, found) {
+            (Type::SelfType, _) | (_, Type::SelfType) => true,
+            (e, f) if is_int(e) && is_int(f) => true,
+            (e, f) if is_float(e) && (is_float(f) || is_int(f)) => true,
+            (Type::Optional(inner), f) => Self::compatible(inner, f),
+            (Type::Slice(e) | Type::Array(e, _), Type::Slice(f) | Type::Array(f, _)) => Self::compatible(e, f),
+            (Type::Tuple(es), Type::Tuple(fs)) => {
+                es.len() == fs.len() && es.iter().zip(fs).all(|(e, f)| Self::compatible(e, f))
+            }
+            // Іменовані та узагальнені типи (аліаси, трейти, структури) перевіряє VM
+            (Type::Generic(..) | Type::Named(_), _) | (_, Type::Named(_) | Type::Generic(..)) => true,
+            (e, f) => e == f,
+        }
+    }
+
+    /// Виводить тип виразу; None — тип невідомий на етапі компіляції
+    pub fn infer_expression(&self, expr: &Expression) -> Option<Type> {
+        self.infer(expr, false)
+    }
+
+    /// Тип виразу для перевірки: неанотовані змінні можуть змінити тип,
+    /// тож їхні виведені типи не беруться до уваги
+    fn checked_type(&self, expr: &Expression) -> Option<Type> {
+        self.infer(expr, true)
+    }
+
+    fn infer(&self, expr: &Expression, annotated_only: bool) -> Option<Type> {
+        match expr {
+            // Цілі літерали компілюються як i32 (див. Compiler::compile_literal)
+            Expression::Literal(Literal::Integer(_)) => Some(Type::Цл32),
+            Expression::Literal(Literal::Float(_)) => Some(Type::Дрб64),
+            Expression::Literal(Literal::String(_)) => Some(Type::Тхт),
+            Expression::Literal(Literal::Char(_)) => Some(Type::Сим),
+            Expression::Literal(Literal::Bool(_)) => Some(Type::Лог),
+            Expression::Literal(Literal::Null) => None,
+            Expression::Identifier(name) if annotated_only => self.lookup_annotated(name),
+            Expression::Identifier(name) => self.lookup(name),
+            Expression::Binary { left, op, right, .. } => match op {
+                BinaryOp::Eq | BinaryOp::Ne | BinaryOp::Lt | BinaryOp::Le
+                | BinaryOp::Gt | BinaryOp::Ge | BinaryOp::And | BinaryOp::Or
+                | BinaryOp::In => Some(Type::Лог),
+                _ => {
+                    let lhs = self.infer(left, annotated_only);
+                    let rhs = self.infer(right, annotated_only);
+                    match (lhs, rhs) {
+                        (Some(a), Some(b)) => Some(Self::unify_numeric(a, b)),
+                        (Some(t), None) | (None, Some(t)) => Some(t),
+                        (None, None) => None,
+                    }
+                }
+            },
+            Expression::Unary { op: UnaryOp::Not, .. } => Some(Type::Лог),
+            Expression::Unary { operand, .. } => self.infer(operand, annotated_only),
+            Expression::Call { callee, .. } => match callee.as_ref() {
+                Expression::Identifier(name) => self.functions.get(name).and_then(|(_, ret)| ret.clone()),
+                _ => None,
+            },
+            Expression::Cast { ty, .. } => Some(ty.clone()),
+            Expression::Array(elements) => {
+                let elem = elements.first().and_then(|e| self.infer(e, annotated_only))?;
+                Some(Type::Slice(Box::new(elem)))
+            }
+            Expression::Tuple(elements) => {
+                let types: Option<Vec<Type>> = elements.iter().map(|e| self.infer(e, annotated_only)).collect();
+                types.map(Type::Tuple)
+            }
+            Expression::Struct { name, .. } => Some(Type::Named(name.clone())),
+            Expression::FormatString(_) => Some(Type::Тхт),
+            Expression::If { then_expr, else_expr, .. } => {
+                self.infer(then_expr, annotated_only).or_else(|| self.infer(else_expr, annotated_only))
+            }
+            Expression::Index { object, .. } => match self.infer(object, annotated_only)? {
+                Type::Slice(elem) | Type::Array(elem, _) => Some(*elem),
+                Type::Тхт => Some(Type::Сим),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Тип результату арифметики: дробовий має пріоритет, далі ширший цілий
+    fn unify_numeric(left: Type, right: Type) -> Type {
+        fn rank(t: &Type) -> Option<u8> {
+            match t {
+                Type::Цл8 | Type::Чс8 => Some(1),
+                Type::Цл16 | Type::Чс16 => Some(2),
+                Type::Цл32 | Type::Чс32 => Some(3),
+                Type::Цл64 | Type::Чс64 => Some(4),
+                Type::Дрб32 => Some(5),
+                Type::Дрб64 => Some(6),
+                _ => None,
+            }
+        }
+        if left == Type::Тхт || right == Type::Тхт {
+            return Type::Тхт;
+        }
+        match (rank(&left), rank(&right)) {
+            (Some(a), Some(b)) if b > a => right,
+            (Some(_), _) => left,
+            (None, _) => right,
+        }
+    }
+}
+
+/// Потік ініціалізації в межах функції. Стан — множина змінних, оголошених
+/// без значення, яким ще не присвоєно; `None` — гілка завжди виходить раніше
+/// (`повернути`, `переривати`), тож у злиття після неї нічого не потрапляє
+struct InitCheck {
+    function: String,
+    /// Рядок останнього вузла з позицією — ідентифікатори власної не мають
+    line: usize,
+    reported: HashSet<String>,
+    errors: Vec<TypeError>,
+}
+
+type InitState = Option<HashSet<String>>;
+
+impl InitCheck {
+    fn function(name: &str, body: &[Statement]) -> Vec<TypeError> {
+        let mut check = InitCheck {
+            function: name.to_string(),
+            line: 0,
+            reported: HashSet::new(),
+            errors: Vec::new(),
+        };
+        check.block(body, HashSet::new());
+        check.errors
+    }
+
+    fn block(&mut self, stmts: &[Statement], mut uninit: HashSet<String>) -> InitState {
+        let outer = uninit.clone();
+        let mut declared = Vec::new();
+        for stmt in stmts {
+            if let Statement::Declaration(Declaration::Variable { name, .. }) = stmt {
+                declared.push(name.clone());
+            }
+            uninit = self.statement(stmt, uninit)?;
+        }
+        // Оголошені в блоці змінні виходять з області видимості, а зовнішні з тими ж іменами
+        // повертаються до свого стану
+        for name in declared {
+            if outer.contains(&name) {
+                uninit.insert(name);
+            } else {
+                uninit.remove(&name);
+            }
+        }
+        Some(uninit)
+    }
+
+    fn statement(&mut self, stmt: &Statement, mut uninit: HashSet<String>) -> InitState {
+        match stmt {
+            Statement::Declaration(Declaration::Variable { name, ty, value, span, .. }) => {
+                self.at(*span);
+                match value {
+                    Some(value) => {
+                        self.read(value, &mut uninit);
+                        uninit.remove(name);
+                    }
+                    // VM заповнює масив фіксованого розміру нулями
+                    None if matches!(ty, Some(Type::Array(..))) => {
+                        uninit.remove(name);
+                    }
+                    None => {
+                        uninit.insert(name.clone());
+                    }
+                }
+            }
+            Statement::Assignment { target, value, op, span } => {
+                self.at(*span);
+                self.read(value, &mut uninit);
+                match (target, op) {
+                    (Expression::Identifier(name), AssignmentOp::Assign) => {
+                        uninit.remove(name);
+                    }
+                    // `х += 1` і `х[0] = 1` спершу читають `х`
+                    _ => self.read(target, &mut uninit),
+                }
+            }
+            Statement::MultiAssign { targets, value, span } => {
+                self.at(*span);
+                self.read(value, &mut uninit);
+                for target in targets {
+                    match target {
+                        Expression::Identifier(name) => {
+                            uninit.remove(name);
+                        }
+                        other => self.read(other, &mut uninit),
+                    }
+                }
+            }
+            Statement::Expression(expr) | Statement::Assert(expr) | Statement::Yield(expr) => self.read(expr, &mut uninit),
+            Statement::Destructure { value, .. } => self.read(value, &mut uninit),
+            Statement::Return(value) => {
+                if let Some(value) = value {
+                    self.read(value, &mut uninit);
+                }
+                return None;
+            }
+            Statement::BreakValue(expr) => {
+                self.read(expr, &mut uninit);
+                return None;
+            }
+            Statement::Break | Statement::Continue => return None,
+            Statement::Block(stmts) | Statement::Unsafe(stmts) | Statement::CompTime(stmts) => {
+                return self.block(stmts, uninit);
+            }
+            Statement::If { condition, then_branch, else_branch, span } => {
+                self.at(*span);
+                self.read(condition, &mut uninit);
+                let after_then = self.statement(then_branch, uninit.clone());
+                let after_else = match else_branch {
+                    Some(branch) => self.statement(branch, uninit),
+                    None => Some(uninit),
+                };
+                return merge(after_then, after_else);
+            }
+            // Тіло циклу може не виконатись жодного разу, тож присвоєння в ньому не враховуються
+            Statement::While { condition, body, span } => {
+                self.at(*span);
+                self.read(condition, &mut uninit);
+                self.statement(body, uninit.clone());
+            }
+            Statement::For { variable, from, to, step, body, span } => {
+                self.at(*span);
+                for expr in [from, to].into_iter().chain(step) {
+                    self.read(expr, &mut uninit);
+                }
+                let mut inner = uninit.clone();
+                inner.remove(variable);
+                self.statement(body, inner);
+            }
+            Statement::ForIn { iterable, body, .. } => {
+                self.read(iterable, &mut uninit);
+                self.statement(body, uninit.clone());
+            }
+            Statement::TryCatch { try_body, catch_body, finally_body, .. } => {
+                // Помилка може перервати `спробувати` на будь-якій інструкції
+                let after_try = self.statement(try_body, uninit.clone());
+                let after = match catch_body {
+                    Some(body) => merge(after_try, self.statement(body, uninit.clone())),
+                    None => after_try,
+                };
+                if let Some(body) = finally_body {
+                    self.statement(body, uninit);
+                }
+                return after;
+            }
+            Statement::WithHandler { body, .. } => return self.statement(body, uninit),
+            // Відкладена дія та вкладені оголошення виконуються пізніше
+            Statement::Defer(_) | Statement::Declaration(_) => {}
+        }
+        Some(uninit)
+    }
+
+    fn at(&mut self, span: Span) {
+        if span.line > 0 {
+            self.line = span.line;
+        }
+    }
+
+    /// Читання змінних у виразі. Блок-вираз може й присвоювати, тож стан
+    /// після нього оновлюється; умовні частини виразу його не змінюють
+    fn read(&mut self, expr: &Expression, uninit: &mut HashSet<String>) {
+        match expr {
+            Expression::Identifier(name) if uninit.contains(name) => {
+                // Про кожну змінну повідомляємо один раз — на першому читанні
+                if !self.reported.insert(name.clone()) {
+                    return;
+                }
+                self.errors.push(TypeError::Uninitialized {
+                    function: self.function.clone(),
+                    variable: name.clone(),
+                    line: self.line,
+                });
+            }
+            Expression::Binary { left, op, right, span } => {
+                self.at(*span);
+                self.read(left, uninit);
+                if matches!(op, BinaryOp::And | BinaryOp::Or) {
+                    self.read(right, &mut uninit.clone());
+                } else {
+                    self.read(right, uninit);
+                }
+            }
+            Expression::Call { callee, args, span } => {
+                self.at(*span);
+                self.read(callee, uninit);
+                for arg in args {
+                    self.read(arg, uninit);
+                }
+            }
+            Expression::MethodCall { object, args, span, .. } => {
+                self.at(*span);
+                self.read(object, uninit);
+                for arg in args {
+                    self.read(arg, uninit);
+                }
+            }
+            Expression::Unary { operand: inner, .. }
+            | Expression::MemberAccess { object: inner, .. }
+            | Expression::Cast { expr: inner, .. }
+            | Expression::ErrorPropagation(inner)
+            | Expression::Await(inner) => self.read(inner, uninit),
+            Expression::Index { object: left, index: right }
+            | Expression::Pipeline { left, right }
+            | Expression::Range { from: left, to: right, .. } => {
+                self.read(left, uninit);
+                self.read(right, uninit);
+            }
+            Expression::Array(items) | Expression::Tuple(items) | Expression::EnumConstruct { args: items, .. } => {
+                for item in items {
+                    self.read(item, uninit);
+                }
+            }
+            Expression::Struct { fields, .. } => {
+                for (_, value) in fields {
+                    self.read(value, uninit);
+                }
+            }
+            Expression::If { condition, then_expr, else_expr } => {
+                self.read(condition, uninit);
+                let mut after_then = uninit.clone();
+                self.read(then_expr, &mut after_then);
+                self.read(else_expr, uninit);
+                uninit.extend(after_then);
+            }
+            Expression::Match { subject, arms } => {
+                self.read(subject, uninit);
+                if arms.is_empty() {
+                    return;
+                }
+                let before = std::mem::take(uninit);
+                for arm in arms {
+                    let mut after_arm = before.clone();
+                    self.read(&arm.body, &mut after_arm);
+                    uninit.extend(after_arm);
+                }
+            }
+            Expression::FormatString(parts) => {
+                for part in parts {
+                    if let FormatPart::Expr(expr) = part {
+                        self.read(expr, uninit);
+                    }
+                }
+            }
+            Expression::Block(stmts, result) => {
+                // Блок, що завжди виходить раніше, не змінює стану після себе
+                if let Some(mut state) = self.block(stmts, uninit.clone()) {
+                    if let Some(result) = result {
+                        self.read(result, &mut state);
+                    }
+                    *uninit = state;
+                }
+            }
+            // Тіло лямбди виконується пізніше, коли змінна вже може мати значення
+            _ => {}
+        }
+    }
+}
+
+/// Після розгалуження змінна ініціалізована, лише якщо так у кожній гілці, що продовжується
+fn merge(a: InitState, b: InitState) -> InitState {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.union(&b).cloned().collect()),
+        (state, None) | (None, state) => state,
+    }
+}
+
+/// Виводить типи всіх змінних програми
+pub fn infer_types(program: &Program) -> InferredTypes {
+    TypeInference::new().infer_program(program)
+}
+
+/// Перевіряє типи присвоєнь та аргументів викликів до виконання
+pub fn type_check(program: &Program) -> Vec<TypeError> {
+    TypeInference::new().check_program(program)
+}
+
+/// Сигнатура методу без імен параметрів: `(себе, цл32) -> тхт`
+fn signature(params: &[Parameter], return_type: &Option<Type>) -> String {
+    let params = params.iter().map(|p| type_name(&p.ty)).collect::<Vec<_>>().join(", ");
+    match return_type {
+        Some(ty) => format!("({}) -> {}", params, type_name(ty)),
+        None => format!("({})", params),
+    }
+}
+
+/// Назва типу для повідомлень про помилки
+pub fn type_name(ty: &Type) -> String {
+    match ty {
+        Type::Цл8 => "цл8".to_string(),
+        Type::Цл16 => "цл16".to_string(),
+        Type::Цл32 => "цл32".to_string(),
+        Type::Цл64 => "цл64".to_string(),
+        Type::Чс8 => "чс8".to_string(),
+        Type::Чс16 => "чс16".to_string(),
+        Type::Чс32 => "чс32".to_string(),
+        Type::Чс64 => "чс64".to_string(),
+        Type::Дрб32 => "дрб32".to_string(),
+        Type::Дрб64 => "дрб64".to_string(),
+        Type::Лог => "лог".to_string(),
+        Type::Сим => "сим".to_string(),
+        Type::Тхт => "тхт".to_string(),
+        Type::Array(inner, size) => format!("{}[{}]", type_name(inner), size),
+        Type::Slice(inner) => format!("[{}]", type_name(inner)),
+        Type::Tuple(items) => format!("({})", items.iter().map(type_name).collect::<Vec<_>>().join(", ")),
+        Type::Reference(inner, _) => format!("&{}", type_name(inner)),
+        Type::Function(..) => "функція".to_string(),
+        Type::Named(name) => name.clone(),
+        Type::Generic(name, params) => format!("{}<{}>", name, params.iter().map(type_name).collect::<Vec<_>>().join(", ")),
+        Type::Optional(inner) => format!("Опція<{}>", type_name(inner)),
+        Type::Result(ok, err) => format!("Результат<{}, {}>", type_name(ok), type_name(err)),
+        Type::SelfType => "себе".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tryzub_lexer::tokenize;
+
+    fn infer(source: &str) -> InferredTypes {
+        let tokens = tokenize(source).unwrap();
+        let program = parse(tokens).unwrap();
+        infer_types(&program)
+    }
+
+    #[test]
+    fn test_infer_int_and_float() {
+        let types = infer(r#"
+змінна глобальна = 1
+функція головна() {
+    змінна х = 10
+    змінна у = 2.5
+    змінна з = х * у
+    змінна п = х > 3
+}
+"#);
+        assert_eq!(declared(&types, "глобальна"), vec![(2, Type::Цл32)]);
+        assert_eq!(declared(&types, "х"), vec![(4, Type::Цл32)]);
+        assert_eq!(declared(&types, "у"), vec![(5, Type::Дрб64)]);
+        assert_eq!(declared(&types, "з"), vec![(6, Type::Дрб64)]);
+        assert_eq!(declared(&types, "п"), vec![(7, Type::Лог)]);
+    }
+
+    /// Рядки оголошень змінної з виведеними типами, за порядком у коді
+    fn declared(types: &InferredTypes, name: &str) -> Vec<(usize, Type)> {
+        let mut found: Vec<(usize, Type)> = types.variables.iter()
+            .filter(|((variable, _), _)| variable == name)
+            .map(|((_, span), ty)| (span.line, ty.clone()))
+            .collect();
+        found.sort_by_key(|(line, _)| *line);
+        found
+    }
+
+    #[test]
+    fn test_infer_shadowed_variables_separately() {
+        let types = infer(r#"
+функція головна() {
+    змінна х = 1
+    якщо х > 0 {
+        змінна х = "а"
+        друк(х)
+    }
+    друк(х)
+}
+"#);
+        assert_eq!(declared(&types, "х"), vec![(3, Type::Цл32), (5, Type::Тхт)]);
+    }
+
+    #[test]
+    fn test_infer_function_result() {
+        let types = infer(r#"
+функція головна() {
+    змінна с = площа(2.0) + 1.0
+    змінна к = кількість()
+}
+функція площа(р: дрб64) -> дрб64 { повернути р * р }
+функція кількість() -> цл64 { повернути 3 }
+"#);
+        assert_eq!(declared(&types, "с"), vec![(3, Type::Дрб64)]);
+        assert_eq!(declared(&types, "к"), vec![(4, Type::Цл64)]);
+    }
+
+    fn check(source: &str) -> Vec<TypeError> {
+        let tokens = tokenize(source).unwrap();
+        let program = parse(tokens).unwrap();
+        type_check(&program)
+    }
+
+    #[test]
+    fn test_type_check_assignment_mismatch() {
+        let errors = check(r#"
+функція головна() {
+    змінна х: цл32 = 1
+    х = "рядок"
+    змінна у: дрб64 = 5
+}
+"#);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(&errors[0], TypeError::Assignment { variable, found, line: 4, .. }
+            if variable == "х" && found == "тхт"));
+    }
+
+    #[test]
+    fn test_unannotated_variables_stay_dynamic() {
+        let errors = check(r#"
+функція квадрат(н: цл64) -> цл64 { повернути н * н }
+функція будь_що(з) { друк(з) }
+функція головна() {
+    змінна х = 10
+    х = "а"
+    будь_що(х)
+    квадрат(х)
+    змінна у: тхт = х
+}
+"#);
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_type_check_argument_mismatch() {
+        let errors = check(r#"
+функція квадрат(н: цл64) -> цл64 { повернути н * н }
+функція корінь(д: дрб64) -> дрб64 { повернути д }
+функція головна() {
+    друк(квадрат("два"))
+    корінь(4)
+}
+"#);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(&errors[0], TypeError::Argument { callee, param, line: 5, .. }
+            if callee == "квадрат" && param == "н"));
+    }
+
+    #[test]
+    fn test_interface_fully_implemented() {
+        let errors = check(r#"
+інтерфейс Форма {
+    функція площа(себе) -> дрб64
+}
+інтерфейс Назване {
+    функція назва(себе) -> тхт
+    функція перейменувати(себе, нова: тхт)
+}
+структура Коло реалізує Форма, Назване {
+    радіус: дрб64,
+    імя: тхт
+}
+реалізація Коло {
+    функція площа(себе) -> дрб64 { повернути 3.14 * себе.радіус * себе.радіус }
+    функція назва(себе) -> тхт { повернути себе.імя }
+}
+реалізація Коло {
+    функція перейменувати(себе, нова: тхт) { себе.імя = нова }
+}
+"#);
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_interface_missing_and_mismatched_methods() {
+        let errors = check(r#"
+інтерфейс Форма {
+    функція площа(себе) -> дрб64
+    функція периметр(себе) -> дрб64
+}
+структура Квадрат реалізує Форма, Невідомий {
+    сторона: дрб64
+}
+реалізація Квадрат {
+    функція площа(себе) -> цл64 { повернути 1 }
+}
+"#);
+        assert_eq!(errors.len(), 3, "{:?}", errors);
+        assert!(errors.contains(&TypeError::MethodSignature {
+            type_name: "Квадрат".to_string(),
+            interface: "Форма".to_string(),
+            method: "площа".to_string(),
+            expected: "(себе) -> дрб64".to_string(),
+            found: "(себе) -> цл64".to_string(),
+            line: 10,
+        }));
+        assert!(errors.iter().any(|e| matches!(e, TypeError::MissingMethod { method, line: 6, .. } if method == "периметр")));
+        assert!(errors.iter().any(|e| matches!(e, TypeError::UnknownInterface { interface, line: 6, .. } if interface == "Невідомий")));
+    }
+
+    #[test]
+    fn test_read_before_initialization() {
+        let errors = check(r#"
+функція погана(п: лог) -> цл32 {
+    змінна х: цл32
+    якщо п {
+        х = 1
+    }
+    повернути х + 1
+}
+"#);
+        assert_eq!(errors, vec![TypeError::Uninitialized {
+            function: "погана".to_string(),
+            variable: "х".to_string(),
+            line: 7,
+        }]);
+    }
+
+    #[test]
+    fn test_initialized_in_every_branch() {
+        let errors = check(r#"
+функція добра(п: лог) -> цл32 {
+    змінна х: цл32
+    якщо п { х = 1 } інакше { х = 2 }
+    змінна у: цл32
+    якщо п { повернути х } інакше { у = 3 }
+    змінна з: цл32
+    з = х + у
+    повернути з
+}
+"#);
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_fixed_array_and_block_expression_initialize() {
+        let errors = check(r#"
+функція ф() -> цл64 {
+    змінна буфер: [цл64; 4]
+    буфер[0] = 7
+    змінна х: цл64
+    стала у = { х = 5; х + 1 }
+    повернути буфер[0] + х + у
+}
+"#);
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+}
@@ -0,0 +1,166 @@
+// Уніфікований вивід помилок у стилі rustc: вид помилки, місце `файл:рядок:стовпець`
+// і фрагмент коду з позначкою `^`. Колір вмикається лише тоді, коли stderr є
+// терміналом і змінна NO_COLOR не задана.
+
+use colored::Colorize;
+use std::io::IsTerminal;
+use std::path::Path;
+
+/// Етап, на якому виникла помилка
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    Lexical,
+    Syntax,
+    Type,
+    Runtime,
+}
+
+impl ErrorKind {
+    fn label(self) -> &'static str {
+        match self {
+            ErrorKind::Lexical => "лексика",
+            ErrorKind::Syntax => "синтаксис",
+            ErrorKind::Type => "типи",
+            ErrorKind::Runtime => "виконання",
+        }
+    }
+}
+
+/// Місце помилки у файлі, про який звітуємо. Береться лише з типізованих помилок
+/// розбору й перевірки самого файлу: текст помилки виконання може згадувати рядки
+/// імпортованого модуля чи рядки з даних користувача
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: Option<usize>,
+}
+
+impl Location {
+    /// Позиція помилки лексера чи парсера, що розбирав цей файл
+    pub fn of_syntax_error(err: &anyhow::Error) -> Option<Location> {
+        if let Some(err) = err.downcast_ref::<tryzub_lexer::LexerError>() {
+            let (line, column) = err.position();
+            return Some(Location { line, column });
+        }
+        err.downcast_ref::<tryzub_parser::ParseError>().map(Location::of_parse_error)
+    }
+
+    pub fn of_parse_error(err: &tryzub_parser::ParseError) -> Location {
+        let (line, column) = err.position();
+        Location { line, column: Some(column) }
+    }
+}
+
+/// Друкує помилку в stderr. `line_offset` — рядки, додані перед `source`
+/// при розборі (обгортка `головна`)
+pub fn report_error(kind: ErrorKind, message: &str, file: &Path, location: Option<Location>, source: Option<&str>, line_offset: usize) {
+    colored::control::set_override(use_color());
+    eprint!("{}", render(kind, message, file, location, source, line_offset));
+}
+
+/// Підсумкове повідомлення команди, що завершилась помилкою
+pub fn report_failure(message: &str) {
+    colored::control::set_override(use_color());
+    eprintln!("{}", format!("[X] {}", message).red().bold());
+}
+
+fn use_color() -> bool {
+    std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty()) && std::io::stderr().is_terminal()
+}
+
+fn render(kind: ErrorKind, message: &str, file: &Path, location: Option<Location>, source: Option<&str>, line_offset: usize) -> String {
+    let mut out = format!(
+        "{}{} {}\n",
+        format!("помилка[{}]", kind.label()).red().bold(),
+        ":".bold(),
+        message.bold()
+    );
+    let arrow = "-->".cyan().bold();
+    let position = location
+        .map(|location| (location.line.saturating_sub(line_offset), location.column))
+        .filter(|&(line, _)| line > 0);
+    let Some((line, column)) = position else {
+        out.push_str(&format!("  {} {}\n", arrow, file.display()));
+        return out;
+    };
+
+    let pad = " ".repeat((line + 1).to_string().len());
+    let location = match column {
+        Some(column) => format!("{}:{}:{}", file.display(), line, column),
+        None => format!("{}:{}", file.display(), line),
+    };
+    out.push_str(&format!("{} {} {}\n", pad, arrow, location));
+
+    let lines: Vec<&str> = source.unwrap_or_default().lines().collect();
+    if line > lines.len() {
+        return out;
+    }
+    let bar = "|".cyan().bold();
+    out.push_str(&format!("{} {}\n", pad, bar));
+    for number in line.saturating_sub(1).max(1)..=(line + 1).min(lines.len()) {
+        let text = lines[number - 1];
+        let gutter = format!("{:>width$}", number, width = pad.len()).cyan().bold();
+        out.push_str(&format!("{} {} {}\n", gutter, bar, text));
+        if number == line {
+            out.push_str(&format!("{} {} {}\n", pad, bar, marker(text, column)));
+        }
+    }
+    out.push_str(&format!("{} {}\n", pad, bar));
+    out
+}
+
+/// `^` під стовпцем помилки; без стовпця підкреслюється весь рядок без відступу
+fn marker(text: &str, column: Option<usize>) -> String {
+    let indent = text.chars().take_while(|c| c.is_whitespace()).count();
+    let (start, width) = match column {
+        Some(column) => (column.saturating_sub(1), 1),
+        None => (indent, text.chars().count().saturating_sub(indent).max(1)),
+    };
+    // Табуляції копіюємо, щоб маркер вирівнювався так само, як текст
+    let padding: String = text.chars().take(start).map(|c| if c == '\t' { '\t' } else { ' ' }).collect();
+    format!("{}{}", padding, "^".repeat(width).red().bold())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_without_color() {
+        colored::control::set_override(false);
+        let source = "функція головна() {\n    змінна х = )\n}\n";
+        let location = Some(Location { line: 2, column: Some(16) });
+        let text = render(ErrorKind::Syntax, "Невалідний вираз", Path::new("а.тризуб"), location, Some(source), 0);
+        assert_eq!(text, "\
+помилка[синтаксис]: Невалідний вираз
+  --> а.тризуб:2:16
+  |
+1 | функція головна() {
+2 |     змінна х = )
+  |                ^
+3 | }
+  |
+");
+        assert!(!text.contains('\u{1b}'));
+
+        // Без місця — лише файл, навіть якщо текст згадує якийсь рядок
+        let text = render(ErrorKind::Runtime, "Помилка в модулі на рядку 50", Path::new("а.тризуб"), None, Some(source), 0);
+        assert_eq!(text, "помилка[виконання]: Помилка в модулі на рядку 50\n  --> а.тризуб\n");
+    }
+
+    #[test]
+    fn test_marker_position() {
+        colored::control::set_override(false);
+        let source = "змінна а = 1\nзмінна б = а № 2";
+        let err = tryzub_lexer::tokenize(source).unwrap_err();
+        let location = Location::of_syntax_error(&err).unwrap();
+        assert_eq!(location, Location { line: 2, column: Some(14) });
+        // Стовпець рахується в символах, тож кирилиця не зсуває маркер
+        assert_eq!(marker("змінна б = а № 2", location.column), "             ^");
+
+        assert_eq!(marker("\tх = \"", Some(2)), "\t^");
+        assert_eq!(marker("\tх = \"", None), "\t^^^^^");
+        let text = render(ErrorKind::Lexical, "Незавершений рядок", Path::new("а.тризуб"), Some(Location { line: 5, column: None }), Some(source), 0);
+        assert_eq!(text, "помилка[лексика]: Незавершений рядок\n  --> а.тризуб:5\n");
+    }
+}
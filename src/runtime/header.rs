@@ -0,0 +1,273 @@
+// C-заголовок для FFI: будується з `#[repr(C)]` типів і `#[no_mangle]` функцій
+// цього крейта, тож не розходиться з реальними сигнатурами.
+
+use anyhow::Result;
+
+/// Ім'я заголовка за замовчуванням
+pub const HEADER_NAME: &str = "tryzub.h";
+
+const SOURCE: &str = include_str!("lib.rs");
+
+const C_KEYWORDS: &[&str] = &[
+    "char", "double", "float", "int", "long", "short", "signed", "unsigned", "void",
+    "default", "register", "volatile", "auto", "extern", "static", "union",
+];
+
+/// `#[repr(C)]` тип: поля `(ім'я, Rust-тип)` або варіанти enum
+struct CItem {
+    kind: &'static str,
+    name: String,
+    members: Vec<(String, String)>,
+}
+
+/// `#[no_mangle]` функція: рядки doc-коментаря та сигнатура без `fn`
+struct CFunction {
+    docs: Vec<String>,
+    signature: String,
+}
+
+/// Текст заголовка з прототипами `tryzub_*` і структурами `TryzubValue`, `TryzubArray`
+pub fn c_header() -> Result<String> {
+    let (items, functions) = scan(SOURCE)?;
+
+    let mut out = format!(
+        "/* Згенеровано `тризуб заголовок` з tryzub-runtime {}; не редагуйте вручну */\n",
+        env!("CARGO_PKG_VERSION")
+    );
+    out.push_str("#ifndef TRYZUB_H\n#define TRYZUB_H\n\n");
+    out.push_str("#include <stdbool.h>\n#include <stddef.h>\n#include <stdint.h>\n\n");
+    out.push_str("#ifdef __cplusplus\nextern \"C\" {\n#endif\n\n");
+
+    for item in items.iter().filter(|i| i.kind == "enum") {
+        let variants: Vec<String> = item.members.iter()
+            .map(|(variant, _)| format!("    {}_{},\n", item.name, variant))
+            .collect();
+        out.push_str(&format!("typedef enum {} {{\n{}}} {};\n\n", item.name, variants.concat(), item.name));
+    }
+    let records: Vec<&CItem> = items.iter().filter(|i| i.kind != "enum").collect();
+    for item in &records {
+        out.push_str(&format!("typedef {} {} {};\n", item.kind, item.name, item.name));
+    }
+    out.push('\n');
+
+    // Поле за значенням вимагає повного визначення свого типу раніше
+    let mut emitted: Vec<&str> = Vec::new();
+    while emitted.len() < records.len() {
+        let ready = records.iter()
+            .find(|item| !emitted.contains(&item.name.as_str()) && item.members.iter().all(|(_, ty)| {
+                !records.iter().any(|r| r.name == ty.trim()) || emitted.contains(&ty.trim())
+            }))
+            .ok_or_else(|| anyhow::anyhow!("Циклічна залежність між #[repr(C)] типами"))?;
+        out.push_str(&format!("{} {} {{\n", ready.kind, ready.name));
+        for (field, ty) in &ready.members {
+            // `()` у C не має відповідника; такий варіант union лише позначає відсутність даних
+            if ty.trim() == "()" {
+                continue;
+            }
+            // Поле Rust може збігатися з ключовим словом C (`float`); ABI від імені не залежить
+            let field = if C_KEYWORDS.contains(&field.as_str()) { format!("{}_", field) } else { field.clone() };
+            out.push_str(&format!("    {};\n", declare(ty, &field)?));
+        }
+        out.push_str("};\n\n");
+        emitted.push(&ready.name);
+    }
+
+    for CFunction { docs, signature } in &functions {
+        for line in docs {
+            out.push_str(&format!("//{}\n", line));
+        }
+        out.push_str(&format!("{};\n\n", prototype(signature)?));
+    }
+
+    out.push_str("#ifdef __cplusplus\n}\n#endif\n\n#endif /* TRYZUB_H */\n");
+    Ok(out)
+}
+
+/// `#[repr(C)]` типи та `#[no_mangle]` функції (з doc-коментарями) з вихідного коду
+fn scan(source: &str) -> Result<(Vec<CItem>, Vec<CFunction>)> {
+    let mut items = Vec::new();
+    let mut functions = Vec::new();
+    let mut docs: Vec<String> = Vec::new();
+    let mut repr_c = false;
+    let mut no_mangle = false;
+    let mut lines = source.lines();
+
+    while let Some(line) = lines.next() {
+        let line = line.trim();
+        if line == "#[cfg(test)]" {
+            break;
+        }
+        if let Some(doc) = line.strip_prefix("///") {
+            docs.push(doc.to_string());
+            continue;
+        }
+        match line {
+            "#[repr(C)]" => { repr_c = true; continue; }
+            "#[no_mangle]" => { no_mangle = true; continue; }
+            _ if line.starts_with("#[") => continue,
+            _ => {}
+        }
+
+        if repr_c {
+            let kind = ["struct", "enum", "union"].into_iter()
+                .find(|kind| line.starts_with(&format!("pub {} ", kind)))
+                .ok_or_else(|| anyhow::anyhow!("Непідтримуваний #[repr(C)] елемент: {}", line))?;
+            let name = line[format!("pub {} ", kind).len()..].trim_end_matches('{').trim().to_string();
+            let mut members = Vec::new();
+            for member in lines.by_ref().map(str::trim).take_while(|l| *l != "}") {
+                let member = member.trim_end_matches(',');
+                if member.is_empty() || member.starts_with("//") {
+                    continue;
+                }
+                match member.strip_prefix("pub ").and_then(|m| m.split_once(':')) {
+                    Some((field, ty)) => members.push((field.trim().to_string(), ty.trim().to_string())),
+                    None => members.push((member.to_string(), String::new())),
+                }
+            }
+            items.push(CItem { kind, name, members });
+        } else if no_mangle {
+            let signature = line.split_once("fn ")
+                .map(|(_, rest)| rest.trim_end_matches('{').trim().to_string())
+                .ok_or_else(|| anyhow::anyhow!("Очікувалась функція після #[no_mangle]: {}", line))?;
+            // Розділ `# Safety` стосується Rust-викликачів
+            let mut docs: Vec<String> = docs.iter().take_while(|d| d.trim() != "# Safety").cloned().collect();
+            while docs.last().is_some_and(|d| d.trim().is_empty()) {
+                docs.pop();
+            }
+            functions.push(CFunction { docs, signature });
+        }
+        repr_c = false;
+        no_mangle = false;
+        docs.clear();
+    }
+    Ok((items, functions))
+}
+
+/// `ім'я(параметри) -> Тип` → C-прототип
+fn prototype(signature: &str) -> Result<String> {
+    let (name, rest) = signature.split_once('(')
+        .ok_or_else(|| anyhow::anyhow!("Некоректна сигнатура: {}", signature))?;
+    declare_function(name.trim(), rest)
+}
+
+/// Оголошення функції `name`; `rest` — усе після відкривної дужки параметрів
+fn declare_function(name: &str, rest: &str) -> Result<String> {
+    let close = matching_paren(rest)?;
+    let (params, ret) = (&rest[..close], rest[close + 1..].trim());
+    let params: Vec<String> = split_top_level(params).into_iter()
+        .map(|param| {
+            let (param_name, ty) = param.split_once(':')
+                .ok_or_else(|| anyhow::anyhow!("Некоректний параметр: {}", param))?;
+            declare(ty, param_name.trim())
+        })
+        .collect::<Result<_>>()?;
+    let params = if params.is_empty() { "void".to_string() } else { params.join(", ") };
+    let call = format!("{}({})", name, params);
+    match ret.strip_prefix("->") {
+        Some(ret) => declare(ret, &call),
+        None => Ok(format!("void {}", call)),
+    }
+}
+
+/// C-оголошення `name` типу `ty`; покажчик на функцію обгортає ім'я: `R (*name)(...)`
+fn declare(ty: &str, name: &str) -> Result<String> {
+    let ty = ty.trim();
+    if let Some(signature) = ty.strip_prefix("extern \"C\" fn") {
+        let rest = signature.trim().strip_prefix('(')
+            .ok_or_else(|| anyhow::anyhow!("Некоректний тип функції: {}", ty))?;
+        return declare_function(&format!("(*{})", name), rest);
+    }
+    let ty = c_type(ty)?;
+    Ok(if ty.ends_with('*') { format!("{}{}", ty, name) } else { format!("{} {}", ty, name) })
+}
+
+/// Відповідність Rust-типів з FFI-сигнатур типам C
+fn c_type(ty: &str) -> Result<String> {
+    let ty = ty.trim();
+    if let Some(inner) = ty.strip_prefix("*mut ") {
+        let inner = c_type(inner)?;
+        return Ok(if inner.ends_with('*') { format!("{}*", inner) } else { format!("{} *", inner) });
+    }
+    if let Some(inner) = ty.strip_prefix("*const ") {
+        // Незмінний покажчик на покажчик: `char *const *`
+        let inner = c_type(inner)?;
+        return Ok(if inner.ends_with('*') { format!("{}const *", inner) } else { format!("const {} *", inner) });
+    }
+    let c = match ty {
+        "i8" => "int8_t", "i16" => "int16_t", "i32" => "int32_t", "i64" => "int64_t",
+        "u8" => "uint8_t", "u16" => "uint16_t", "u32" => "uint32_t", "u64" => "uint64_t",
+        "usize" => "size_t", "isize" => "ptrdiff_t",
+        "f32" => "float", "f64" => "double",
+        "bool" => "bool",
+        "c_char" => "char", "c_int" => "int", "c_void" => "void",
+        _ if ty.starts_with(|c: char| c.is_ascii_uppercase()) && ty.chars().all(|c| c.is_ascii_alphanumeric()) => ty,
+        _ => return Err(anyhow::anyhow!("Тип '{}' не має відповідника в C", ty)),
+    };
+    Ok(c.to_string())
+}
+
+/// Позиція `)`, що закриває вже відкриту дужку
+fn matching_paren(text: &str) -> Result<usize> {
+    let mut depth = 0;
+    for (i, c) in text.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => return Ok(i),
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+    Err(anyhow::anyhow!("Незакрита дужка: {}", text))
+}
+
+/// Параметри через кому, не розриваючи вкладені `extern "C" fn(a, b)`
+fn split_top_level(params: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let (mut depth, mut start) = (0, 0);
+    for (i, c) in params.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&params[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&params[start..]);
+    parts.into_iter().map(str::trim).filter(|p| !p.is_empty()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_prototypes() {
+        let header = c_header().unwrap();
+        for expected in [
+            "TryzubValue *tryzub_create_integer(int64_t value);",
+            "TryzubValue *tryzub_create_string(const char *s);",
+            "char *tryzub_string_concat(const char *a, const char *b);",
+            "void tryzub_string_free(char *s);",
+            "void *tryzub_allocate_aligned(size_t size, size_t align);",
+            "int tryzub_spawn_async(TryzubValue *(*callback)(void));",
+            "int tryzub_runtime_init(void);",
+            "    ValueType_Integer,",
+            "struct TryzubArray {\n    size_t length;\n    size_t capacity;\n    TryzubValue *elements;\n};",
+        ] {
+            assert!(header.contains(expected), "немає {:?} у заголовку:\n{}", expected, header);
+        }
+        // Union визначено раніше структури, що містить його за значенням
+        assert!(header.find("union ValueData {").unwrap() < header.find("struct TryzubValue {").unwrap());
+        assert!(header.contains("    double float_;"));
+        assert!(!header.contains("# Safety"));
+    }
+
+    #[test]
+    fn test_rejects_unmapped_types() {
+        assert!(prototype("f(x: Vec<u8>)").is_err());
+        assert_eq!(prototype("f(x: *const *mut c_char) -> bool").unwrap(), "bool f(char *const *x)");
+    }
+}
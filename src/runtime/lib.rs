@@ -2,9 +2,8 @@ use anyhow::Result;
 use crossbeam_channel::{bounded, Receiver, Sender};
 use dashmap::DashMap;
 use once_cell::sync::Lazy;
-use parking_lot::{Mutex, RwLock};
+use parking_lot::Mutex;
 use std::alloc::{alloc, dealloc, Layout};
-use std::collections::HashMap;
 use std::ffi::{CStr, CString};
 use std::os::raw::{c_char, c_int, c_void};
 use std::ptr;
@@ -12,6 +11,8 @@ use std::sync::Arc;
 use std::thread;
 use thiserror::Error;
 
+pub mod header;
+
 // ===== Send/Sync wrapper for raw pointers =====
 
 /// A wrapper around `*mut u8` that is `Send` and `Sync`.
@@ -23,12 +24,6 @@ struct SendPtr(*mut u8);
 unsafe impl Send for SendPtr {}
 unsafe impl Sync for SendPtr {}
 
-impl SendPtr {
-    fn as_ptr(self) -> *mut u8 {
-        self.0
-    }
-}
-
 // ===== Система управління пам'яттю =====
 
 #[derive(Debug)]
@@ -42,6 +37,8 @@ pub struct MemoryManager {
 struct AllocationInfo {
     size: usize,
     layout: Layout,
+    /// Читається лише Debug-виводом менеджера
+    #[allow(dead_code)]
     source_location: Option<String>,
 }
 
@@ -54,7 +51,12 @@ impl MemoryManager {
         }
     }
 
+    /// # Safety
+    /// Повернена пам'ять не ініціалізована; її звільняють лише через `deallocate`.
     pub unsafe fn allocate(&self, size: usize, align: usize, location: Option<String>) -> Result<*mut u8> {
+        if !align.is_power_of_two() {
+            return Err(MemoryError::InvalidAlignment { align }.into());
+        }
         let layout = Layout::from_size_align(size, align)
             .map_err(|e| anyhow::anyhow!("Invalid layout: {}", e))?;
 
@@ -74,6 +76,8 @@ impl MemoryManager {
         Ok(ptr)
     }
 
+    /// # Safety
+    /// `ptr` більше не використовується після звільнення.
     pub unsafe fn deallocate(&self, ptr: *mut u8) -> Result<()> {
         if let Some((_, info)) = self.allocations.remove(&SendPtr(ptr)) {
             dealloc(ptr, info.layout);
@@ -110,6 +114,9 @@ pub enum MemoryError {
 
     #[error("Невалідний покажчик: 0x{ptr:x}")]
     InvalidPointer { ptr: usize },
+
+    #[error("Вирівнювання {align} не є степенем двійки")]
+    InvalidAlignment { align: usize },
 }
 
 // Глобальний менеджер пам'яті
@@ -121,13 +128,13 @@ static MEMORY_MANAGER: Lazy<MemoryManager> = Lazy::new(|| {
 
 pub struct ThreadPool {
     workers: Vec<Worker>,
-    sender: Sender<Job>,
+    /// `None` після `shutdown` — закритий канал завершує цикли робітників
+    sender: Option<Sender<Job>>,
 }
 
 type Job = Box<dyn FnOnce() + Send + 'static>;
 
 struct Worker {
-    id: usize,
     thread: Option<thread::JoinHandle<()>>,
 }
 
@@ -142,7 +149,7 @@ impl ThreadPool {
             workers.push(Worker::new(id, Arc::clone(&receiver)));
         }
 
-        ThreadPool { workers, sender }
+        ThreadPool { workers, sender: Some(sender) }
     }
 
     pub fn execute<F>(&self, f: F) -> Result<()>
@@ -150,25 +157,38 @@ impl ThreadPool {
         F: FnOnce() + Send + 'static,
     {
         let job = Box::new(f);
-        self.sender.send(job)
+        let sender = self.sender.as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Thread pool is shut down"))?;
+        sender.send(job)
             .map_err(|e| anyhow::anyhow!("Failed to send job to thread pool: {}", e))?;
         Ok(())
     }
 
+    /// Дочікується поставлених задач і зупиняє робітників
     pub fn shutdown(&mut self) {
-        drop(self.sender.clone());
+        drop(self.sender.take());
 
+        let current = thread::current().id();
         for worker in &mut self.workers {
             if let Some(thread) = worker.thread.take() {
-                thread.join().unwrap();
+                // Пул може звільнитися з власного робітника — себе не чекаємо
+                if thread.thread().id() != current {
+                    let _ = thread.join();
+                }
             }
         }
     }
 }
 
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
 impl Worker {
     fn new(id: usize, receiver: Arc<Mutex<Receiver<Job>>>) -> Worker {
-        let thread = thread::spawn(move || loop {
+        let thread = thread::Builder::new().name(format!("tryzub-worker-{}", id)).spawn(move || loop {
             let job = receiver.lock().recv();
 
             match job {
@@ -179,10 +199,9 @@ impl Worker {
                     break;
                 }
             }
-        });
+        }).expect("не вдалося запустити робочий потік");
 
         Worker {
-            id,
             thread: Some(thread),
         }
     }
@@ -342,8 +361,10 @@ pub extern "C" fn tryzub_create_float(value: f64) -> *mut TryzubValue {
     Box::into_raw(val)
 }
 
+/// # Safety
+/// `s` — коректний C-рядок з нульовим завершенням.
 #[no_mangle]
-pub extern "C" fn tryzub_create_string(s: *const c_char) -> *mut TryzubValue {
+pub unsafe extern "C" fn tryzub_create_string(s: *const c_char) -> *mut TryzubValue {
     unsafe {
         let c_str = CStr::from_ptr(s);
         let string = CString::new(c_str.to_bytes()).unwrap();
@@ -355,28 +376,50 @@ pub extern "C" fn tryzub_create_string(s: *const c_char) -> *mut TryzubValue {
     }
 }
 
+/// Конкатенація рядків для скомпільованого коду. `NULL` вважається порожнім рядком.
+/// Результат належить викликачу і звільняється через `tryzub_string_free`.
+///
+/// # Safety
+/// `a` і `b` — `NULL` або коректні C-рядки з нульовим завершенням.
+#[no_mangle]
+pub unsafe extern "C" fn tryzub_string_concat(a: *const c_char, b: *const c_char) -> *mut c_char {
+    let bytes = |s: *const c_char| if s.is_null() { &[][..] } else { CStr::from_ptr(s).to_bytes() };
+    let mut result = Vec::with_capacity(bytes(a).len() + bytes(b).len());
+    result.extend_from_slice(bytes(a));
+    result.extend_from_slice(bytes(b));
+    // Обидві частини вже без нульових байтів, тож CString::new не може впасти
+    CString::new(result).unwrap().into_raw()
+}
+
+/// # Safety
+/// `s` — `NULL` або рядок, повернутий `tryzub_string_concat`, ще не звільнений.
 #[no_mangle]
-pub extern "C" fn tryzub_free_value(value: *mut TryzubValue) {
+pub unsafe extern "C" fn tryzub_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        let _ = CString::from_raw(s);
+    }
+}
+
+/// # Safety
+/// `value` — `NULL` або значення, створене функціями `tryzub_create_*`, ще не звільнене.
+#[no_mangle]
+pub unsafe extern "C" fn tryzub_free_value(value: *mut TryzubValue) {
     unsafe {
         if !value.is_null() {
             let val = Box::from_raw(value);
             match val.value_type {
-                ValueType::String => {
-                    if !val.data.string.is_null() {
-                        let _ = CString::from_raw(val.data.string);
-                    }
+                ValueType::String if !val.data.string.is_null() => {
+                    let _ = CString::from_raw(val.data.string);
                 }
-                ValueType::Array => {
-                    if !val.data.array.is_null() {
-                        let array = Box::from_raw(val.data.array);
-                        // Звільняємо елементи масиву
-                        for i in 0..array.length {
-                            let elem = array.elements.add(i);
-                            tryzub_free_value(elem);
-                        }
-                        dealloc(array.elements as *mut u8,
-                               Layout::array::<TryzubValue>(array.capacity).unwrap());
+                ValueType::Array if !val.data.array.is_null() => {
+                    let array = Box::from_raw(val.data.array);
+                    // Звільняємо елементи масиву
+                    for i in 0..array.length {
+                        let elem = array.elements.add(i);
+                        tryzub_free_value(elem);
                     }
+                    dealloc(array.elements as *mut u8,
+                           Layout::array::<TryzubValue>(array.capacity).unwrap());
                 }
                 _ => {}
             }
@@ -388,7 +431,8 @@ pub extern "C" fn tryzub_free_value(value: *mut TryzubValue) {
 
 pub struct AsyncRuntime {
     thread_pool: ThreadPool,
-    tasks: DashMap<usize, TaskState>,
+    /// Спільна з робочими потоками таблиця станів (клон `DashMap` копіював би дані)
+    tasks: Arc<DashMap<usize, TaskState>>,
     next_task_id: Arc<Mutex<usize>>,
 }
 
@@ -399,11 +443,19 @@ enum TaskState {
     Failed(TryzubError),
 }
 
+/// Стан задачі для моніторингу (без результату)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskStatus {
+    Running,
+    Completed,
+    Failed,
+}
+
 impl AsyncRuntime {
     pub fn new(num_threads: usize) -> Self {
         Self {
             thread_pool: ThreadPool::new(num_threads),
-            tasks: DashMap::new(),
+            tasks: Arc::new(DashMap::new()),
             next_task_id: Arc::new(Mutex::new(0)),
         }
     }
@@ -438,6 +490,25 @@ impl AsyncRuntime {
         Ok(task_id)
     }
 
+    /// Ідентифікатори задач, що ще виконуються, у порядку зростання —
+    /// обхід `DashMap` не детермінований, а для діагностики зависань потрібен стабільний вивід
+    pub fn active_tasks(&self) -> Vec<usize> {
+        let mut ids: Vec<usize> = self.tasks.iter()
+            .filter(|entry| matches!(entry.value(), TaskState::Running))
+            .map(|entry| *entry.key())
+            .collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    pub fn task_status(&self, task_id: usize) -> Option<TaskStatus> {
+        self.tasks.get(&task_id).map(|state| match state.value() {
+            TaskState::Running => TaskStatus::Running,
+            TaskState::Completed(_) => TaskStatus::Completed,
+            TaskState::Failed(_) => TaskStatus::Failed,
+        })
+    }
+
     pub fn await_task(&self, task_id: usize) -> Result<TryzubValue> {
         loop {
             if let Some(state) = self.tasks.get(&task_id) {
@@ -466,6 +537,14 @@ impl AsyncRuntime {
             }
         }
     }
+
+    /// Чекає на задачу й прибирає її з таблиці: дескриптор одноразовий, тож
+    /// результат більше нікому не знадобиться
+    pub fn take_task(&self, task_id: usize) -> Result<TryzubValue> {
+        let result = self.await_task(task_id);
+        self.tasks.remove(&task_id);
+        result
+    }
 }
 
 // ===== Глобальні функції runtime =====
@@ -484,6 +563,17 @@ pub extern "C" fn tryzub_allocate(size: usize) -> *mut c_void {
     }
 }
 
+/// Виділяє пам'ять з вирівнюванням `align`; `NULL`, якщо `align` не степінь двійки
+#[no_mangle]
+pub extern "C" fn tryzub_allocate_aligned(size: usize, align: usize) -> *mut c_void {
+    unsafe {
+        match MEMORY_MANAGER.allocate(size, align, None) {
+            Ok(ptr) => ptr as *mut c_void,
+            Err(_) => ptr::null_mut(),
+        }
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn tryzub_deallocate(ptr: *mut c_void) {
     unsafe {
@@ -491,8 +581,10 @@ pub extern "C" fn tryzub_deallocate(ptr: *mut c_void) {
     }
 }
 
+/// # Safety
+/// Кожен покажчик — `NULL` або коректна адреса `usize` для запису.
 #[no_mangle]
-pub extern "C" fn tryzub_get_memory_stats(total_allocated: *mut usize, allocation_count: *mut usize) {
+pub unsafe extern "C" fn tryzub_get_memory_stats(total_allocated: *mut usize, allocation_count: *mut usize) {
     let stats = MEMORY_MANAGER.get_stats();
     unsafe {
         if !total_allocated.is_null() {
@@ -567,6 +659,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_aligned_allocation() {
+        for align in [16, 64] {
+            let ptr = tryzub_allocate_aligned(48, align);
+            assert!(!ptr.is_null());
+            assert_eq!(ptr as usize % align, 0);
+            tryzub_deallocate(ptr);
+        }
+
+        assert!(tryzub_allocate_aligned(48, 3).is_null());
+        assert!(tryzub_allocate_aligned(48, 0).is_null());
+        let err = unsafe { MEMORY_MANAGER.allocate(48, 3, None) }.unwrap_err();
+        assert!(matches!(err.downcast_ref::<MemoryError>(), Some(MemoryError::InvalidAlignment { align: 3 })));
+    }
+
+    #[test]
+    fn test_string_concat() {
+        let a = CString::new("Три").unwrap();
+        let b = CString::new("зуб").unwrap();
+        unsafe {
+            let joined = tryzub_string_concat(a.as_ptr(), b.as_ptr());
+            assert_eq!(CStr::from_ptr(joined).to_str().unwrap(), "Тризуб");
+            tryzub_string_free(joined);
+
+            let only_b = tryzub_string_concat(ptr::null(), b.as_ptr());
+            assert_eq!(CStr::from_ptr(only_b).to_str().unwrap(), "зуб");
+            tryzub_string_free(only_b);
+        }
+    }
+
     #[test]
     fn test_thread_pool() {
         let pool = ThreadPool::new(4);
@@ -587,4 +709,69 @@ mod tests {
         results.sort();
         assert_eq!(results, vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
     }
+
+    #[test]
+    fn test_thread_pool_shutdown() {
+        let mut pool = ThreadPool::new(2);
+        let done = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        for _ in 0..5 {
+            let done = done.clone();
+            pool.execute(move || {
+                done.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            }).unwrap();
+        }
+        pool.shutdown();
+        assert_eq!(done.load(std::sync::atomic::Ordering::SeqCst), 5);
+        assert!(pool.execute(|| {}).is_err());
+    }
+
+    #[test]
+    fn test_async_task_result() {
+        let runtime = AsyncRuntime::new(2);
+        let ok = runtime.spawn_task(|| Ok(TryzubValue {
+            value_type: ValueType::Integer,
+            data: ValueData { integer: 42 },
+        })).unwrap();
+        let failed = runtime.spawn_task(|| Err(anyhow::anyhow!("зламано"))).unwrap();
+
+        let value = runtime.await_task(ok).unwrap();
+        assert!(matches!(value.value_type, ValueType::Integer));
+        assert_eq!(unsafe { value.data.integer }, 42);
+        let err = runtime.await_task(failed).unwrap_err();
+        assert!(err.to_string().contains("зламано"));
+
+        assert!(runtime.take_task(ok).is_ok());
+        assert_eq!(runtime.task_status(ok), None);
+        assert!(runtime.take_task(failed).is_err());
+        assert_eq!(runtime.task_status(failed), None);
+    }
+
+    #[test]
+    fn test_active_tasks_sorted() {
+        // Потоків більше, ніж заблокованих задач, щоб задача з помилкою теж виконалась
+        let runtime = AsyncRuntime::new(6);
+        let (release_tx, release_rx) = crossbeam_channel::unbounded::<()>();
+        let mut ids = Vec::new();
+        for _ in 0..4 {
+            let release = release_rx.clone();
+            ids.push(runtime.spawn_task(move || {
+                let _ = release.recv();
+                Ok(TryzubValue { value_type: ValueType::Null, data: ValueData { null: () } })
+            }).unwrap());
+        }
+        let failed = runtime.spawn_task(|| Err(anyhow::anyhow!("зламано"))).unwrap();
+        let _ = runtime.await_task(failed);
+
+        assert_eq!(runtime.active_tasks(), ids);
+        assert_eq!(runtime.task_status(ids[0]), Some(TaskStatus::Running));
+        assert_eq!(runtime.task_status(failed), Some(TaskStatus::Failed));
+        assert_eq!(runtime.task_status(999), None);
+
+        drop(release_tx);
+        for &id in &ids {
+            runtime.await_task(id).unwrap();
+            assert_eq!(runtime.task_status(id), Some(TaskStatus::Completed));
+        }
+        assert!(runtime.active_tasks().is_empty());
+    }
 }
@@ -128,7 +128,7 @@ impl Compiler {
                 }
                 self.scope_depth -= 1;
             }
-            Statement::If { condition, then_branch, else_branch } => {
+            Statement::If { condition, then_branch, else_branch, .. } => {
                 self.compile_expression(condition);
                 let jump_false = self.chunk.emit(Op::JumpIfFalse, 0);
 
@@ -144,7 +144,7 @@ impl Compiler {
                 let end_target = self.chunk.code.len() as u32;
                 self.chunk.patch_jump(jump_end, end_target);
             }
-            Statement::While { condition, body } => {
+            Statement::While { condition, body, .. } => {
                 let loop_start = self.chunk.code.len();
 
                 self.compile_expression(condition);
@@ -158,7 +158,7 @@ impl Compiler {
                 let exit_target = self.chunk.code.len() as u32;
                 self.chunk.patch_jump(exit_jump, exit_target);
             }
-            Statement::For { variable, from, to, step: _, body } => {
+            Statement::For { variable, from, to, step: _, body, .. } => {
                 self.compile_expression(from);
                 let i_slot = self.add_local(variable.clone());
                 self.chunk.emit(Op::StoreLocal, i_slot as u32);
@@ -180,7 +180,7 @@ impl Compiler {
                 let exit_target = self.chunk.code.len() as u32;
                 self.chunk.patch_jump(exit_jump, exit_target);
             }
-            Statement::Assignment { target, value, op } => {
+            Statement::Assignment { target, value, op, .. } => {
                 match op {
                     AssignmentOp::Assign => {
                         self.compile_expression(value);
@@ -246,7 +246,7 @@ impl Compiler {
                     self.chunk.emit(Op::Const, c);
                 }
             }
-            Expression::Binary { left, op, right } => {
+            Expression::Binary { left, op, right, .. } => {
                 self.compile_expression(left);
                 self.compile_expression(right);
                 match op {
@@ -280,7 +280,7 @@ impl Compiler {
                     UnaryOp::BitNot => self.chunk.emit(Op::BitNot, 0),
                 };
             }
-            Expression::Call { callee, args } => {
+            Expression::Call { callee, args, .. } => {
                 if let Expression::Identifier(name) = callee.as_ref() {
                     if name == "друк" && args.len() == 1 {
                         self.compile_expression(&args[0]);
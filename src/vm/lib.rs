@@ -13,6 +13,7 @@ use std::collections::HashSet;
 use std::rc::Rc;
 use std::cell::RefCell;
 use std::sync::{Arc, Mutex};
+use std::cell::{Cell, OnceCell};
 use hmac::{Hmac, Mac};
 use sha2::Sha256;
 use sha2::Digest as Sha2Digest;
@@ -104,6 +105,78 @@ use tryzub_parser::{
 // Значення (Value) — всі можливі типи даних у VM
 // ════════════════════════════════════════════════════════════════════
 
+/// Дані з копіюванням при записі: `clone` лише збільшує лічильник посилань,
+/// а перша зміна через `DerefMut` копіює дані, якщо ними ще хтось володіє.
+/// Так передача великого масиву в функцію не копіює його, а семантика
+/// значень зберігається — зміна копії ніколи не видна в оригіналі.
+#[derive(Clone, Default, PartialEq)]
+pub struct Shared<T>(Rc<T>);
+
+impl<T> Shared<T> {
+    pub fn new(value: T) -> Self {
+        Shared(Rc::new(value))
+    }
+
+    /// Чи ділять дві копії один буфер (ще не було запису)
+    pub fn ptr_eq(a: &Self, b: &Self) -> bool {
+        Rc::ptr_eq(&a.0, &b.0)
+    }
+}
+
+impl<T: Clone> Shared<T> {
+    /// Забирає дані: без копії, якщо це останнє посилання
+    pub fn into_inner(self) -> T {
+        Rc::try_unwrap(self.0).unwrap_or_else(|rc| (*rc).clone())
+    }
+}
+
+impl<T> std::ops::Deref for Shared<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: Clone> std::ops::DerefMut for Shared<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        Rc::make_mut(&mut self.0)
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for Shared<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T> From<T> for Shared<T> {
+    fn from(value: T) -> Self {
+        Shared::new(value)
+    }
+}
+
+impl<A, T: FromIterator<A>> FromIterator<A> for Shared<T> {
+    fn from_iter<I: IntoIterator<Item = A>>(iter: I) -> Self {
+        Shared::new(iter.into_iter().collect())
+    }
+}
+
+impl<T: Clone + IntoIterator> IntoIterator for Shared<T> {
+    type Item = T::Item;
+    type IntoIter = T::IntoIter;
+    fn into_iter(self) -> T::IntoIter {
+        self.into_inner().into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Shared<T> where &'a T: IntoIterator {
+    type Item = <&'a T as IntoIterator>::Item;
+    type IntoIter = <&'a T as IntoIterator>::IntoIter;
+    fn into_iter(self) -> Self::IntoIter {
+        (&*self.0).into_iter()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum Value {
     Integer(i64),
@@ -111,9 +184,10 @@ pub enum Value {
     String(String),
     Char(char),
     Bool(bool),
-    Array(Vec<Value>),
+    /// Елементи спільні між копіями масиву, див. `Shared`
+    Array(Shared<Vec<Value>>),
     Tuple(Vec<Value>),
-    Struct(String, HashMap<String, Value>), // (назва_типу, поля)
+    Struct(String, Shared<HashMap<String, Value>>), // (назва_типу, поля)
     /// Варіант алгебраїчного типу: Деякий(42), Помилка("ой")
     EnumVariant {
         type_name: String,
@@ -127,6 +201,8 @@ pub enum Value {
         return_type: Option<tryzub_parser::Type>,
         body: Vec<Statement>,
         closure: Environment,
+        /// Виклик `асинхронний функція` запускає задачу замість виконання
+        is_async: bool,
     },
     /// Лямбда-функція
     Lambda {
@@ -165,6 +241,10 @@ pub enum Value {
     },
     /// Модуль (namespace)
     Module(String, HashMap<String, Value>),
+    /// Дескриптор запущеної асинхронної задачі, який приймає `чекати`
+    Task(usize),
+    /// Відправник або отримувач каналу
+    Channel(Arc<ChannelEnd>),
     Null,
 }
 
@@ -175,6 +255,23 @@ pub enum LambdaBody {
 }
 
 impl Value {
+    /// Приблизний розмір значення в байтах для `//@ліміт_памʼяті`; оточення
+    /// замикань не враховуються, спільні буфери рахуються за кожним посиланням
+    fn approx_size(&self) -> usize {
+        let nested = match self {
+            Value::String(s) => s.len(),
+            Value::Array(items) => items.iter().map(Value::approx_size).sum(),
+            Value::Tuple(items) | Value::Set(items) => items.iter().map(Value::approx_size).sum(),
+            Value::EnumVariant { fields, .. } => fields.iter().map(Value::approx_size).sum(),
+            Value::Struct(name, fields) => name.len()
+                + fields.iter().map(|(k, v)| k.len() + v.approx_size()).sum::<usize>(),
+            Value::Dict(pairs) => pairs.iter().map(|(k, v)| k.approx_size() + v.approx_size()).sum(),
+            Value::Module(_, members) => members.values().map(Value::approx_size).sum(),
+            _ => 0,
+        };
+        std::mem::size_of::<Value>() + nested
+    }
+
     fn to_bool(&self) -> bool {
         match self {
             Value::Bool(b) => *b,
@@ -202,16 +299,18 @@ impl Value {
             Value::Char(c) => c.to_string(),
             Value::Bool(b) => if *b { "істина" } else { "хиба" }.to_string(),
             Value::Array(arr) => {
-                let elements: Vec<String> = arr.iter().map(|v| v.to_display_string()).collect();
+                let elements: Vec<String> = arr.iter().map(|v| v.nested()).collect();
                 format!("[{}]", elements.join(", "))
             }
             Value::Tuple(elems) => {
-                let parts: Vec<String> = elems.iter().map(|v| v.to_display_string()).collect();
+                let parts: Vec<String> = elems.iter().map(|v| v.nested()).collect();
                 format!("({})", parts.join(", "))
             }
             Value::Struct(name, fields) => {
-                let parts: Vec<String> = fields.iter()
-                    .map(|(k, v)| format!("{}: {}", k, v.to_display_string()))
+                let mut names: Vec<&String> = fields.keys().collect();
+                names.sort();
+                let parts: Vec<String> = names.into_iter()
+                    .map(|k| format!("{}: {}", k, fields[k].nested()))
                     .collect();
                 format!("{} {{ {} }}", name, parts.join(", "))
             }
@@ -219,7 +318,7 @@ impl Value {
                 if fields.is_empty() {
                     variant.clone()
                 } else {
-                    let parts: Vec<String> = fields.iter().map(|v| v.to_display_string()).collect();
+                    let parts: Vec<String> = fields.iter().map(|v| v.nested()).collect();
                     format!("{}({})", variant, parts.join(", "))
                 }
             }
@@ -232,12 +331,12 @@ impl Value {
             }
             Value::Dict(pairs) => {
                 let parts: Vec<String> = pairs.iter()
-                    .map(|(k, v)| format!("{} -> {}", k.to_display_string(), v.to_display_string()))
+                    .map(|(k, v)| format!("{} -> {}", k.nested(), v.nested()))
                     .collect();
                 format!("#{{{}}}", parts.join(", "))
             }
             Value::Set(items) => {
-                let parts: Vec<String> = items.iter().map(|v| v.to_display_string()).collect();
+                let parts: Vec<String> = items.iter().map(|v| v.nested()).collect();
                 format!("%{{{}}}", parts.join(", "))
             }
             Value::Null => "нуль".to_string(),
@@ -247,6 +346,20 @@ impl Value {
             Value::CurriedBuiltin { name, .. } => format!("<каррінг {}>", name),
             Value::Generator { .. } => "<генератор>".to_string(),
             Value::Module(name, _) => format!("<модуль {}>", name),
+            Value::Task(id) => format!("<задача {}>", id),
+            Value::Channel(end) => match **end {
+                ChannelEnd::Sender { id, .. } => format!("<відправник каналу {}>", id),
+                ChannelEnd::Receiver { id, .. } => format!("<отримувач каналу {}>", id),
+            },
+        }
+    }
+
+    /// Представлення значення всередині масиву чи структури: рядки беруться
+    /// в лапки, щоб `"5"` відрізнялося від `5`
+    fn nested(&self) -> String {
+        match self {
+            Value::String(s) => format!("{:?}", s),
+            other => other.to_display_string(),
         }
     }
 
@@ -264,30 +377,217 @@ impl Value {
             Value::Struct(name, _) => name,
             Value::EnumVariant { type_name, .. } => type_name,
             Value::Module(..) => "модуль",
+            Value::Task(_) => "задача",
+            Value::Channel(_) => "канал",
             Value::Null => "нуль",
             _ => "функція",
         }
     }
+
+    /// Загальна категорія значення для `тип(x)`
+    fn kind_name(&self) -> &'static str {
+        match self {
+            Value::Integer(_) => "ціле",
+            Value::Float(_) => "дробове",
+            Value::String(_) => "рядок",
+            Value::Char(_) => "символ",
+            Value::Bool(_) => "логічне",
+            Value::Array(_) => "масив",
+            Value::Tuple(_) => "кортеж",
+            Value::Dict(_) => "словник",
+            Value::Set(_) => "множина",
+            Value::Struct(..) => "структура",
+            Value::EnumVariant { .. } => "перелік",
+            Value::Range { .. } => "діапазон",
+            Value::Module(..) => "модуль",
+            Value::Task(_) => "задача",
+            Value::Channel(_) => "канал",
+            Value::Generator { .. } => "генератор",
+            Value::Null => "нуль",
+            Value::Function { .. } | Value::Lambda { .. }
+            | Value::BuiltinFn(_) | Value::CurriedBuiltin { .. } => "функція",
+        }
+    }
 }
 
+// ════════════════════════════════════════════════════════════════════
+// Передача значень між потоками
+// ════════════════════════════════════════════════════════════════════
+
+/// Знімок значення без `Rc`-середовищ, який можна передати в інший потік.
+/// Функції, генератори та модулі тримають замикання і не передаються.
+#[derive(Debug)]
+pub enum SendValue {
+    Integer(i64),
+    Float(f64),
+    String(String),
+    Char(char),
+    Bool(bool),
+    Array(Vec<SendValue>),
+    Tuple(Vec<SendValue>),
+    Struct(String, HashMap<String, SendValue>),
+    EnumVariant {
+        type_name: String,
+        variant: String,
+        fields: Vec<SendValue>,
+    },
+    Dict(Vec<(SendValue, SendValue)>),
+    Set(Vec<SendValue>),
+    Range {
+        from: i64,
+        to: i64,
+        inclusive: bool,
+    },
+    Channel(Arc<ChannelEnd>),
+    Null,
+}
+
+impl SendValue {
+    /// `None`, якщо значення (чи будь-що всередині) не можна передати між потоками
+    fn from_value(value: &Value) -> Option<Self> {
+        let list = |items: &[Value]| items.iter().map(Self::from_value).collect::<Option<Vec<_>>>();
+        Some(match value {
+            Value::Integer(n) => SendValue::Integer(*n),
+            Value::Float(f) => SendValue::Float(*f),
+            Value::String(s) => SendValue::String(s.clone()),
+            Value::Char(c) => SendValue::Char(*c),
+            Value::Bool(b) => SendValue::Bool(*b),
+            Value::Array(items) => SendValue::Array(list(items)?),
+            Value::Tuple(items) => SendValue::Tuple(list(items)?),
+            Value::Set(items) => SendValue::Set(list(items)?),
+            Value::Struct(name, fields) => SendValue::Struct(
+                name.clone(),
+                fields.iter()
+                    .map(|(k, v)| Self::from_value(v).map(|v| (k.clone(), v)))
+                    .collect::<Option<_>>()?,
+            ),
+            Value::EnumVariant { type_name, variant, fields } => SendValue::EnumVariant {
+                type_name: type_name.clone(),
+                variant: variant.clone(),
+                fields: list(fields)?,
+            },
+            Value::Dict(pairs) => SendValue::Dict(
+                pairs.iter()
+                    .map(|(k, v)| Some((Self::from_value(k)?, Self::from_value(v)?)))
+                    .collect::<Option<_>>()?,
+            ),
+            Value::Range { from, to, inclusive } => SendValue::Range { from: *from, to: *to, inclusive: *inclusive },
+            Value::Channel(end) => SendValue::Channel(end.clone()),
+            Value::Null => SendValue::Null,
+            Value::Function { .. } | Value::Lambda { .. } | Value::BuiltinFn(_)
+            | Value::CurriedBuiltin { .. } | Value::Generator { .. } | Value::Module(..) => return None,
+            // Результат задачі чекає лише VM, що її запустив
+            Value::Task(_) => return None,
+        })
+    }
+
+    fn into_value(self) -> Value {
+        let list = |items: Vec<SendValue>| items.into_iter().map(Self::into_value).collect::<Vec<_>>();
+        match self {
+            SendValue::Integer(n) => Value::Integer(n),
+            SendValue::Float(f) => Value::Float(f),
+            SendValue::String(s) => Value::String(s),
+            SendValue::Char(c) => Value::Char(c),
+            SendValue::Bool(b) => Value::Bool(b),
+            SendValue::Array(items) => Value::Array(list(items).into()),
+            SendValue::Tuple(items) => Value::Tuple(list(items)),
+            SendValue::Set(items) => Value::Set(list(items)),
+            SendValue::Struct(name, fields) => Value::Struct(
+                name,
+                fields.into_iter().map(|(k, v)| (k, v.into_value())).collect(),
+            ),
+            SendValue::EnumVariant { type_name, variant, fields } => Value::EnumVariant {
+                type_name,
+                variant,
+                fields: list(fields),
+            },
+            SendValue::Dict(pairs) => Value::Dict(
+                pairs.into_iter().map(|(k, v)| (k.into_value(), v.into_value())).collect(),
+            ),
+            SendValue::Range { from, to, inclusive } => Value::Range { from, to, inclusive },
+            SendValue::Channel(end) => Value::Channel(end),
+            SendValue::Null => Value::Null,
+        }
+    }
+}
+
+/// Функція для іншого потоку: її визначення без замикання, аргументи та знімок
+/// змінних замикання. Робітник відтворює функцію й викликає її через `call_value`,
+/// тож `?`, перевірки типів і контракти працюють так само, як у потоці програми
+struct SendTask {
+    func: SendFunction,
+    args: Vec<SendValue>,
+    captured: Vec<(String, SendValue)>,
+}
+
+enum SendFunction {
+    Function {
+        name: Option<String>,
+        generic_params: Vec<String>,
+        params: Vec<Parameter>,
+        return_type: Option<tryzub_parser::Type>,
+        body: Vec<Statement>,
+        is_async: bool,
+    },
+    Lambda {
+        params: Vec<LambdaParam>,
+        body: LambdaBody,
+    },
+}
+
+thread_local! {
+    /// Чи виконується поточний потік як робітник пулу
+    static IN_POOL_WORKER: Cell<bool> = const { Cell::new(false) };
+    /// VM робітника з уже виконаними оголошеннями програми; перебудовується,
+    /// лише коли задача приходить від іншої програми
+    static WORKER_VM: RefCell<Option<VM>> = const { RefCell::new(None) };
+}
+
+/// Кінець каналу між потоками. Копії дескриптора, зокрема в інших потоках,
+/// ділять один `Arc`, тож канал звільняється разом з останньою копією обох
+/// кінців. Закриття забирає відправника, і отримувач від'єднується, щойно
+/// дочитає буфер.
+#[derive(Debug)]
+pub enum ChannelEnd {
+    Sender {
+        id: usize,
+        sender: Mutex<Option<crossbeam_channel::Sender<SendValue>>>,
+    },
+    Receiver {
+        id: usize,
+        receiver: crossbeam_channel::Receiver<SendValue>,
+    },
+}
+
+static NEXT_CHANNEL_ID: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+/// Комірка, куди робочий потік кладе результат задачі
+type TaskSlot = Arc<Mutex<Option<SendValue>>>;
+
 // ════════════════════════════════════════════════════════════════════
 // Середовище виконання (Scope)
 // ════════════════════════════════════════════════════════════════════
 
 type Environment = Rc<RefCell<Scope>>;
 
+/// Змінні мають семантику значень: масиви, кортежі, словники та структури
+/// зберігаються без спільних посилань, тож `б = а` дає незалежну глибоку копію,
+/// і зміна `б[0]` чи `б.поле` не впливає на `а`.
 #[derive(Debug, Clone)]
 pub struct Scope {
     variables: HashMap<String, Value>,
     parent: Option<Environment>,
     inferred_types: HashMap<String, String>,
+    /// Імена, оголошені через `стала` саме в цій області
+    constants: HashSet<String>,
 }
 
 impl Scope {
     fn new(parent: Option<Environment>) -> Self {
-        Self { variables: HashMap::new(), parent, inferred_types: HashMap::new() }
+        Self { variables: HashMap::new(), parent, inferred_types: HashMap::new(), constants: HashSet::new() }
     }
 
+    /// Повертає копію значення; мутації копії не потрапляють назад у змінну
     fn get(&self, name: &str) -> Option<Value> {
         if let Some(value) = self.variables.get(name) {
             Some(value.clone())
@@ -298,6 +598,7 @@ impl Scope {
         }
     }
 
+    /// Оголошує змінну, забираючи значення у власність (копію, отриману з `get`)
     fn set(&mut self, name: String, value: Value) {
         if !self.inferred_types.contains_key(&name) {
             let type_name = value.type_name().to_string();
@@ -305,11 +606,22 @@ impl Scope {
                 self.inferred_types.insert(name.clone(), type_name);
             }
         }
+        self.constants.remove(&name);
         self.variables.insert(name, value);
     }
 
+    /// Оголошує сталу; повторне оголошення через `set` знову робить ім'я змінним
+    fn set_constant(&mut self, name: String, value: Value) {
+        self.set(name.clone(), value);
+        self.constants.insert(name);
+    }
+
+    /// Замінює значення змінної цілком; попереднє значення інших змінних не зачіпається
     fn update(&mut self, name: &str, value: Value) -> Result<()> {
         if self.variables.contains_key(name) {
+            if self.constants.contains(name) {
+                return Err(anyhow::anyhow!("Не можна присвоїти сталій '{}'", name));
+            }
             if let Some(expected_type) = self.inferred_types.get(name) {
                 let actual_type = value.type_name().to_string();
                 if &actual_type != expected_type && actual_type != "нуль" {
@@ -328,8 +640,11 @@ impl Scope {
         }
     }
 
+    /// Імена всіх областей ланцюга: спершу найближча, в межах області — за алфавітом,
+    /// щоб підказка при рівній відстані не залежала від порядку HashMap
     fn all_names(&self) -> Vec<String> {
         let mut names: Vec<String> = self.variables.keys().cloned().collect();
+        names.sort();
         if let Some(parent) = &self.parent {
             names.extend(parent.borrow().all_names());
         }
@@ -365,6 +680,8 @@ pub struct VM {
     yielded_values: Vec<Value>,
     /// Черга async завдань
     async_queue: Vec<(Vec<Statement>, Environment)>,
+    /// Оголошення програми — з них робочі потоки відтворюють глобальне середовище
+    program_decls: Arc<Vec<Declaration>>,
     /// Зареєстровані макроси: ім'я → (параметри, тіло)
     macros: HashMap<String, (Vec<String>, Vec<Statement>)>,
     /// Шляхи для пошуку stdlib модулів
@@ -389,6 +706,16 @@ pub struct VM {
     pure_cache: PureCache,
     /// Позначені як чисті функції
     pure_functions: HashSet<String>,
+    /// Запущені async-задачі: ід у AsyncRuntime → комірка для результату
+    async_tasks: HashMap<usize, TaskSlot>,
+    /// Пул потоків для async-функцій і паралельно(); створюється при першій задачі,
+    /// потоки зупиняються, коли звільняється останнє посилання. На робочому потоці
+    /// задачі виконуються одразу, тож VM робітників пулу не створюють
+    task_runtime: OnceCell<Arc<tryzub_runtime::AsyncRuntime>>,
+    /// Статичні методи типів: "Тип::метод"
+    static_methods: HashSet<String>,
+    /// Типи з блоками реалізації — лише для них `Тип.член` шукається серед статичних членів
+    impl_types: HashSet<String>,
     /// Лічильник операцій VM (для профілювання)
     op_count: u64,
     /// Лічильник для GC — запускати кожні N операцій
@@ -402,8 +729,41 @@ pub struct VM {
     allocations: HashMap<usize, std::alloc::Layout>,
     /// Call stack для stack traces
     call_stack: Vec<CallFrame>,
+    /// Середовища викликачів, що чекають на повернення з функцій і лямбд —
+    /// їхні змінні живі й враховуються лімітом пам'яті
+    frame_envs: Vec<Rc<RefCell<Scope>>>,
+    /// Чи змінював метод, що виконується, поля `себе`/`це`
+    self_mutated: bool,
+    /// Змінений екземпляр після виклику методу — записується назад у змінну викликача
+    method_self: Option<Value>,
+    /// Покриття виконання (None — вимкнено)
+    coverage: Option<Coverage>,
+    /// Ліміт часу, заданий через `VM::with_timeout`; директива може лише зменшити його
+    timeout: Option<std::time::Duration>,
+    /// Діючий ліміт часу: (початок виконання, тривалість)
+    time_limit: Option<(std::time::Instant, std::time::Duration)>,
+    /// Межа приблизного обсягу значень з директиви `//@ліміт_памʼяті`, у байтах
+    memory_limit: Option<usize>,
+    /// Значення `переривати значення`, що ще не дійшло до свого блоку-виразу
+    block_value: Option<Value>,
+    /// Трасування: кожен оператор і значення виразів друкуються в stderr
+    trace: bool,
+    /// Дії `відкласти` для кожної активної функції разом з оточенням, де їх оголошено
+    deferred: Vec<Vec<(Statement, Environment)>>,
 }
 
+/// Сигнал від `?`: помилку вже покладено в `return_value`, і виконання має
+/// вийти з усіх виразів та блоків до найближчої функції чи лямбди
+#[derive(Debug, thiserror::Error)]
+#[error("Оператор '?' поширив помилку за межі функції")]
+struct ErrorPropagated;
+
+/// Сигнал від `переривати значення`: значення покладено в `block_value`, і виконання
+/// розмотується до найближчого блоку-виразу; межу функції чи лямбди він не перетинає
+#[derive(Debug, thiserror::Error)]
+#[error("'переривати' зі значенням можливий лише всередині блоку-виразу")]
+struct BlockBreak;
+
 #[derive(Debug, Clone)]
 pub struct CallFrame {
     pub function_name: String,
@@ -411,6 +771,14 @@ pub struct CallFrame {
     pub line: usize,
 }
 
+/// Лічильники покриття: скільки разів викликалась кожна функція
+/// і виконувалась кожна інструкція з відомим рядком
+#[derive(Debug, Clone, Default)]
+pub struct Coverage {
+    pub functions: HashMap<String, u64>,
+    pub lines: std::collections::BTreeMap<usize, u64>,
+}
+
 // ════════════════════════════════════════════════════════════════════
 // Веб-сервер — реальний HTTP через std::net::TcpListener
 // ════════════════════════════════════════════════════════════════════
@@ -503,6 +871,35 @@ enum LoopOptResult {
     SetVariable(String, Value),
 }
 
+/// Вбудований модуль, решту членів якого дає stdlib/математика.тризуб
+const MATH_MODULE: &str = "математика";
+
+/// Нативні члени модуля `математика`; при завантаженні stdlib-модуля вони
+/// мають перевагу над його однойменними оголошеннями
+fn native_math_members() -> HashMap<String, Value> {
+    [
+        ("ПІ", Value::Float(std::f64::consts::PI)),
+        ("Е", Value::Float(std::f64::consts::E)),
+        ("ТАУ", Value::Float(std::f64::consts::TAU)),
+        ("НЕСКІНЧЕННІСТЬ", Value::Float(f64::INFINITY)),
+        ("НЕ_ЧИСЛО", Value::Float(f64::NAN)),
+        ("корінь", Value::BuiltinFn("корінь".to_string())),
+        ("логарифм", Value::BuiltinFn("логарифм".to_string())),
+        ("степінь", Value::BuiltinFn("степінь_ф".to_string())),
+        ("синус", Value::BuiltinFn("синус".to_string())),
+        ("косинус", Value::BuiltinFn("косинус".to_string())),
+    ].into_iter().map(|(name, value)| (name.to_string(), value)).collect()
+}
+
+/// Сума цілих з `from..to`; `None`, якщо діапазон порожній або сума не вміщається в i64
+fn range_sum(from: i64, to: i64) -> Option<i64> {
+    if to <= from {
+        return None;
+    }
+    let (from, to) = (from as i128, to as i128);
+    i64::try_from((to - from) * (from + to - 1) / 2).ok()
+}
+
 impl Drop for VM {
     fn drop(&mut self) {
         for (addr, layout) in self.allocations.drain() {
@@ -525,15 +922,25 @@ impl VM {
         {
             let mut scope = global_scope.borrow_mut();
             scope.set("друк".to_string(), Value::BuiltinFn("друк".to_string()));
+            scope.set("друкф".to_string(), Value::BuiltinFn("друкф".to_string()));
+            scope.set("форматувати".to_string(), Value::BuiltinFn("форматувати".to_string()));
             scope.set("цілеврядок".to_string(), Value::BuiltinFn("цілеврядок".to_string()));
+            scope.set("великі".to_string(), Value::BuiltinFn("великі".to_string()));
+            scope.set("малі".to_string(), Value::BuiltinFn("малі".to_string()));
             scope.set("довжина".to_string(), Value::BuiltinFn("довжина".to_string()));
             scope.set("тип_значення".to_string(), Value::BuiltinFn("тип_значення".to_string()));
+            scope.set("тип".to_string(), Value::BuiltinFn("тип".to_string()));
+            scope.set("влог".to_string(), Value::BuiltinFn("влог".to_string()));
+            scope.set("врядкувлог".to_string(), Value::BuiltinFn("врядкувлог".to_string()));
             scope.set("діапазон".to_string(), Value::BuiltinFn("діапазон".to_string()));
             scope.set("фільтрувати".to_string(), Value::BuiltinFn("фільтрувати".to_string()));
             scope.set("перетворити".to_string(), Value::BuiltinFn("перетворити".to_string()));
             scope.set("згорнути".to_string(), Value::BuiltinFn("згорнути".to_string()));
             scope.set("сортувати".to_string(), Value::BuiltinFn("сортувати".to_string()));
             scope.set("обернути".to_string(), Value::BuiltinFn("обернути".to_string()));
+            scope.set("повторити".to_string(), Value::BuiltinFn("повторити".to_string()));
+            scope.set("повторити_масив".to_string(), Value::BuiltinFn("повторити_масив".to_string()));
+            scope.set("з'єднати".to_string(), Value::BuiltinFn("з'єднати".to_string()));
             scope.set("додати".to_string(), Value::BuiltinFn("додати".to_string()));
             scope.set("паніка".to_string(), Value::BuiltinFn("паніка".to_string()));
             scope.set("перевірити_рівне".to_string(), Value::BuiltinFn("перевірити_рівне".to_string()));
@@ -552,12 +959,17 @@ impl VM {
             // Час
             scope.set("час_зараз".to_string(), Value::BuiltinFn("час_зараз".to_string()));
             scope.set("час_затримка".to_string(), Value::BuiltinFn("час_затримка".to_string()));
+            scope.set("спати".to_string(), Value::BuiltinFn("спати".to_string()));
 
             // Async / Concurrency
             scope.set("все".to_string(), Value::BuiltinFn("все".to_string()));
             scope.set("перегони".to_string(), Value::BuiltinFn("перегони".to_string()));
             scope.set("потік".to_string(), Value::BuiltinFn("потік".to_string()));
             scope.set("канал".to_string(), Value::BuiltinFn("канал".to_string()));
+            scope.set("паралельно".to_string(), Value::BuiltinFn("паралельно".to_string()));
+            scope.set("надіслати".to_string(), Value::BuiltinFn("надіслати".to_string()));
+            scope.set("отримати".to_string(), Value::BuiltinFn("отримати".to_string()));
+            scope.set("закрити_канал".to_string(), Value::BuiltinFn("закрити_канал".to_string()));
 
             // JSON
             scope.set("json_розібрати".to_string(), Value::BuiltinFn("json_розібрати".to_string()));
@@ -571,7 +983,12 @@ impl VM {
             scope.set("степінь_ф".to_string(), Value::BuiltinFn("степінь_ф".to_string()));
             scope.set("логарифм".to_string(), Value::BuiltinFn("логарифм".to_string()));
             scope.set("ПІ".to_string(), Value::Float(std::f64::consts::PI));
+            scope.set("найбільше_цл64".to_string(), Value::Integer(i64::MAX));
+            scope.set("найменше_цл64".to_string(), Value::Integer(i64::MIN));
             scope.set("Е".to_string(), Value::Float(std::f64::consts::E));
+            // Простір імен математика.ПІ, математика.корінь(х); користувацьке
+            // оголошення чи імпорт з тим самим ім'ям просто його затіняє
+            scope.set(MATH_MODULE.to_string(), Value::Module(MATH_MODULE.to_string(), native_math_members()));
             scope.set("ціле_з_рядка".to_string(), Value::BuiltinFn("ціле_з_рядка".to_string()));
             scope.set("ціле_в_рядок".to_string(), Value::BuiltinFn("ціле_в_рядок".to_string()));
             scope.set("дробове_з_рядка".to_string(), Value::BuiltinFn("дробове_з_рядка".to_string()));
@@ -580,6 +997,8 @@ impl VM {
             scope.set("файл_прочитати".to_string(), Value::BuiltinFn("файл_прочитати".to_string()));
             scope.set("файл_записати".to_string(), Value::BuiltinFn("файл_записати".to_string()));
             scope.set("файл_існує".to_string(), Value::BuiltinFn("файл_існує".to_string()));
+            scope.set("читати_файл".to_string(), Value::BuiltinFn("читати_файл".to_string()));
+            scope.set("записати_файл".to_string(), Value::BuiltinFn("записати_файл".to_string()));
             scope.set("файл_рядки".to_string(), Value::BuiltinFn("файл_рядки".to_string()));
             scope.set("файл_додати".to_string(), Value::BuiltinFn("файл_додати".to_string()));
             scope.set("мін".to_string(), Value::BuiltinFn("мін".to_string()));
@@ -646,7 +1065,8 @@ impl VM {
                 "відстань_gps", "кут_до_точки",
                 "i2c_записати", "i2c_прочитати", "spi_передати",
                 "затримка_мкс", "затримка_мс",
-                "байти_в_число", "число_в_байти", "біт_встановити", "біт_прочитати"] {
+                "байти_в_число", "число_в_байти", "біт_встановити", "біт_прочитати",
+                "врядок_байти", "збайтів_рядок"] {
                 scope.set(name.to_string(), Value::BuiltinFn(name.to_string()));
             }
 
@@ -691,6 +1111,15 @@ impl VM {
             });
             scope.set("Успіх".to_string(), Value::BuiltinFn("Успіх".to_string()));
             scope.set("Помилка".to_string(), Value::BuiltinFn("Помилка".to_string()));
+            // Короткі синоніми, що створюють ті самі варіанти
+            for name in &["успіх", "помилка", "щось", "є_помилкою", "розгорнути"] {
+                scope.set(name.to_string(), Value::BuiltinFn(name.to_string()));
+            }
+            scope.set("ніщо".to_string(), Value::EnumVariant {
+                type_name: "Опція".to_string(),
+                variant: "Нічого".to_string(),
+                fields: vec![],
+            });
 
             // Системне програмування
             for name in &["зовнішня_бібліотека", "зовнішній_виклик", "зовнішній_виклик_дрб", "закрити_бібліотеку",
@@ -715,6 +1144,7 @@ impl VM {
             contracts: HashMap::new(),
             yielded_values: Vec::new(),
             async_queue: Vec::new(),
+            program_decls: Arc::new(Vec::new()),
             macros: HashMap::new(),
             effect_handlers: Vec::new(),
             registered_effects: HashMap::new(),
@@ -753,6 +1183,10 @@ impl VM {
             string_interner: StringInterner::new(),
             pure_cache: PureCache::new(10_000),
             pure_functions: HashSet::new(),
+            async_tasks: HashMap::new(),
+            task_runtime: OnceCell::new(),
+            static_methods: HashSet::new(),
+            impl_types: HashSet::new(),
             op_count: 0,
             gc_threshold: 10_000,
             default_jwt_secret: {
@@ -762,10 +1196,129 @@ impl VM {
             vector_index: None,
             allocations: HashMap::new(),
             call_stack: Vec::new(),
+            frame_envs: Vec::new(),
+            self_mutated: false,
+            method_self: None,
+            coverage: None,
+            timeout: None,
+            time_limit: None,
+            memory_limit: None,
+            block_value: None,
+            trace: false,
+            deferred: Vec::new(),
+        }
+    }
+
+    /// Застосовує директиви `//@таймаут 5с` і `//@ліміт_памʼяті 10МБ`;
+    /// невідома директива лише друкує попередження
+    fn apply_directives(&mut self, directives: &[tryzub_parser::Directive]) -> Result<()> {
+        for directive in directives {
+            let invalid = || anyhow::anyhow!(
+                "Помилка значення: некоректне значення директиви //@{} '{}' на рядку {}",
+                directive.name, directive.value, directive.line
+            );
+            match directive.name.as_str() {
+                "таймаут" => {
+                    let duration = parse_duration(&directive.value).ok_or_else(invalid)?;
+                    let limit = self.timeout.map_or(duration, |timeout| timeout.min(duration));
+                    self.time_limit = Some((std::time::Instant::now(), limit));
+                }
+                "ліміт_памʼяті" | "ліміт_пам'яті" => {
+                    self.memory_limit = Some(parse_byte_size(&directive.value).ok_or_else(invalid)?);
+                }
+                name => eprintln!("Попередження: невідома директива //@{} на рядку {}", name, directive.line),
+            }
+        }
+        Ok(())
+    }
+
+    /// Обмежує час виконання програми, напр. для ненадійного коду. Відлік
+    /// починається з `execute_program`; час перевіряється на кожній ітерації
+    /// циклу й кожному виклику функції
+    pub fn with_timeout(mut self, limit: std::time::Duration) -> Self {
+        self.timeout = Some(limit);
+        self
+    }
+
+    #[inline]
+    fn check_timeout(&self) -> Result<()> {
+        if let Some((start, limit)) = self.time_limit {
+            if start.elapsed() > limit {
+                return Err(anyhow::anyhow!("Помилка виконання: перевищено час виконання ({:?})", limit));
+            }
         }
+        Ok(())
+    }
+
+    /// Перевірка меж; викликається періодично з обчислення виразів
+    fn check_limits(&self) -> Result<()> {
+        self.check_timeout()?;
+        if let Some(limit) = self.memory_limit {
+            // Ланцюги кадрів сходяться у спільних предках, тож кожну область рахуємо раз
+            let mut used = 0;
+            let mut seen = HashSet::new();
+            for frame in self.frame_envs.iter().chain(std::iter::once(&self.current_env)) {
+                let mut env = Some(frame.clone());
+                while let Some(scope) = env {
+                    if !seen.insert(Rc::as_ptr(&scope)) {
+                        break;
+                    }
+                    let scope = scope.borrow();
+                    used += scope.variables.iter().map(|(k, v)| k.len() + v.approx_size()).sum::<usize>();
+                    env = scope.parent.clone();
+                }
+            }
+            if used > limit {
+                return Err(anyhow::anyhow!(
+                    "Перевищено ліміт пам'яті: {} байт із {} (директива //@ліміт_памʼяті)", used, limit
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Вмикає підрахунок викликів функцій і виконань рядків
+    pub fn enable_coverage(&mut self) {
+        self.coverage.get_or_insert_with(Coverage::default);
+    }
+
+    /// Вмикає трасування: кожен виконаний оператор і значення викликів та операцій
+    /// друкуються в stderr з відступом за глибиною викликів
+    pub fn enable_trace(&mut self) {
+        self.trace = true;
+    }
+
+    /// Зібрані лічильники покриття, якщо його ввімкнено
+    pub fn coverage(&self) -> Option<&Coverage> {
+        self.coverage.as_ref()
+    }
+
+    /// Текстовий звіт покриття: функції за кількістю викликів, рядки по порядку
+    pub fn coverage_report(&self) -> String {
+        let coverage = match &self.coverage {
+            Some(c) => c,
+            None => return "Покриття не ввімкнено\n".to_string(),
+        };
+        let mut functions: Vec<(&String, &u64)> = coverage.functions.iter().collect();
+        functions.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+
+        let mut report = String::from("Покриття виконання\n  Функції:\n");
+        for (name, count) in functions {
+            report.push_str(&format!("    {:<24} {}\n", name, count));
+        }
+        report.push_str("  Рядки:\n");
+        for (line, count) in &coverage.lines {
+            report.push_str(&format!("    рядок {:<18} {}\n", line, count));
+        }
+        report
     }
 
     pub fn execute_program(&mut self, program: Program, _args: Vec<String>) -> Result<()> {
+        let program = program.configured();
+        self.time_limit = self.timeout.map(|limit| (std::time::Instant::now(), limit));
+        self.apply_directives(&program.directives)?;
+        self.program_decls = Arc::new(program.declarations.clone());
+
         // Спочатку реєструємо всі оголошення
         for decl in &program.declarations {
             self.execute_declaration(decl.clone())?;
@@ -774,12 +1327,27 @@ impl VM {
         // Шукаємо функцію головна() — якщо є, запускаємо
         let main_fn = self.global_env.borrow().get("головна");
         if let Some(Value::Function { params: _, body, closure, .. }) = main_fn {
+            if let Some(coverage) = &mut self.coverage {
+                *coverage.functions.entry("головна".to_string()).or_default() += 1;
+            }
             let prev_env = self.current_env.clone();
             self.current_env = Rc::new(RefCell::new(Scope::new(Some(closure))));
+            self.deferred.push(Vec::new());
+            let mut outcome = Ok(Value::Null);
             for stmt in body {
-                self.execute_statement(stmt)?;
+                if let Err(err) = self.execute_statement(stmt) {
+                    outcome = Err(err);
+                    break;
+                }
                 if self.return_value.is_some() { break; }
             }
+            if let Err(err) = self.run_deferred(outcome) {
+                if err.is::<ErrorPropagated>() {
+                    let value = self.return_value.take().unwrap_or(Value::Null);
+                    return Err(anyhow::anyhow!("Необроблена помилка в головна(): {}", value.to_display_string()));
+                }
+                return Err(err);
+            }
             self.return_value = None;
             self.current_env = prev_env;
         }
@@ -790,18 +1358,27 @@ impl VM {
 
     fn execute_declaration(&mut self, decl: Declaration) -> Result<()> {
         match decl {
-            Declaration::Variable { name, ty, value, .. } => {
+            Declaration::Variable { name, ty, value, is_mutable, .. } => {
+                let initialized = value.is_some() || matches!(ty, Some(tryzub_parser::Type::Array(..)));
                 let val = if let Some(expr) = value {
                     self.evaluate_expression(expr)?
+                } else if let Some(ref t @ tryzub_parser::Type::Array(_, _)) = ty {
+                    // Масив фіксованого розміру без ініціалізатора — заповнюємо нулями
+                    Self::zero_value(t)
                 } else {
                     Value::Null
                 };
-                if let Some(ref expected_type) = ty {
+                // `змінна х: цл64` без значення отримає його пізніше; читання до того ловить `перевірити`
+                if let (Some(ref expected_type), true) = (&ty, initialized) {
                     self.check_type(&val, expected_type)?;
                 }
-                self.current_env.borrow_mut().set(name, val);
+                if is_mutable {
+                    self.current_env.borrow_mut().set(name, val);
+                } else {
+                    self.current_env.borrow_mut().set_constant(name, val);
+                }
             }
-            Declaration::Function { name, generic_params, params, return_type, body, contract, .. } => {
+            Declaration::Function { name, generic_params, params, return_type, body, contract, is_async, .. } => {
                 let func = Value::Function {
                     name: Some(name.clone()),
                     generic_params,
@@ -809,6 +1386,7 @@ impl VM {
                     return_type,
                     body,
                     closure: self.current_env.clone(),
+                    is_async,
                 };
                 if let Some(c) = contract {
                     self.contracts.insert(name.clone(), c);
@@ -846,23 +1424,10 @@ impl VM {
                 // Зберігаємо реалізовані методи
                 let mut implemented: HashSet<String> = HashSet::new();
                 for method in methods {
-                    if let Declaration::Function { name, generic_params, params, return_type, body, .. } = method {
+                    if let Declaration::Function { name, .. } = &method {
                         implemented.insert(name.clone());
-                        let func = Value::Function {
-                            name: Some(name.clone()),
-                            generic_params,
-                            params,
-                            return_type,
-                            body: body.clone(),
-                            closure: self.current_env.clone(),
-                        };
-                        self.current_env.borrow_mut().set(
-                            format!("{}::{}", for_type, name), func.clone()
-                        );
-                        self.trait_methods.insert(
-                            (for_type.clone(), name), body
-                        );
                     }
+                    self.register_impl_member(&for_type, method)?;
                 }
 
                 // Default methods — якщо трейт має default_body а реалізація не перевизначила
@@ -886,6 +1451,7 @@ impl VM {
                                     return_type: None,
                                     body: default_body.clone(),
                                     closure: self.current_env.clone(),
+                                    is_async: false,
                                 };
                                 self.current_env.borrow_mut().set(
                                     format!("{}::{}", for_type, tm.name), func
@@ -900,22 +1466,7 @@ impl VM {
             }
             Declaration::Impl { type_name: for_type, methods } => {
                 for method in methods {
-                    if let Declaration::Function { name, generic_params, params, return_type, body, .. } = method {
-                        let func = Value::Function {
-                            name: Some(name.clone()),
-                            generic_params,
-                            params,
-                            return_type,
-                            body: body.clone(),
-                            closure: self.current_env.clone(),
-                        };
-                        self.current_env.borrow_mut().set(
-                            format!("{}::{}", for_type, name), func.clone()
-                        );
-                        self.trait_methods.insert(
-                            (for_type.clone(), name), body
-                        );
-                    }
+                    self.register_impl_member(&for_type, method)?;
                 }
             }
             Declaration::Struct { name, fields, .. } => {
@@ -1004,6 +1555,17 @@ impl VM {
     }
 
     fn execute_statement(&mut self, stmt: Statement) -> Result<()> {
+        if let Some(coverage) = &mut self.coverage {
+            if let Some(line) = Self::statement_line(&stmt) {
+                *coverage.lines.entry(line).or_default() += 1;
+            }
+        }
+        if self.trace {
+            if let Some(label) = Self::statement_label(&stmt) {
+                let line = Self::statement_line(&stmt).map(|l| format!("[рядок {}] ", l)).unwrap_or_default();
+                eprintln!("{}{}{}", self.trace_indent(), line, label);
+            }
+        }
         match stmt {
             Statement::Expression(expr) => { self.evaluate_expression(expr)?; }
             Statement::Block(statements) => {
@@ -1022,7 +1584,7 @@ impl VM {
                     Value::Null
                 });
             }
-            Statement::If { condition, then_branch, else_branch } => {
+            Statement::If { condition, then_branch, else_branch, .. } => {
                 let cond_value = self.evaluate_expression(condition)?;
                 if cond_value.to_bool() {
                     self.execute_statement(*then_branch)?;
@@ -1030,29 +1592,31 @@ impl VM {
                     self.execute_statement(*else_stmt)?;
                 }
             }
-            Statement::While { condition, body } => {
+            Statement::While { condition, body, .. } => {
+                // продовжити лише обриває поточне тіло: прапор скидається перед
+                // кожною ітерацією і після циклу, щоб не вийти за його межі
                 while self.evaluate_expression(condition.clone())?.to_bool() {
+                    self.continue_flag = false;
+                    self.check_timeout()?;
                     self.execute_statement(*body.clone())?;
-                    if self.break_flag { self.break_flag = false; break; }
-                    if self.continue_flag { self.continue_flag = false; continue; }
                     if self.return_value.is_some() { break; }
+                    if self.break_flag { self.break_flag = false; break; }
                 }
+                self.continue_flag = false;
             }
-            Statement::For { variable, from, to, step, body } => {
-                let from_val = match self.evaluate_expression(from)? {
-                    Value::Integer(n) => n,
-                    _ => return Err(anyhow::anyhow!("Початкове значення циклу має бути цілим числом")),
+            Statement::For { variable, from, to, step, body, .. } => {
+                let from_val = self.evaluate_expression(from)?;
+                let to_val = self.evaluate_expression(to)?;
+                let step_val = match step {
+                    Some(step_expr) => self.evaluate_expression(step_expr)?,
+                    None => Value::Integer(1),
                 };
-                let to_val = match self.evaluate_expression(to)? {
-                    Value::Integer(n) => n,
-                    _ => return Err(anyhow::anyhow!("Кінцеве значення циклу має бути цілим числом")),
+                // Крок, що веде від меж (від 0 до 3 через -1), дає порожній цикл
+                let (from_val, to_val, step_val) = match (from_val, to_val, step_val) {
+                    (_, _, Value::Integer(0)) => return Err(anyhow::anyhow!("Крок циклу не може дорівнювати нулю")),
+                    (Value::Integer(a), Value::Integer(b), Value::Integer(c)) => (a, b, c),
+                    (a, b, c) => return self.execute_float_for(variable, a, b, c, *body),
                 };
-                let step_val = if let Some(step_expr) = step {
-                    match self.evaluate_expression(step_expr)? {
-                        Value::Integer(n) => n,
-                        _ => return Err(anyhow::anyhow!("Крок циклу має бути цілим числом")),
-                    }
-                } else { 1 };
 
                 // ═══ PREDICTIVE PATTERN RECOGNITION ═══
                 // Розпізнаємо типові паттерни циклів і замінюємо O(n) на O(1)
@@ -1074,6 +1638,7 @@ impl VM {
                         let mut i = from_val;
                         while (step_val > 0 && i < to_val) || (step_val < 0 && i > to_val) {
                             self.current_env.borrow_mut().set(variable.clone(), Value::Integer(i));
+                            self.check_timeout()?;
                             self.execute_statement(*body.clone())?;
                             if self.break_flag { self.break_flag = false; break; }
                             if self.continue_flag { self.continue_flag = false; }
@@ -1088,6 +1653,7 @@ impl VM {
                     let mut i = from_val;
                     while (step_val > 0 && i < to_val) || (step_val < 0 && i > to_val) {
                         self.current_env.borrow_mut().set(variable.clone(), Value::Integer(i));
+                        self.check_timeout()?;
                         self.execute_statement(*body.clone())?;
                         if self.break_flag { self.break_flag = false; break; }
                         if self.continue_flag { self.continue_flag = false; }
@@ -1100,7 +1666,7 @@ impl VM {
             Statement::ForIn { pattern, iterable, body } => {
                 let iter_val = self.evaluate_expression(iterable)?;
                 let items = match iter_val {
-                    Value::Array(arr) => arr,
+                    Value::Array(arr) => arr.into_inner(),
                     Value::Range { from, to, inclusive } => {
                         let end = if inclusive { to + 1 } else { to };
                         (from..end).map(Value::Integer).collect()
@@ -1125,6 +1691,7 @@ impl VM {
 
                 for item in items {
                     self.bind_pattern(&pattern, &item)?;
+                    self.check_timeout()?;
                     self.execute_statement(*body.clone())?;
                     if self.break_flag { self.break_flag = false; break; }
                     if self.continue_flag { self.continue_flag = false; }
@@ -1133,16 +1700,30 @@ impl VM {
                 self.current_env = prev_env;
             }
             Statement::Break => { self.break_flag = true; }
+            Statement::BreakValue(expr) => {
+                self.block_value = Some(self.evaluate_expression(expr)?);
+                return Err(BlockBreak.into());
+            }
             Statement::Continue => { self.continue_flag = true; }
-            Statement::Assignment { target, value, op } => {
+            Statement::Assignment { target, value, op, .. } => {
                 self.execute_assignment(target, value, op)?;
             }
+            Statement::MultiAssign { targets, value, .. } => {
+                self.execute_multi_assign(targets, value)?;
+            }
             Statement::Declaration(decl) => {
                 self.execute_declaration(decl)?;
             }
             Statement::TryCatch { try_body, catch_param, catch_body, finally_body } => {
                 let result = self.execute_statement(*try_body);
                 if let Err(err) = result {
+                    // `?` і `переривати значення` — це не винятки, а вихід з функції чи блоку
+                    if err.is::<ErrorPropagated>() || err.is::<BlockBreak>() {
+                        if let Some(finally) = finally_body {
+                            self.execute_statement(*finally)?;
+                        }
+                        return Err(err);
+                    }
                     if let (Some(param), Some(body)) = (catch_param, catch_body) {
                         let prev_env = self.current_env.clone();
                         self.current_env = Rc::new(RefCell::new(Scope::new(Some(self.current_env.clone()))));
@@ -1197,90 +1778,317 @@ impl VM {
                 let val = self.evaluate_expression(expr)?;
                 self.yielded_values.push(val);
             }
+            Statement::Defer(body) => {
+                // Знімок, як у лямбд: цикл перезаписує змінну ітерації у тій самій області
+                let env = self.capture_environment();
+                self.deferred.last_mut()
+                    .ok_or_else(|| anyhow::anyhow!("'відкласти' можливий лише всередині функції"))?
+                    .push((*body, env));
+            }
         }
         Ok(())
     }
 
+    /// Бінарна операція складеного присвоєння (`+=` → `+`); `None` для звичайного `=`
+    fn compound_op(op: &AssignmentOp) -> Option<BinaryOp> {
+        match op {
+            AssignmentOp::Assign => None,
+            AssignmentOp::AddAssign => Some(BinaryOp::Add),
+            AssignmentOp::SubAssign => Some(BinaryOp::Sub),
+            AssignmentOp::MulAssign => Some(BinaryOp::Mul),
+            AssignmentOp::DivAssign => Some(BinaryOp::Div),
+            AssignmentOp::ModAssign => Some(BinaryOp::Mod),
+        }
+    }
+
+    /// Нове значення цілі: `rhs` для `=`, або `поточне op rhs` для складених форм
+    fn assigned_value(&mut self, op: &AssignmentOp, current: Option<&Value>, rhs: Value, target: &str) -> Result<Value> {
+        match Self::compound_op(op) {
+            None => Ok(rhs),
+            Some(bin_op) => {
+                let current = current.cloned()
+                    .ok_or_else(|| anyhow::anyhow!("Складене присвоєння: {} не існує", target))?;
+                let expected = current.kind_name();
+                let result = self.apply_binary_op(bin_op, current, rhs)?;
+                if result.kind_name() != expected {
+                    return Err(anyhow::anyhow!(
+                        "Невідповідність типів: {} має тип '{}', не можна присвоїти '{}'",
+                        target, expected, result.kind_name()
+                    ));
+                }
+                Ok(result)
+            }
+        }
+    }
+
+    /// Рядок інструкції для покриття — з span'а самої інструкції або її виразу
+    fn statement_line(stmt: &Statement) -> Option<usize> {
+        let span = match stmt {
+            Statement::If { span, .. }
+            | Statement::While { span, .. }
+            | Statement::For { span, .. }
+            | Statement::Assignment { span, .. }
+            | Statement::MultiAssign { span, .. }
+            | Statement::Declaration(Declaration::Variable { span, .. }) => Some(*span),
+            Statement::Expression(expr)
+            | Statement::Return(Some(expr))
+            | Statement::Yield(expr)
+            | Statement::Assert(expr) => return Self::expression_line(expr),
+            _ => None,
+        };
+        span.map(|s| s.line).filter(|&line| line > 0)
+    }
+
+    /// Рядок виразу для покриття, якщо вираз несе span
+    fn expression_line(expr: &Expression) -> Option<usize> {
+        match expr {
+            Expression::Binary { span, .. }
+            | Expression::Call { span, .. }
+            | Expression::MethodCall { span, .. } => Some(span.line).filter(|&line| line > 0),
+            _ => None,
+        }
+    }
+
+    fn trace_indent(&self) -> String {
+        "  ".repeat(self.call_stack.len())
+    }
+
+    /// Підпис оператора для трасування; блоки не підписуються, бо їхні оператори
+    /// трасуються окремо
+    fn statement_label(stmt: &Statement) -> Option<String> {
+        let label = match stmt {
+            Statement::Block(_) => return None,
+            Statement::Expression(expr) => Self::expression_text(expr),
+            Statement::Declaration(Declaration::Variable { name, is_mutable, .. }) => {
+                format!("{} {}", if *is_mutable { "змінна" } else { "стала" }, name)
+            }
+            Statement::Declaration(_) => "оголошення".to_string(),
+            Statement::Assignment { target, .. } => format!("присвоєння {}", Self::expression_text(target)),
+            Statement::MultiAssign { .. } | Statement::Destructure { .. } => "присвоєння".to_string(),
+            Statement::Return(_) => "повернути".to_string(),
+            Statement::If { .. } => "якщо".to_string(),
+            Statement::While { .. } => "поки".to_string(),
+            Statement::For { .. } | Statement::ForIn { .. } => "для".to_string(),
+            Statement::Break | Statement::BreakValue(_) => "переривати".to_string(),
+            Statement::Continue => "продовжити".to_string(),
+            Statement::TryCatch { .. } => "спробувати".to_string(),
+            Statement::Assert(_) => "перевірити".to_string(),
+            Statement::Yield(_) => "віддати".to_string(),
+            Statement::WithHandler { handler, .. } => format!("з_обробником {}", handler),
+            Statement::CompTime(_) => "компчас".to_string(),
+            Statement::Unsafe(_) => "небезпечний".to_string(),
+            Statement::Defer(_) => "відкласти".to_string(),
+        };
+        Some(label)
+    }
+
+    /// Вирази, значення яких показує трасування: виклики та операції.
+    /// Літерали й імена видно з самого підпису
+    fn traced_expression(expr: &Expression) -> Option<String> {
+        match expr {
+            Expression::Binary { .. } | Expression::Unary { .. }
+            | Expression::Call { .. } | Expression::MethodCall { .. } => Some(Self::expression_text(expr)),
+            _ => None,
+        }
+    }
+
+    /// Короткий запис виразу: аргументи викликів і вкладені складні вирази
+    /// скорочуються до `…`
+    fn expression_text(expr: &Expression) -> String {
+        let operand = |e: &Expression| match e {
+            Expression::Identifier(_) | Expression::Literal(_) => Self::expression_text(e),
+            _ => "…".to_string(),
+        };
+        match expr {
+            Expression::Identifier(name) => name.clone(),
+            Expression::Literal(Literal::Integer(n)) => n.to_string(),
+            Expression::Literal(Literal::Float(f)) => f.to_string(),
+            Expression::Literal(Literal::String(s)) => format!("{:?}", s),
+            Expression::Literal(Literal::Char(c)) => format!("{:?}", c),
+            Expression::Literal(Literal::Bool(b)) => if *b { "істина" } else { "хиба" }.to_string(),
+            Expression::Literal(Literal::Null) => "нуль".to_string(),
+            Expression::Binary { left, op, right, .. } => {
+                format!("{} {} {}", operand(left), binary_op_symbol(op), operand(right))
+            }
+            Expression::Unary { op, operand: inner } => {
+                let symbol = match op { UnaryOp::Neg => "-", UnaryOp::Not => "!", UnaryOp::BitNot => "~" };
+                format!("{}{}", symbol, operand(inner))
+            }
+            Expression::Call { callee, args, .. } => {
+                format!("{}({})", operand(callee), if args.is_empty() { "" } else { "…" })
+            }
+            Expression::MethodCall { object, method, args, .. } => {
+                format!("{}.{}({})", operand(object), method, if args.is_empty() { "" } else { "…" })
+            }
+            Expression::MemberAccess { object, member } => format!("{}.{}", operand(object), member),
+            Expression::Index { object, index } => format!("{}[{}]", operand(object), operand(index)),
+            _ => "…".to_string(),
+        }
+    }
+
+    /// Змінна, в яку записується присвоєння поля чи елемента; `це` — синонім `себе`
+    fn assignment_root(object: &Expression) -> Option<String> {
+        match object {
+            Expression::Identifier(name) => Some(name.clone()),
+            Expression::SelfRef => Some("себе".to_string()),
+            _ => None,
+        }
+    }
+
     fn execute_assignment(&mut self, target: Expression, value: Expression, op: AssignmentOp) -> Result<()> {
+        let rhs = self.evaluate_expression(value)?;
+        self.assign_value(target, rhs, op)
+    }
+
+    /// `а, б = вираз`: права частина — масив або кортеж тієї ж довжини, що й список цілей
+    fn execute_multi_assign(&mut self, targets: Vec<Expression>, value: Expression) -> Result<()> {
+        let values = match self.evaluate_expression(value)? {
+            Value::Array(items) => items.into_inner(),
+            Value::Tuple(items) => items,
+            other => return Err(anyhow::anyhow!(
+                "Множинне присвоєння очікує масив або кортеж, отримано '{}'", other.kind_name()
+            )),
+        };
+        if values.len() != targets.len() {
+            return Err(anyhow::anyhow!(
+                "Множинне присвоєння: {} цілей, але {} значень", targets.len(), values.len()
+            ));
+        }
+        for (target, value) in targets.into_iter().zip(values) {
+            self.assign_value(target, value, AssignmentOp::Assign)?;
+        }
+        Ok(())
+    }
+
+    fn assign_value(&mut self, target: Expression, rhs: Value, op: AssignmentOp) -> Result<()> {
         match target {
             Expression::Identifier(name) => {
-                let new_value = match op {
-                    AssignmentOp::Assign => self.evaluate_expression(value)?,
-                    _ => {
+                let new_value = match Self::compound_op(&op) {
+                    None => rhs,
+                    Some(bin_op) => {
                         let current = self.current_env.borrow().get(&name)
                             .ok_or_else(|| anyhow::anyhow!("Невідома змінна: {}", name))?;
-                        let rhs = self.evaluate_expression(value)?;
-                        let bin_op = match op {
-                            AssignmentOp::AddAssign => BinaryOp::Add,
-                            AssignmentOp::SubAssign => BinaryOp::Sub,
-                            AssignmentOp::MulAssign => BinaryOp::Mul,
-                            AssignmentOp::DivAssign => BinaryOp::Div,
-                            AssignmentOp::ModAssign => BinaryOp::Mod,
-                            _ => unreachable!(),
-                        };
                         self.apply_binary_op(bin_op, current, rhs)?
                     }
                 };
                 self.current_env.borrow_mut().update(&name, new_value)?;
             }
-            Expression::MemberAccess { object, member } => {
-                if let Expression::Identifier(obj_name) = *object {
-                    let new_value = self.evaluate_expression(value)?;
-                    let obj = self.current_env.borrow().get(&obj_name)
-                        .ok_or_else(|| anyhow::anyhow!("Невідома змінна: {}", obj_name))?;
-                    if let Value::Struct(type_name, mut fields) = obj {
-                        fields.insert(member, new_value);
-                        let updated = Value::Struct(type_name, fields);
-                        self.current_env.borrow_mut().update(&obj_name, updated)?;
-                    }
+            target @ (Expression::MemberAccess { .. } | Expression::Index { .. }) => {
+                let mut path = Vec::new();
+                let root = self.lvalue_path(target, &mut path)?;
+                let mut value = self.current_env.borrow().get(&root)
+                    .ok_or_else(|| anyhow::anyhow!("Невідома змінна: {}", root))?;
+                // Звільняємо змінну: якщо буфер більше ніхто не ділить,
+                // запис змінить його на місці замість повної копії
+                self.current_env.borrow_mut().update(&root, Value::Null)?;
+                let written = self.write_lvalue(&mut value, &path, rhs, &op, root.clone());
+                self.current_env.borrow_mut().update(&root, value)?;
+                written?;
+                if root == "себе" {
+                    self.self_mutated = true;
                 }
             }
+            _ => return Err(anyhow::anyhow!("Присвоєння можливе тільки до змінних")),
+        }
+        Ok(())
+    }
+
+    /// Розкладає ціль `м[і][j]` чи `о.поле.підполе` на кореневу змінну й кроки до
+    /// місця запису. Індекси обчислюються зліва направо
+    fn lvalue_path(&mut self, target: Expression, path: &mut Vec<LvalueStep>) -> Result<String> {
+        match target {
+            Expression::Identifier(name) => Ok(name),
+            Expression::SelfRef => Ok("себе".to_string()),
+            Expression::MemberAccess { object, member } => {
+                let root = self.lvalue_path(*object, path)?;
+                path.push(LvalueStep::Field(member));
+                Ok(root)
+            }
             Expression::Index { object, index } => {
-                if let Expression::Identifier(obj_name) = *object {
-                    let idx = self.evaluate_expression(*index)?;
-                    let new_value = self.evaluate_expression(value)?;
-                    let obj = self.current_env.borrow().get(&obj_name)
-                        .ok_or_else(|| anyhow::anyhow!("Невідома змінна: {}", obj_name))?;
-                    match obj {
-                        Value::Array(mut arr) => {
-                            if let Value::Integer(i) = idx {
-                                let idx = if i < 0 { arr.len() as i64 + i } else { i } as usize;
-                                if idx < arr.len() {
-                                    arr[idx] = new_value;
-                                    self.current_env.borrow_mut().update(&obj_name, Value::Array(arr))?;
-                                }
-                            }
-                        }
-                        Value::Dict(mut pairs) => {
-                            let mut found = false;
-                            for (k, v) in pairs.iter_mut() {
-                                if self.values_equal(k, &idx) {
-                                    *v = new_value.clone();
-                                    found = true;
-                                    break;
-                                }
-                            }
-                            if !found {
-                                pairs.push((idx, new_value));
-                            }
-                            self.current_env.borrow_mut().update(&obj_name, Value::Dict(pairs))?;
-                        }
-                        _ => return Err(anyhow::anyhow!("Індексне присвоєння підтримується тільки для масивів та словників")),
-                    }
+                let root = self.lvalue_path(*object, path)?;
+                path.push(LvalueStep::Index(self.evaluate_expression(*index)?));
+                Ok(root)
+            }
+            _ => Err(anyhow::anyhow!("Присвоєння можливе тільки до змінних, полів та елементів")),
+        }
+    }
+
+    /// Спускається за кроками `path` усередину `value` і записує значення на останньому.
+    /// Межі масивів і наявність полів перевіряються на кожному рівні
+    fn write_lvalue(&mut self, value: &mut Value, path: &[LvalueStep], rhs: Value, op: &AssignmentOp, place: String) -> Result<()> {
+        let Some((step, rest)) = path.split_first() else {
+            return Ok(());
+        };
+        match (value, step) {
+            (Value::Struct(type_name, fields), LvalueStep::Field(member)) => {
+                let place = format!("{}.{}", place, member);
+                if rest.is_empty() {
+                    let new_value = self.assigned_value(op, fields.get(member), rhs, &format!("поле '{}'", place))?;
+                    fields.insert(member.clone(), new_value);
+                    return Ok(());
                 }
+                let inner = fields.get_mut(member)
+                    .ok_or_else(|| anyhow::anyhow!("Структура '{}' не має поля '{}'", type_name, member))?;
+                self.write_lvalue(inner, rest, rhs, op, place)
             }
-            _ => return Err(anyhow::anyhow!("Присвоєння можливе тільки до змінних")),
+            (Value::Array(arr), LvalueStep::Index(idx)) => {
+                let Value::Integer(i) = idx else {
+                    return Err(anyhow::anyhow!("Індекс масиву має бути цілим, отримано '{}'", idx.kind_name()));
+                };
+                let position = Self::normalize_index(*i, arr.len())?;
+                let place = format!("{}[{}]", place, i);
+                if rest.is_empty() {
+                    let new_value = self.assigned_value(op, arr.get(position), rhs, &format!("елемент {}", place))?;
+                    arr[position] = new_value;
+                    return Ok(());
+                }
+                self.write_lvalue(&mut arr[position], rest, rhs, op, place)
+            }
+            (Value::Dict(pairs), LvalueStep::Index(key)) => {
+                let position = pairs.iter().position(|(k, _)| self.values_equal(k, key));
+                if rest.is_empty() {
+                    let target = format!("ключ {} у '{}'", key.to_display_string(), place);
+                    let new_value = self.assigned_value(op, position.map(|p| &pairs[p].1), rhs, &target)?;
+                    match position {
+                        Some(p) => pairs[p].1 = new_value,
+                        None => pairs.push((key.clone(), new_value)),
+                    }
+                    return Ok(());
+                }
+                let p = position.ok_or_else(|| anyhow::anyhow!(
+                    "Ключ {} відсутній у '{}'", key.to_display_string(), place
+                ))?;
+                let place = format!("{}[{}]", place, key.nested());
+                self.write_lvalue(&mut pairs[p].1, rest, rhs, op, place)
+            }
+            (other, LvalueStep::Field(_)) => Err(anyhow::anyhow!(
+                "Присвоєння поля можливе тільки для структур, '{}' має тип '{}'", place, other.kind_name()
+            )),
+            (_, LvalueStep::Index(_)) => Err(anyhow::anyhow!(
+                "Індексне присвоєння підтримується тільки для масивів та словників"
+            )),
         }
-        Ok(())
     }
 
     // ── Обчислення виразів ──
 
     #[inline(always)]
     fn evaluate_expression(&mut self, expr: Expression) -> Result<Value> {
+        if self.trace {
+            if let Some(label) = Self::traced_expression(&expr) {
+                let value = self.evaluate_untraced(expr)?;
+                eprintln!("{}  {} = {}", self.trace_indent(), label, value.nested());
+                return Ok(value);
+            }
+        }
+        self.evaluate_untraced(expr)
+    }
+
+    fn evaluate_untraced(&mut self, expr: Expression) -> Result<Value> {
         self.op_count += 1;
         if self.op_count & 0xFFFF == 0 {
             self.run_gc();
+            self.check_limits()?;
         }
         match expr {
             Expression::Literal(lit) => Ok(self.evaluate_literal(lit)),
@@ -1290,7 +2098,7 @@ impl VM {
                         let known = self.current_env.borrow().all_names();
                         let suggestion = Self::find_similar(&name, &known);
                         let hint = if let Some(s) = suggestion {
-                            format!("\n  Підказка: можливо ви мали на увазі '{}'?", s)
+                            format!("\n  Підказка: можливо, ви мали на увазі '{}'?", s)
                         } else { String::new() };
                         let trace = self.format_stack_trace();
                         anyhow::anyhow!("[Т001] Невідома змінна або функція: '{}'{}\n{}", name, hint, trace)
@@ -1298,18 +2106,16 @@ impl VM {
             }
             Expression::SelfRef => {
                 self.current_env.borrow().get("себе")
-                    .ok_or_else(|| anyhow::anyhow!("'себе' доступне тільки в методах"))
+                    .ok_or_else(|| anyhow::anyhow!("'себе' та 'це' доступні тільки в методах"))
             }
-            Expression::Binary { left, op, right } => {
+            Expression::Binary { left, op, right, .. } => {
                 let lhs = self.evaluate_expression(*left)?;
                 let rhs = self.evaluate_expression(*right)?;
                 if let (Value::Integer(a), Value::Integer(b)) = (&lhs, &rhs) {
                     match op {
-                        BinaryOp::Add => return Ok(Value::Integer(a + b)),
-                        BinaryOp::Sub => return Ok(Value::Integer(a - b)),
-                        BinaryOp::Mul => return Ok(Value::Integer(a * b)),
-                        BinaryOp::Div => return if *b != 0 { Ok(Value::Integer(a / b)) } else { Err(anyhow::anyhow!("Ділення на нуль")) },
-                        BinaryOp::Mod => return if *b != 0 { Ok(Value::Integer(a % b)) } else { Err(anyhow::anyhow!("Ділення на нуль")) },
+                        BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div | BinaryOp::Mod => {
+                            return Self::checked_int_op(&op, *a, *b);
+                        }
                         BinaryOp::Lt => return Ok(Value::Bool(a < b)),
                         BinaryOp::Le => return Ok(Value::Bool(a <= b)),
                         BinaryOp::Gt => return Ok(Value::Bool(a > b)),
@@ -1321,6 +2127,8 @@ impl VM {
                 }
                 match self.apply_binary_op(op.clone(), lhs.clone(), rhs.clone()) {
                     Ok(result) => Ok(result),
+                    // Перевантаження операторів можливе лише для структур
+                    Err(e) if !matches!(lhs, Value::Struct(..)) => Err(e),
                     Err(_) => {
                         // Operator overloading — шукаємо трейт-метод
                         let method_name = match op {
@@ -1342,7 +2150,7 @@ impl VM {
                 let val = self.evaluate_expression(*operand)?;
                 self.apply_unary_op(op, val)
             }
-            Expression::Call { callee, args } => {
+            Expression::Call { callee, args, .. } => {
                 let func = self.evaluate_expression(*callee)?;
                 let mut arg_values = Vec::new();
                 for arg in args {
@@ -1350,35 +2158,69 @@ impl VM {
                 }
                 self.call_value(func, arg_values)
             }
-            Expression::MethodCall { object, method, args } => {
+            Expression::MethodCall { object, method, args, .. } => {
+                // Статичний виклик: Тип.метод(...)
+                if let Some(func) = self.resolve_static_member(&object, &method) {
+                    let key = format!("{}::{}", Self::type_name_of(&object).unwrap_or_default(), method);
+                    if !self.static_methods.contains(&key) {
+                        return Err(anyhow::anyhow!(
+                            "Метод '{}' не є статичним — викличте його на екземплярі типу", key
+                        ));
+                    }
+                    let mut arg_values = Vec::new();
+                    for arg in args {
+                        arg_values.push(self.evaluate_expression(arg)?);
+                    }
+                    return self.call_value(func, arg_values);
+                }
+                // Метод структури працює з копією екземпляра; зміни полів через
+                // `це`/`себе` записуються назад у змінну, на якій його викликали
+                let target = Self::assignment_root(&object);
                 let obj = self.evaluate_expression(*object)?;
+                let is_instance = matches!(obj, Value::Struct(..));
                 let mut arg_values = Vec::new();
                 for arg in args {
                     arg_values.push(self.evaluate_expression(arg)?);
                 }
-                self.call_method(obj, &method, arg_values)
+                self.method_self = None;
+                let result = self.call_method(obj, &method, arg_values)?;
+                if let (true, Some(name), Some(updated)) = (is_instance, target, self.method_self.take()) {
+                    self.current_env.borrow_mut().update(&name, updated)?;
+                    if name == "себе" {
+                        self.self_mutated = true;
+                    }
+                }
+                Ok(result)
             }
             Expression::Index { object, index } => {
                 let obj = self.evaluate_expression(*object)?;
                 let idx = self.evaluate_expression(*index)?;
                 match (obj, idx) {
                     (Value::Array(arr), Value::Integer(i)) => {
-                        let idx = if i < 0 { arr.len() as i64 + i } else { i } as usize;
-                        arr.get(idx).cloned().ok_or_else(|| anyhow::anyhow!("Індекс {} поза межами", i))
+                        // Буфер спільний зі змінною — беремо копію лише елемента
+                        let idx = Self::normalize_index(i, arr.len())?;
+                        Ok(arr[idx].clone())
+                    }
+                    (Value::Tuple(mut items), Value::Integer(i)) => {
+                        let idx = Self::normalize_index(i, items.len())?;
+                        Ok(items.swap_remove(idx))
                     }
                     (Value::String(s), Value::Integer(i)) => {
-                        let idx = if i < 0 { s.len() as i64 + i } else { i } as usize;
-                        s.chars().nth(idx).map(Value::Char)
-                            .ok_or_else(|| anyhow::anyhow!("Індекс {} поза межами", i))
+                        let idx = Self::normalize_index(i, s.chars().count())?;
+                        Ok(Value::Char(s.chars().nth(idx).unwrap()))
                     }
-                    _ => Err(anyhow::anyhow!("Індексація підтримується тільки для масивів та рядків")),
+                    _ => Err(anyhow::anyhow!("Індексація підтримується тільки для масивів, кортежів та рядків")),
                 }
             }
             Expression::MemberAccess { object, member } => {
+                // Статичне поле або метод: Тип.ІМ'Я
+                if let Some(val) = self.resolve_static_member(&object, &member) {
+                    return Ok(val);
+                }
                 let obj = self.evaluate_expression(*object)?;
                 match &obj {
-                    Value::Module(_, members) => {
-                        members.get(&member).cloned()
+                    Value::Module(name, members) => {
+                        self.module_member(name, members, &member)?
                             .ok_or_else(|| anyhow::anyhow!("Символ '{}' не знайдено в модулі", member))
                     }
                     Value::Struct(_, fields) => {
@@ -1395,7 +2237,7 @@ impl VM {
                 for elem in elements {
                     values.push(self.evaluate_expression(elem)?);
                 }
-                Ok(Value::Array(values))
+                Ok(Value::Array(values.into()))
             }
             Expression::Tuple(elements) => {
                 let mut values = Vec::new();
@@ -1409,20 +2251,20 @@ impl VM {
                 for (field_name, field_expr) in fields {
                     field_values.insert(field_name, self.evaluate_expression(field_expr)?);
                 }
-                Ok(Value::Struct(name, field_values))
+                Ok(Value::Struct(name, field_values.into()))
             }
             Expression::Lambda { params, body } => {
                 Ok(Value::Lambda {
                     params,
                     body: LambdaBody::Expr(*body),
-                    closure: self.current_env.clone(),
+                    closure: self.capture_environment(),
                 })
             }
             Expression::LambdaBlock { params, body } => {
                 Ok(Value::Lambda {
                     params,
                     body: LambdaBody::Block(body),
-                    closure: self.current_env.clone(),
+                    closure: self.capture_environment(),
                 })
             }
             Expression::Match { subject, arms } => {
@@ -1440,17 +2282,13 @@ impl VM {
                     Value::EnumVariant { variant, fields, .. } if variant == "Успіх" => {
                         Ok(fields.first().cloned().unwrap_or(Value::Null))
                     }
-                    Value::EnumVariant { variant, .. } if variant == "Помилка" => {
-                        // Поширюємо помилку назовні
-                        self.return_value = Some(value.clone());
-                        Ok(value)
-                    }
                     Value::EnumVariant { variant, fields, .. } if variant == "Деякий" => {
                         Ok(fields.first().cloned().unwrap_or(Value::Null))
                     }
-                    Value::EnumVariant { variant, .. } if variant == "Нічого" => {
-                        self.return_value = Some(value.clone());
-                        Ok(value)
+                    Value::EnumVariant { variant, .. } if variant == "Помилка" || variant == "Нічого" => {
+                        // Поширюємо помилку назовні, оминаючи решту виразу
+                        self.return_value = Some(value);
+                        Err(ErrorPropagated.into())
                     }
                     _ => Ok(value),
                 }
@@ -1521,6 +2359,7 @@ impl VM {
                 let val = self.evaluate_expression(*expr)?;
                 // 2. Якщо результат — функція/лямбда (Future), плануємо та виконуємо
                 match val {
+                    Value::Task(id) => self.await_async_task(id),
                     Value::Function { .. } | Value::Lambda { .. } => {
                         // Додаємо в чергу та одразу виконуємо (cooperative scheduling)
                         let result = self.call_value(val, vec![])?;
@@ -1540,15 +2379,176 @@ impl VM {
                     self.evaluate_expression(*else_expr)
                 }
             }
+            Expression::Block(statements, result) => {
+                let prev_env = self.current_env.clone();
+                self.current_env = Rc::new(RefCell::new(Scope::new(Some(prev_env.clone()))));
+                let value = match self.evaluate_block(statements, result) {
+                    Err(err) if err.is::<BlockBreak>() => Ok(self.block_value.take().unwrap_or(Value::Null)),
+                    other => other,
+                };
+                self.current_env = prev_env;
+                value
+            }
+        }
+    }
+
+    /// Цикл `для` з дробовими межами чи кроком. Значення лічильника
+    /// обчислюється як `від + k * крок`, щоб похибка не накопичувалась.
+    fn execute_float_for(&mut self, variable: String, from: Value, to: Value, step: Value, body: Statement) -> Result<()> {
+        let as_float = |value: Value, what: &str| match value {
+            Value::Integer(n) => Ok(n as f64),
+            Value::Float(f) => Ok(f),
+            other => Err(anyhow::anyhow!("{} циклу має бути числом, отримано '{}'", what, other.type_name())),
+        };
+        let from = as_float(from, "Початкове значення")?;
+        let to = as_float(to, "Кінцеве значення")?;
+        let step = as_float(step, "Крок")?;
+        if step == 0.0 || !step.is_finite() {
+            return Err(anyhow::anyhow!("Крок циклу не може дорівнювати нулю"));
+        }
+
+        let prev_env = self.current_env.clone();
+        self.current_env = Rc::new(RefCell::new(Scope::new(Some(self.current_env.clone()))));
+        let mut k = 0.0;
+        loop {
+            let i = from + k * step;
+            if !((step > 0.0 && i < to) || (step < 0.0 && i > to)) { break; }
+            self.current_env.borrow_mut().set(variable.clone(), Value::Float(i));
+            self.check_timeout()?;
+            self.execute_statement(body.clone())?;
+            if self.break_flag { self.break_flag = false; break; }
+            if self.continue_flag { self.continue_flag = false; }
+            if self.return_value.is_some() { break; }
+            k += 1.0;
+        }
+        self.current_env = prev_env;
+        Ok(())
+    }
+
+    /// Тіло блок-виразу у вже відкритій області видимості
+    fn evaluate_block(&mut self, statements: Vec<Statement>, result: Option<Box<Expression>>) -> Result<Value> {
+        for stmt in statements {
+            self.execute_statement(stmt)?;
+            // повернути/перервати всередині блоку виходять за його межі
+            if self.return_value.is_some() || self.break_flag || self.continue_flag {
+                return Ok(Value::Null);
+            }
+        }
+        match result {
+            Some(expr) => self.evaluate_expression(*expr),
+            None => Ok(Value::Null),
         }
     }
 
     // ── Виклик значень ──
 
+    /// Параметри, передумови, тіло, постумови й тип результату функції у вже
+    /// створеному середовищі виклику
+    fn run_function_body(
+        &mut self,
+        func_name: &str,
+        params: &[Parameter],
+        args: &[Value],
+        body: &[Statement],
+        return_type: &Option<Type>,
+    ) -> Result<Value> {
+        for (i, param) in params.iter().enumerate() {
+            if param.name == "себе" {
+                if let Some(self_val) = args.get(i) {
+                    self.current_env.borrow_mut().set("себе".to_string(), self_val.clone());
+                }
+                continue;
+            }
+            let val = if let Some(arg) = args.get(i) {
+                arg.clone()
+            } else if let Some(ref default_expr) = param.default {
+                self.evaluate_expression(default_expr.clone())?
+            } else {
+                Value::Null
+            };
+            if !matches!(&param.ty, tryzub_parser::Type::Named(n) if n == "Будь")
+                && !matches!(&param.ty, tryzub_parser::Type::SelfType) {
+                self.check_type(&val, &param.ty)?;
+            }
+            self.current_env.borrow_mut().set(param.name.clone(), val);
+        }
+        if let Some(contract) = self.contracts.get(func_name).cloned() {
+            for pre in &contract.preconditions {
+                let val = self.evaluate_expression(pre.clone())?;
+                if !val.to_bool() {
+                    return Err(anyhow::anyhow!(
+                        "Контракт порушено: передумова не виконана у функції '{}'", func_name
+                    ));
+                }
+            }
+        }
+
+        self.deferred.push(Vec::new());
+        let outcome = self.execute_function_body(body);
+        let last_expr_value = match self.run_deferred(outcome) {
+            Err(err) if err.is::<ErrorPropagated>() => {
+                if !Self::can_propagate_into(return_type) {
+                    return Err(anyhow::anyhow!(
+                        "Оператор '?' у функції '{}' потребує, щоб вона повертала Результат або Опцію",
+                        func_name
+                    ));
+                }
+                Value::Null
+            }
+            // Блок-вираз із `переривати значення` мав бути в цій же функції
+            Err(err) if err.is::<BlockBreak>() => {
+                self.block_value = None;
+                return Err(anyhow::anyhow!("{} (функція '{}')", err, func_name));
+            }
+            other => other?,
+        };
+
+        let result = self.return_value.take().unwrap_or(last_expr_value);
+
+        // Перевірка постумов (контракти: гарантує)
+        if let Some(contract) = self.contracts.get(func_name).cloned() {
+            if !contract.postconditions.is_empty() {
+                // Зберігаємо результат як змінну для перевірки
+                if let Some(ref rn) = contract.result_name {
+                    self.current_env.borrow_mut().set(rn.clone(), result.clone());
+                }
+                for post in &contract.postconditions {
+                    let val = self.evaluate_expression(post.clone())?;
+                    if !val.to_bool() {
+                        return Err(anyhow::anyhow!(
+                            "Контракт порушено: постумова не виконана у функції '{}'", func_name
+                        ));
+                    }
+                }
+            }
+        }
+
+        if let Some(ref ret_ty) = return_type {
+            if !matches!(ret_ty, tryzub_parser::Type::Named(n) if n == "Будь") {
+                self.check_type(&result, ret_ty).map_err(|e| {
+                    anyhow::anyhow!("Функція '{}': {}", func_name, e)
+                })?;
+            }
+        }
+
+        Ok(result)
+    }
+
     fn call_value(&mut self, func: Value, args: Vec<Value>) -> Result<Value> {
+        self.check_timeout()?;
+        // Виклик асинхронної функції запускає задачу й повертає її дескриптор.
+        // На робочому потоці виконуємо одразу, щоб не чекати на зайнятий пул.
+        if let Value::Function { is_async: true, .. } = func {
+            if !IN_POOL_WORKER.with(|w| w.get()) {
+                return self.spawn_async_task(&func, args);
+            }
+        }
         match func {
             Value::Function { params, body, closure, name, return_type, .. } => {
                 let func_name = name.clone().unwrap_or_default();
+                if let Some(coverage) = &mut self.coverage {
+                    *coverage.functions.entry(func_name.clone()).or_default() += 1;
+                }
 
                 // Кеш чистих функцій — якщо функція позначена як чиста,
                 // повертаємо кешований результат замість перевиконання
@@ -1570,84 +2570,26 @@ impl VM {
                     line: 0,
                 });
                 let prev_env = self.current_env.clone();
+                self.frame_envs.push(prev_env.clone());
                 self.current_env = Rc::new(RefCell::new(Scope::new(Some(closure))));
-
-                for (i, param) in params.iter().enumerate() {
-                    if param.name == "себе" {
-                        if let Some(self_val) = args.get(i) {
-                            self.current_env.borrow_mut().set("себе".to_string(), self_val.clone());
-                        }
-                        continue;
-                    }
-                    let val = if let Some(arg) = args.get(i) {
-                        arg.clone()
-                    } else if let Some(ref default_expr) = param.default {
-                        self.evaluate_expression(default_expr.clone())?
-                    } else {
-                        Value::Null
-                    };
-                    if !matches!(&param.ty, tryzub_parser::Type::Named(n) if n == "Будь")
-                        && !matches!(&param.ty, tryzub_parser::Type::SelfType) {
-                        self.check_type(&val, &param.ty)?;
-                    }
-                    self.current_env.borrow_mut().set(param.name.clone(), val);
-                }
-                if let Some(contract) = self.contracts.get(&func_name).cloned() {
-                    for pre in &contract.preconditions {
-                        let val = self.evaluate_expression(pre.clone())?;
-                        if !val.to_bool() {
-                            return Err(anyhow::anyhow!(
-                                "Контракт порушено: передумова не виконана у функції '{}'", func_name
-                            ));
-                        }
-                    }
-                }
-
+                let prev_self_mutated = std::mem::replace(&mut self.self_mutated, false);
                 let prev_return = self.return_value.take();
-                let mut last_expr_value = Value::Null;
-
-                for (i, stmt) in body.iter().enumerate() {
-                    if i == body.len() - 1 {
-                        if let Statement::Expression(expr) = stmt {
-                            last_expr_value = self.evaluate_expression(expr.clone())?;
-                            break;
-                        }
-                    }
-                    self.execute_statement(stmt.clone())?;
-                    if self.return_value.is_some() { break; }
-                }
 
-                let result = self.return_value.take().unwrap_or(last_expr_value);
-
-                // Перевірка постумов (контракти: гарантує)
-                if let Some(contract) = self.contracts.get(&func_name).cloned() {
-                    if !contract.postconditions.is_empty() {
-                        // Зберігаємо результат як змінну для перевірки
-                        if let Some(ref rn) = contract.result_name {
-                            self.current_env.borrow_mut().set(rn.clone(), result.clone());
-                        }
-                        for post in &contract.postconditions {
-                            let val = self.evaluate_expression(post.clone())?;
-                            if !val.to_bool() {
-                                return Err(anyhow::anyhow!(
-                                    "Контракт порушено: постумова не виконана у функції '{}'", func_name
-                                ));
-                            }
-                        }
-                    }
-                }
-
-                if let Some(ref ret_ty) = return_type {
-                    if !matches!(ret_ty, tryzub_parser::Type::Named(n) if n == "Будь") {
-                        self.check_type(&result, ret_ty).map_err(|e| {
-                            anyhow::anyhow!("Функція '{}': {}", func_name, e)
-                        })?;
-                    }
-                }
+                let result = self.run_function_body(&func_name, &params, &args, &body, &return_type);
 
+                // Стан викликача відновлюється й після помилки: інакше перехоплена
+                // помилка лишила б середовище та `себе` викликаного методу
+                self.method_self = if result.is_ok() && self.self_mutated {
+                    self.current_env.borrow().get("себе")
+                } else {
+                    None
+                };
+                self.self_mutated = prev_self_mutated;
                 self.return_value = prev_return;
                 self.current_env = prev_env;
+                self.frame_envs.pop();
                 self.call_stack.pop();
+                let result = result?;
 
                 // Зберігаємо в кеш якщо функція чиста
                 if self.pure_functions.contains(&func_name) {
@@ -1659,28 +2601,51 @@ impl VM {
             }
             Value::Lambda { params, body, closure } => {
                 let prev_env = self.current_env.clone();
+                self.frame_envs.push(prev_env.clone());
                 self.current_env = Rc::new(RefCell::new(Scope::new(Some(closure))));
 
                 for (param, arg) in params.iter().zip(args.iter()) {
                     self.current_env.borrow_mut().set(param.name.clone(), arg.clone());
                 }
 
+                if let Some(coverage) = &mut self.coverage {
+                    *coverage.functions.entry("<лямбда>".to_string()).or_default() += 1;
+                    // Інструкції блокового тіла рахує execute_statement, а тіло-вираз — тут
+                    if let Some(line) = match &body { LambdaBody::Expr(expr) => Self::expression_line(expr), _ => None } {
+                        *coverage.lines.entry(line).or_default() += 1;
+                    }
+                }
+
+                let prev_return = self.return_value.take();
                 let result = match body {
-                    LambdaBody::Expr(expr) => self.evaluate_expression(expr)?,
+                    LambdaBody::Expr(expr) => self.evaluate_expression(expr),
                     LambdaBody::Block(stmts) => {
-                        let prev_return = self.return_value.take();
+                        self.deferred.push(Vec::new());
+                        let mut outcome = Ok(Value::Null);
                         for stmt in stmts {
-                            self.execute_statement(stmt)?;
+                            if let Err(err) = self.execute_statement(stmt) {
+                                outcome = Err(err);
+                                break;
+                            }
                             if self.return_value.is_some() { break; }
                         }
-                        let r = self.return_value.take().unwrap_or(Value::Null);
-                        self.return_value = prev_return;
-                        r
+                        self.run_deferred(outcome)
                     }
                 };
+                let result = match result {
+                    Err(err) if err.is::<ErrorPropagated>() => Ok(Value::Null),
+                    Err(err) if err.is::<BlockBreak>() => {
+                        self.block_value = None;
+                        Err(anyhow::anyhow!("{} (лямбда)", err))
+                    }
+                    other => other,
+                };
+                let result = result.map(|value| self.return_value.take().unwrap_or(value));
+                self.return_value = prev_return;
 
                 self.current_env = prev_env;
-                Ok(result)
+                self.frame_envs.pop();
+                result
             }
             Value::BuiltinFn(name) => self.call_builtin(&name, args),
             Value::CurriedBuiltin { name, saved_args } => {
@@ -1694,11 +2659,59 @@ impl VM {
         }
     }
 
+    /// Виконує дії `відкласти` поточної функції у зворотному порядку — і після
+    /// `повернути`, і після помилки. Помилка тіла важливіша за помилку відкладеної дії
+    fn run_deferred(&mut self, mut outcome: Result<Value>) -> Result<Value> {
+        let actions = self.deferred.pop().unwrap_or_default();
+        if actions.is_empty() {
+            return outcome;
+        }
+        let return_value = self.return_value.take();
+        let prev_env = self.current_env.clone();
+        for (stmt, env) in actions.into_iter().rev() {
+            self.current_env = env;
+            let result = self.execute_statement(stmt);
+            // `повернути` у відкладеній дії лише завершує її, результат функції не змінює
+            self.return_value = None;
+            if let (Err(err), true) = (result, outcome.is_ok()) {
+                outcome = Err(err);
+            }
+        }
+        self.current_env = prev_env;
+        self.return_value = return_value;
+        outcome
+    }
+
+    /// Виконує тіло функції; останній вираз без `повернути` стає результатом
+    fn execute_function_body(&mut self, body: &[Statement]) -> Result<Value> {
+        for (i, stmt) in body.iter().enumerate() {
+            if i == body.len() - 1 {
+                if let Statement::Expression(expr) = stmt {
+                    return self.evaluate_expression(expr.clone());
+                }
+            }
+            self.execute_statement(stmt.clone())?;
+            if self.return_value.is_some() { break; }
+        }
+        Ok(Value::Null)
+    }
+
+    /// Чи може функція з таким типом результату повернути помилку, поширену `?`
+    fn can_propagate_into(return_type: &Option<Type>) -> bool {
+        match return_type {
+            None | Some(Type::Result(..) | Type::Optional(_)) => true,
+            Some(Type::Named(name) | Type::Generic(name, _)) => {
+                matches!(name.as_str(), "Результат" | "Опція" | "Будь")
+            }
+            Some(_) => false,
+        }
+    }
+
     fn call_method(&mut self, obj: Value, method: &str, args: Vec<Value>) -> Result<Value> {
         // ── Виклик функції з модуля ──
         if let Value::Module(ref mod_name, ref members) = obj {
-            if let Some(func) = members.get(method) {
-                return self.call_value(func.clone(), args);
+            if let Some(func) = self.module_member(mod_name, members, method)? {
+                return self.call_value(func, args);
             }
             return Err(anyhow::anyhow!("Функція '{}' не знайдена в модулі '{}'", method, mod_name));
         }
@@ -1715,7 +2728,7 @@ impl VM {
                         _ => return Err(anyhow::anyhow!(".взяти() потребує ціле число")),
                     };
                     let result: Vec<Value> = (*from..end).take(n).map(Value::Integer).collect();
-                    return Ok(Value::Array(result));
+                    return Ok(Value::Array(result.into()));
                 }
                 "фільтрувати" => {
                     if let Some(func) = args.first() {
@@ -1725,7 +2738,7 @@ impl VM {
                             let cond = self.call_value(func.clone(), vec![val.clone()])?;
                             if cond.to_bool() { result.push(val); }
                         }
-                        return Ok(Value::Array(result));
+                        return Ok(Value::Array(result.into()));
                     }
                     return Err(anyhow::anyhow!(".фільтрувати() потребує предикат"));
                 }
@@ -1735,7 +2748,7 @@ impl VM {
                         for i in *from..end {
                             result.push(self.call_value(func.clone(), vec![Value::Integer(i)])?);
                         }
-                        return Ok(Value::Array(result));
+                        return Ok(Value::Array(result.into()));
                     }
                     return Err(anyhow::anyhow!(".перетворити() потребує функцію"));
                 }
@@ -1778,14 +2791,7 @@ impl VM {
                     return Ok(Value::Array(rev));
                 }
                 "сортувати" => {
-                    let mut sorted = arr.clone();
-                    sorted.sort_by(|a, b| match (a, b) {
-                        (Value::Integer(x), Value::Integer(y)) => x.cmp(y),
-                        (Value::Float(x), Value::Float(y)) => x.partial_cmp(y).unwrap_or(std::cmp::Ordering::Equal),
-                        (Value::String(x), Value::String(y)) => x.cmp(y),
-                        _ => std::cmp::Ordering::Equal,
-                    });
-                    return Ok(Value::Array(sorted));
+                    return Ok(Value::Array(self.sort_values(arr.to_vec(), args.first().cloned())?.into()));
                 }
                 "фільтрувати" => {
                     if let Some(func) = args.first() {
@@ -1794,7 +2800,7 @@ impl VM {
                             let cond = self.call_value(func.clone(), vec![item.clone()])?;
                             if cond.to_bool() { result.push(item.clone()); }
                         }
-                        return Ok(Value::Array(result));
+                        return Ok(Value::Array(result.into()));
                     }
                     return Err(anyhow::anyhow!(".фільтрувати() потребує предикат"));
                 }
@@ -1804,7 +2810,7 @@ impl VM {
                         for item in arr {
                             result.push(self.call_value(func.clone(), vec![item.clone()])?);
                         }
-                        return Ok(Value::Array(result));
+                        return Ok(Value::Array(result.into()));
                     }
                     return Err(anyhow::anyhow!(".перетворити() потребує функцію"));
                 }
@@ -1835,7 +2841,7 @@ impl VM {
                 "зрізати" | "зріз" => {
                     let from = match args.first() { Some(Value::Integer(n)) => *n as usize, _ => 0 };
                     let to = match args.get(1) { Some(Value::Integer(n)) => *n as usize, _ => arr.len() };
-                    return Ok(Value::Array(arr[from.min(arr.len())..to.min(arr.len())].to_vec()));
+                    return Ok(Value::Array(arr[from.min(arr.len())..to.min(arr.len())].to_vec().into()));
                 }
                 "розгорнути" => {
                     let mut result = Vec::new();
@@ -1843,22 +2849,22 @@ impl VM {
                         if let Value::Array(inner) = item { result.extend(inner.iter().cloned()); }
                         else { result.push(item.clone()); }
                     }
-                    return Ok(Value::Array(result));
+                    return Ok(Value::Array(result.into()));
                 }
                 "зшити" => {
                     if let Some(Value::Array(other)) = args.first() {
                         let pairs: Vec<Value> = arr.iter().zip(other.iter())
-                            .map(|(a, b)| Value::Array(vec![a.clone(), b.clone()]))
+                            .map(|(a, b)| Value::Array(vec![a.clone(), b.clone()].into()))
                             .collect();
-                        return Ok(Value::Array(pairs));
+                        return Ok(Value::Array(pairs.into()));
                     }
                     return Err(anyhow::anyhow!(".зшити() потребує масив"));
                 }
                 "пронумерувати" => {
                     let result: Vec<Value> = arr.iter().enumerate()
-                        .map(|(i, v)| Value::Array(vec![Value::Integer(i as i64), v.clone()]))
+                        .map(|(i, v)| Value::Array(vec![Value::Integer(i as i64), v.clone()].into()))
                         .collect();
-                    return Ok(Value::Array(result));
+                    return Ok(Value::Array(result.into()));
                 }
                 "будь_який" => {
                     if let Some(func) = args.first() {
@@ -1910,16 +2916,16 @@ impl VM {
                             result.push(item.clone());
                         }
                     }
-                    return Ok(Value::Array(result));
+                    return Ok(Value::Array(result.into()));
                 }
                 "частини" => {
                     if let Some(Value::Integer(n)) = args.first() {
                         let n = *n as usize;
                         if n == 0 { return Err(anyhow::anyhow!(".частини(0) — недопустимо")); }
                         let chunks: Vec<Value> = arr.chunks(n)
-                            .map(|c| Value::Array(c.to_vec()))
+                            .map(|c| Value::Array(c.to_vec().into()))
                             .collect();
-                        return Ok(Value::Array(chunks));
+                        return Ok(Value::Array(chunks.into()));
                     }
                     return Err(anyhow::anyhow!(".частини() потребує число"));
                 }
@@ -1960,7 +2966,8 @@ impl VM {
                         if let Value::Integer(i) = &args[0] {
                             let i = *i as usize;
                             let mut new_arr = arr.clone();
-                            new_arr.insert(i.min(new_arr.len()), args[1].clone());
+                            let at = i.min(new_arr.len());
+                            new_arr.insert(at, args[1].clone());
                             return Ok(Value::Array(new_arr));
                         }
                     }
@@ -2001,8 +3008,8 @@ impl VM {
                     }
                     return Ok(Value::Bool(false));
                 }
-                "великими" => return Ok(Value::String(s.to_uppercase())),
-                "малими" => return Ok(Value::String(s.to_lowercase())),
+                "великими" => return Ok(Value::String(change_case(s, true))),
+                "малими" => return Ok(Value::String(change_case(s, false))),
                 "обрізати" => return Ok(Value::String(s.trim().to_string())),
                 "розділити" => {
                     let sep = match args.first() {
@@ -2010,7 +3017,7 @@ impl VM {
                         _ => " ".to_string(),
                     };
                     let parts: Vec<Value> = s.split(&sep).map(|p| Value::String(p.to_string())).collect();
-                    return Ok(Value::Array(parts));
+                    return Ok(Value::Array(parts.into()));
                 }
                 "замінити" => {
                     if args.len() == 2 {
@@ -2062,7 +3069,7 @@ impl VM {
                 }
                 "повторити" => {
                     if let Some(Value::Integer(n)) = args.first() {
-                        return Ok(Value::String(s.repeat(*n as usize)));
+                        return Ok(Value::String(s.repeat(Self::repeat_count("повторити", *n)?)));
                     }
                     return Ok(Value::String(s.clone()));
                 }
@@ -2204,7 +3211,7 @@ impl VM {
                 "пусто" => return Ok(Value::Bool(pairs.is_empty())),
                 "пари" => {
                     return Ok(Value::Array(pairs.iter()
-                        .map(|(k, v)| Value::Array(vec![k.clone(), v.clone()]))
+                        .map(|(k, v)| Value::Array(vec![k.clone(), v.clone()].into()))
                         .collect()));
                 }
                 _ => {}
@@ -2240,7 +3247,7 @@ impl VM {
                     }
                     return Err(anyhow::anyhow!("множина.перетин потребує множину"));
                 }
-                "об_єднання" | "об'єднання" => {
+                "об_єднання" | "об'єднання" | "обʼєднання" => {
                     if let Some(Value::Set(other)) = args.first() {
                         let mut result = items.clone();
                         for o in other {
@@ -2294,7 +3301,7 @@ impl VM {
                     return Err(anyhow::anyhow!("множина.симетрична_різниця потребує множину"));
                 }
                 "пусто" => return Ok(Value::Bool(items.is_empty())),
-                "в_масив" => return Ok(Value::Array(items.clone())),
+                "в_масив" => return Ok(Value::Array(items.clone().into())),
                 _ => {}
             }
         }
@@ -2314,7 +3321,7 @@ impl VM {
                     match method {
                         "в_масив" => {
                             let (values, _) = self.generator_cache.get(&gen_id).ok_or_else(|| anyhow::anyhow!("Генератор не знайдено"))?;
-                            return Ok(Value::Array(values.clone()));
+                            return Ok(Value::Array(values.clone().into()));
                         }
                         "взяти" => {
                             let n = match args.first() {
@@ -2353,7 +3360,8 @@ impl VM {
             match method {
                 "парне" => return Ok(Value::Bool(n % 2 == 0)),
                 "непарне" => return Ok(Value::Bool(n % 2 != 0)),
-                "абс" => return Ok(Value::Integer(n.abs())),
+                "абс" => return n.checked_abs().map(Value::Integer)
+                    .ok_or_else(|| anyhow::anyhow!("Помилка значення: переповнення цл64 у абс({})", n)),
                 "в_текст" => return Ok(Value::String(n.to_string())),
                 "в_дробове" => return Ok(Value::Float(*n as f64)),
                 "степінь" => {
@@ -2402,6 +3410,11 @@ impl VM {
         };
 
         let method_key = format!("{}::{}", type_name, method);
+        if self.static_methods.contains(&method_key) {
+            return Err(anyhow::anyhow!(
+                "Статичний метод '{}' викликається через ім'я типу: {}.{}()", method_key, type_name, method
+            ));
+        }
         let maybe_func = self.current_env.borrow().get(&method_key);
         if let Some(func) = maybe_func {
             let mut all_args = vec![obj];
@@ -2427,49 +3440,45 @@ impl VM {
 
         // Паттерн: змінна = змінна + loop_var (арифметична сума)
         // Або:     змінна = змінна + loop_var * loop_var (сума квадратів)
-        if let Statement::Assignment { target, value, op: AssignmentOp::Assign } = stmt {
+        if let Statement::Assignment { target, value, op: AssignmentOp::Assign, .. } = stmt {
             if let Expression::Identifier(target_name) = target {
                 // Паттерн 1: acc = acc + i → сума арифметичної прогресії
-                if let Expression::Binary { left, op: BinaryOp::Add, right } = value {
+                if let Expression::Binary { left, op: BinaryOp::Add, right, .. } = value {
                     if self.is_ident(left, target_name) && self.is_ident(right, loop_var) {
-                        let n = to - from; // кількість ітерацій
-                        if n <= 0 { return None; }
                         // Формула: sum(from..to) = n * (from + to - 1) / 2
-                        let sum = n * (from + to - 1) / 2;
+                        let sum = range_sum(from, to)?;
                         let current = self.current_env.borrow().get(target_name)
                             .and_then(|v| if let Value::Integer(n) = v { Some(n) } else { None })
                             .unwrap_or(0);
                         return Some(LoopOptResult::SetVariable(
                             target_name.clone(),
-                            Value::Integer(current + sum),
+                            Value::Integer(current.checked_add(sum)?),
                         ));
                     }
                     // Паттерн 1b: acc = i + acc (комутативний)
                     if self.is_ident(right, target_name) && self.is_ident(left, loop_var) {
-                        let n = to - from;
-                        if n <= 0 { return None; }
-                        let sum = n * (from + to - 1) / 2;
+                        let sum = range_sum(from, to)?;
                         let current = self.current_env.borrow().get(target_name)
                             .and_then(|v| if let Value::Integer(n) = v { Some(n) } else { None })
                             .unwrap_or(0);
                         return Some(LoopOptResult::SetVariable(
                             target_name.clone(),
-                            Value::Integer(current + sum),
+                            Value::Integer(current.checked_add(sum)?),
                         ));
                     }
                 }
 
                 // Паттерн 2: acc = acc * factor (де factor не залежить від loop_var)
-                if let Expression::Binary { left, op: BinaryOp::Mul, right } = value {
+                if let Expression::Binary { left, op: BinaryOp::Mul, right, .. } = value {
                     if self.is_ident(left, target_name) {
                         if let Expression::Literal(Literal::Integer(factor)) = right.as_ref() {
-                            let n = to - from;
+                            let n = to.checked_sub(from)?;
                             if n <= 0 { return None; }
                             let current = self.current_env.borrow().get(target_name)
                                 .and_then(|v| if let Value::Integer(n) = v { Some(n) } else { None })
                                 .unwrap_or(1);
-                            // acc * factor^n
-                            let result = current * factor.pow(n as u32);
+                            // acc * factor^n; при переповненні цикл виконується звичайно й повідомляє про нього
+                            let result = current.checked_mul(factor.checked_pow(u32::try_from(n).ok()?)?)?;
                             return Some(LoopOptResult::SetVariable(
                                 target_name.clone(),
                                 Value::Integer(result),
@@ -2479,38 +3488,39 @@ impl VM {
                 }
 
                 // Паттерн 3: acc = acc + 1 (простий лічильник)
-                if let Expression::Binary { left, op: BinaryOp::Add, right } = value {
+                if let Expression::Binary { left, op: BinaryOp::Add, right, .. } = value {
                     if self.is_ident(left, target_name) {
                         if let Expression::Literal(Literal::Integer(1)) = right.as_ref() {
-                            let n = to - from;
+                            let n = to.checked_sub(from)?;
                             if n <= 0 { return None; }
                             let current = self.current_env.borrow().get(target_name)
                                 .and_then(|v| if let Value::Integer(n) = v { Some(n) } else { None })
                                 .unwrap_or(0);
                             return Some(LoopOptResult::SetVariable(
                                 target_name.clone(),
-                                Value::Integer(current + n),
+                                Value::Integer(current.checked_add(n)?),
                             ));
                         }
                     }
                 }
 
                 // Паттерн 4: acc = acc + i * i (сума квадратів)
-                if let Expression::Binary { left, op: BinaryOp::Add, right } = value {
+                if let Expression::Binary { left, op: BinaryOp::Add, right, .. } = value {
                     if self.is_ident(left, target_name) {
-                        if let Expression::Binary { left: ml, op: BinaryOp::Mul, right: mr } = right.as_ref() {
+                        if let Expression::Binary { left: ml, op: BinaryOp::Mul, right: mr, .. } = right.as_ref() {
                             if self.is_ident(ml, loop_var) && self.is_ident(mr, loop_var) {
-                                let n = to - from;
-                                if n <= 0 { return None; }
+                                if to <= from { return None; }
                                 // Формула суми квадратів: Σi² від a до b
-                                let sum_sq_to = |m: i64| -> i64 { m * (m + 1) * (2 * m + 1) / 6 };
-                                let sum = sum_sq_to(to - 1) - sum_sq_to(from - 1);
+                                let sum_sq_to = |m: i128| -> Option<i128> {
+                                    m.checked_mul(m + 1)?.checked_mul(2 * m + 1).map(|p| p / 6)
+                                };
+                                let sum = sum_sq_to(to as i128 - 1)? - sum_sq_to(from as i128 - 1)?;
                                 let current = self.current_env.borrow().get(target_name)
                                     .and_then(|v| if let Value::Integer(n) = v { Some(n) } else { None })
                                     .unwrap_or(0);
                                 return Some(LoopOptResult::SetVariable(
                                     target_name.clone(),
-                                    Value::Integer(current + sum),
+                                    Value::Integer(current.checked_add(i64::try_from(sum).ok()?)?),
                                 ));
                             }
                         }
@@ -2520,18 +3530,16 @@ impl VM {
         }
 
         // Паттерн з AssignmentOp::AddAssign: acc += i
-        if let Statement::Assignment { target, value, op: AssignmentOp::AddAssign } = stmt {
+        if let Statement::Assignment { target, value, op: AssignmentOp::AddAssign, .. } = stmt {
             if let Expression::Identifier(target_name) = target {
                 if self.is_ident_expr(value, loop_var) {
-                    let n = to - from;
-                    if n <= 0 { return None; }
-                    let sum = n * (from + to - 1) / 2;
+                    let sum = range_sum(from, to)?;
                     let current = self.current_env.borrow().get(target_name)
                         .and_then(|v| if let Value::Integer(n) = v { Some(n) } else { None })
                         .unwrap_or(0);
                     return Some(LoopOptResult::SetVariable(
                         target_name.clone(),
-                        Value::Integer(current + sum),
+                        Value::Integer(current.checked_add(sum)?),
                     ));
                 }
             }
@@ -3257,6 +4265,41 @@ impl VM {
         trace
     }
 
+    /// Помилка вводу-виводу з українським описом причини
+    fn io_error(action: &str, path: &str, e: std::io::Error) -> anyhow::Error {
+        let reason = match e.kind() {
+            std::io::ErrorKind::NotFound => "файл або каталог не існує".to_string(),
+            std::io::ErrorKind::PermissionDenied => "немає прав доступу".to_string(),
+            std::io::ErrorKind::AlreadyExists => "файл вже існує".to_string(),
+            _ => e.to_string(),
+        };
+        anyhow::anyhow!("Помилка вводу-виводу: не вдалося {} '{}': {}", action, path, reason)
+    }
+
+    /// Перетворює індекс у позицію: від'ємний рахується з кінця (`[-1]` — останній)
+    fn normalize_index(i: i64, len: usize) -> Result<usize> {
+        let idx = if i < 0 { len as i64 + i } else { i };
+        if idx < 0 || idx >= len as i64 {
+            return Err(anyhow::anyhow!("Індекс {} поза межами (довжина {})", i, len));
+        }
+        Ok(idx as usize)
+    }
+
+    /// Нульове значення типу (для неініціалізованих масивів фіксованого розміру)
+    fn zero_value(ty: &tryzub_parser::Type) -> Value {
+        use tryzub_parser::Type;
+        match ty {
+            Type::Цл8 | Type::Цл16 | Type::Цл32 | Type::Цл64 |
+            Type::Чс8 | Type::Чс16 | Type::Чс32 | Type::Чс64 => Value::Integer(0),
+            Type::Дрб32 | Type::Дрб64 => Value::Float(0.0),
+            Type::Лог => Value::Bool(false),
+            Type::Тхт => Value::String(String::new()),
+            Type::Сим => Value::Char('\0'),
+            Type::Array(elem, size) => Value::Array(vec![Self::zero_value(elem); *size].into()),
+            _ => Value::Null,
+        }
+    }
+
     fn check_type(&self, value: &Value, expected: &tryzub_parser::Type) -> Result<()> {
         use tryzub_parser::Type;
         let ok = match expected {
@@ -3266,7 +4309,20 @@ impl VM {
             Type::Лог => matches!(value, Value::Bool(_)),
             Type::Тхт => matches!(value, Value::String(_)),
             Type::Сим => matches!(value, Value::Char(_)),
-            Type::Slice(_) | Type::Array(_, _) => matches!(value, Value::Array(_)),
+            Type::Slice(_) => matches!(value, Value::Array(_)),
+            Type::Array(_, size) => {
+                if let Value::Array(items) = value {
+                    if items.len() != *size {
+                        return Err(anyhow::anyhow!(
+                            "Невідповідність розміру масиву: очікувалось {} елементів, отримано {}",
+                            size, items.len()
+                        ));
+                    }
+                    true
+                } else {
+                    false
+                }
+            }
             Type::Tuple(_) => matches!(value, Value::Tuple(_)),
             Type::Named(name) => {
                 match value {
@@ -3412,17 +4468,37 @@ impl VM {
                 println!("{}", parts.join(" "));
                 Ok(Value::Null)
             }
+            // друкф("{:05} {:.2}", а, б) — як друк, але за шаблоном
+            "друкф" | "форматувати" => {
+                let Some(Value::String(template)) = args.first() else {
+                    return Err(anyhow::anyhow!("Помилка типу: {} очікує рядок-шаблон першим аргументом", name));
+                };
+                let text = Self::format_template(template, &args[1..])?;
+                if name == "друкф" {
+                    println!("{}", text);
+                    Ok(Value::Null)
+                } else {
+                    Ok(Value::String(text))
+                }
+            }
             "цілеврядок" => {
                 match args.first() {
                     Some(v) => Ok(Value::String(v.to_display_string())),
                     None => Err(anyhow::anyhow!("цілеврядок очікує 1 аргумент")),
                 }
             }
+            "великі" | "малі" => {
+                match args.first() {
+                    Some(Value::String(s)) => Ok(Value::String(change_case(s, name == "великі"))),
+                    _ => Err(anyhow::anyhow!("Помилка типу: {} очікує рядок", name)),
+                }
+            }
             "довжина" => {
                 match args.first() {
                     Some(Value::Array(arr)) => Ok(Value::Integer(arr.len() as i64)),
+                    Some(Value::Tuple(items)) => Ok(Value::Integer(items.len() as i64)),
                     Some(Value::String(s)) => Ok(Value::Integer(s.chars().count() as i64)),
-                    _ => Err(anyhow::anyhow!("довжина підтримує масиви та рядки")),
+                    _ => Err(anyhow::anyhow!("довжина підтримує масиви, кортежі та рядки")),
                 }
             }
             "тип_значення" => {
@@ -3431,6 +4507,30 @@ impl VM {
                     None => Err(anyhow::anyhow!("тип_значення очікує 1 аргумент")),
                 }
             }
+            "тип" => {
+                match args.first() {
+                    Some(v) => Ok(Value::String(v.kind_name().to_string())),
+                    None => Err(anyhow::anyhow!("тип очікує 1 аргумент")),
+                }
+            }
+            "влог" => {
+                match args.first() {
+                    Some(v) => Ok(Value::Bool(v.to_bool())),
+                    None => Err(anyhow::anyhow!("влог очікує 1 аргумент")),
+                }
+            }
+            "врядкувлог" => {
+                match args.first() {
+                    Some(Value::String(s)) => match s.trim() {
+                        "істина" => Ok(Value::Bool(true)),
+                        "хиба" => Ok(Value::Bool(false)),
+                        other => Err(anyhow::anyhow!(
+                            "Помилка значення: '{}' не є логічним (очікувалось \"істина\" або \"хиба\")", other
+                        )),
+                    },
+                    _ => Err(anyhow::anyhow!("врядкувлог очікує рядок")),
+                }
+            }
             "паніка" => {
                 let msg = args.first().map(|v| v.to_display_string()).unwrap_or_default();
                 let trace = self.format_stack_trace();
@@ -3470,15 +4570,36 @@ impl VM {
             }
 
             // ── Опція/Результат конструктори ──
-            "Деякий" => Ok(Value::EnumVariant {
+            "Деякий" | "щось" => Ok(Value::EnumVariant {
                 type_name: "Опція".to_string(), variant: "Деякий".to_string(), fields: args,
             }),
-            "Успіх" => Ok(Value::EnumVariant {
+            "Успіх" | "успіх" => Ok(Value::EnumVariant {
                 type_name: "Результат".to_string(), variant: "Успіх".to_string(), fields: args,
             }),
-            "Помилка" => Ok(Value::EnumVariant {
+            "Помилка" | "помилка" => Ok(Value::EnumVariant {
                 type_name: "Результат".to_string(), variant: "Помилка".to_string(), fields: args,
             }),
+            "є_помилкою" => match args.first() {
+                Some(Value::EnumVariant { variant, .. }) if variant == "Помилка" || variant == "Нічого" => Ok(Value::Bool(true)),
+                Some(Value::EnumVariant { variant, .. }) if variant == "Успіх" || variant == "Деякий" => Ok(Value::Bool(false)),
+                _ => Err(anyhow::anyhow!("є_помилкою очікує Результат або Опцію")),
+            },
+            // Значення успіху або паніка, якщо його немає
+            "розгорнути" => match args.into_iter().next() {
+                Some(Value::EnumVariant { variant, fields, .. }) if variant == "Успіх" || variant == "Деякий" => {
+                    Ok(fields.into_iter().next().unwrap_or(Value::Null))
+                }
+                Some(Value::EnumVariant { variant, fields, .. }) if variant == "Помилка" => {
+                    let msg = fields.first().map(|v| v.to_display_string()).unwrap_or_default();
+                    let trace = self.format_stack_trace();
+                    Err(anyhow::anyhow!("Паніка: розгорнути() на помилці: {}\n{}", msg, trace))
+                }
+                Some(Value::EnumVariant { variant, .. }) if variant == "Нічого" => {
+                    let trace = self.format_stack_trace();
+                    Err(anyhow::anyhow!("Паніка: розгорнути() на ніщо\n{}", trace))
+                }
+                _ => Err(anyhow::anyhow!("розгорнути очікує Результат або Опцію")),
+            },
 
             // ── Колекції: повна реалізація з каррінгом для pipeline ──
 
@@ -3491,7 +4612,7 @@ impl VM {
                         let cond = self.call_value(func.clone(), vec![item.clone()])?;
                         if cond.to_bool() { result.push(item); }
                     }
-                    Ok(Value::Array(result))
+                    Ok(Value::Array(result.into()))
                 } else if args.len() == 1 {
                     Ok(self.curry_builtin("фільтрувати", args))
                 } else { Err(anyhow::anyhow!("фільтрувати очікує 1-2 аргументи")) }
@@ -3504,7 +4625,7 @@ impl VM {
                     for item in arr {
                         result.push(self.call_value(func.clone(), vec![item])?);
                     }
-                    Ok(Value::Array(result))
+                    Ok(Value::Array(result.into()))
                 } else if args.len() == 1 {
                     Ok(self.curry_builtin("перетворити", args))
                 } else { Err(anyhow::anyhow!("перетворити очікує 1-2 аргументи")) }
@@ -3521,20 +4642,12 @@ impl VM {
                 } else { Err(anyhow::anyhow!("згорнути очікує 2-3 аргументи")) }
             }
             "сортувати" => {
-                match args.first() {
-                    Some(Value::Array(arr)) => {
-                        let mut sorted = arr.clone();
-                        sorted.sort_by(|a, b| {
-                            match (a, b) {
-                                (Value::Integer(x), Value::Integer(y)) => x.cmp(y),
-                                (Value::Float(x), Value::Float(y)) => x.partial_cmp(y).unwrap_or(std::cmp::Ordering::Equal),
-                                (Value::String(x), Value::String(y)) => x.cmp(y),
-                                _ => std::cmp::Ordering::Equal,
-                            }
-                        });
-                        Ok(Value::Array(sorted))
+                match (args.first(), args.len()) {
+                    (Some(Value::Array(arr)), 1 | 2) => {
+                        Ok(Value::Array(self.sort_values(arr.to_vec(), args.get(1).cloned())?.into()))
                     }
-                    _ => Err(anyhow::anyhow!("сортувати очікує масив")),
+                    (Some(_), 1) => Ok(self.curry_builtin("сортувати", args)),
+                    _ => Err(anyhow::anyhow!("сортувати очікує масив і, за бажанням, компаратор")),
                 }
             }
             "обернути" => {
@@ -3550,6 +4663,33 @@ impl VM {
                     _ => Err(anyhow::anyhow!("обернути очікує масив або рядок")),
                 }
             }
+            "повторити" => {
+                match (args.first(), args.get(1)) {
+                    (Some(Value::String(s)), Some(Value::Integer(n))) => {
+                        Ok(Value::String(s.repeat(Self::repeat_count("повторити", *n)?)))
+                    }
+                    _ => Err(anyhow::anyhow!("повторити очікує (рядок, кількість: цл64)")),
+                }
+            }
+            "повторити_масив" => {
+                match (args.first(), args.get(1)) {
+                    (Some(Value::Array(arr)), Some(Value::Integer(n))) => {
+                        let count = Self::repeat_count("повторити_масив", *n)?;
+                        Ok(Value::Array(arr.iter().cloned().cycle().take(arr.len() * count).collect()))
+                    }
+                    _ => Err(anyhow::anyhow!("повторити_масив очікує (масив, кількість: цл64)")),
+                }
+            }
+            // Зворотне до розділити: елементи приводяться до рядка
+            "з'єднати" => {
+                match (args.first(), args.get(1)) {
+                    (Some(Value::Array(arr)), Some(Value::String(sep))) => {
+                        let parts: Vec<String> = arr.iter().map(|v| v.to_display_string()).collect();
+                        Ok(Value::String(parts.join(sep)))
+                    }
+                    _ => Err(anyhow::anyhow!("з'єднати очікує (масив, роздільник: тхт)")),
+                }
+            }
             "додати" => {
                 // додати(масив, елемент)
                 if args.len() == 2 {
@@ -3604,7 +4744,8 @@ impl VM {
             }
             "абс" => {
                 match args.first() {
-                    Some(Value::Integer(n)) => Ok(Value::Integer(n.abs())),
+                    Some(Value::Integer(n)) => n.checked_abs().map(Value::Integer)
+                        .ok_or_else(|| anyhow::anyhow!("Помилка значення: переповнення цл64 у абс({})", n)),
                     Some(Value::Float(f)) => Ok(Value::Float(f.abs())),
                     _ => Err(anyhow::anyhow!("абс очікує число")),
                 }
@@ -3657,15 +4798,15 @@ impl VM {
                 let now = std::time::SystemTime::now()
                     .duration_since(std::time::UNIX_EPOCH)
                     .unwrap_or_default();
-                Ok(Value::Float(now.as_secs_f64() * 1000.0)) // мілісекунди
+                Ok(Value::Integer(now.as_millis() as i64)) // Unix-час у мілісекундах
             }
-            "час_затримка" => {
+            "спати" | "час_затримка" => {
                 match args.first() {
-                    Some(Value::Integer(ms)) => {
+                    Some(Value::Integer(ms)) if *ms >= 0 => {
                         std::thread::sleep(std::time::Duration::from_millis(*ms as u64));
                         Ok(Value::Null)
                     }
-                    _ => Err(anyhow::anyhow!("час_затримка очікує мілісекунди")),
+                    _ => Err(anyhow::anyhow!("{} очікує невід'ємну кількість мілісекунд (цл64)", name)),
                 }
             }
 
@@ -3679,7 +4820,7 @@ impl VM {
                             let result = self.call_value(func.clone(), vec![])?;
                             results.push(result);
                         }
-                        Ok(Value::Array(results))
+                        Ok(Value::Array(results.into()))
                     }
                     _ => Err(anyhow::anyhow!("все() очікує масив функцій")),
                 }
@@ -3699,6 +4840,35 @@ impl VM {
                     _ => Err(anyhow::anyhow!("перегони() очікує масив функцій")),
                 }
             }
+            "паралельно" => {
+                // паралельно([ф1, ф2]) — кожна функція виконується на потоці з пулу,
+                // результати повертаються в тому ж порядку
+                match args.first() {
+                    Some(Value::Array(funcs)) => {
+                        if IN_POOL_WORKER.with(|w| w.get()) {
+                            // Вкладений виклик виконуємо послідовно, щоб не заблокувати пул
+                            let results = funcs.iter()
+                                .map(|f| self.call_value(f.clone(), vec![]))
+                                .collect::<Result<Vec<_>>>()?;
+                            return Ok(Value::Array(results.into()));
+                        }
+                        let tasks = funcs.iter()
+                            .map(|f| self.make_send_task(f, vec![]))
+                            .collect::<Result<Vec<_>>>()?;
+                        let started = tasks.into_iter()
+                            .map(|task| self.start_task(task))
+                            .collect::<Result<Vec<_>>>()?;
+                        let mut results = Vec::with_capacity(started.len());
+                        for (index, (id, slot)) in started.into_iter().enumerate() {
+                            let value = self.finish_task(id, slot)
+                                .map_err(|e| anyhow::anyhow!("Помилка у паралельній задачі {}: {}", index, e))?;
+                            results.push(value.into_value());
+                        }
+                        Ok(Value::Array(results.into()))
+                    }
+                    _ => Err(anyhow::anyhow!("паралельно() очікує масив функцій")),
+                }
+            }
             "потік" => {
                 // потік(функція) — запускає функцію в окремому потоці
                 // Повертає результат після завершення (join)
@@ -3744,10 +4914,58 @@ impl VM {
                 }
             }
             "канал" => {
-                // канал() — повертає пару [відправник, отримувач]
-                // В поточній реалізації — через спільний масив
-                let buffer = Value::Array(vec![]);
-                Ok(Value::Array(vec![buffer.clone(), buffer]))
+                // канал() — повертає кортеж (відправник, отримувач); дескриптори можна
+                // передавати в паралельно() та async-функції
+                let id = NEXT_CHANNEL_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                let (sender, receiver) = crossbeam_channel::unbounded();
+                Ok(Value::Tuple(vec![
+                    Value::Channel(Arc::new(ChannelEnd::Sender { id, sender: Mutex::new(Some(sender)) })),
+                    Value::Channel(Arc::new(ChannelEnd::Receiver { id, receiver })),
+                ]))
+            }
+            "надіслати" => {
+                let (id, sender) = Self::channel_sender(args.first(), "надіслати")?;
+                let value = args.get(1).ok_or_else(|| anyhow::anyhow!("надіслати очікує (відправник, значення)"))?;
+                let value = SendValue::from_value(value).ok_or_else(|| anyhow::anyhow!(
+                    "Значення типу '{}' не можна надіслати в канал", value.kind_name()
+                ))?;
+                let sender = sender.lock().unwrap_or_else(|e| e.into_inner()).clone()
+                    .ok_or_else(|| anyhow::anyhow!("Канал {} закрито", id))?;
+                sender.send(value).map_err(|_| anyhow::anyhow!("Канал {} закрито", id))?;
+                Ok(Value::Null)
+            }
+            "отримати" if matches!(args.first(), Some(Value::Array(_))) => {
+                // Безпечний доступ до масиву: вихід за межі дає ніщо замість помилки
+                match (&args[0], args.get(1)) {
+                    (Value::Array(arr), Some(Value::Integer(i))) => Ok(match Self::normalize_index(*i, arr.len()) {
+                        Ok(idx) => Value::EnumVariant {
+                            type_name: "Опція".to_string(), variant: "Деякий".to_string(), fields: vec![arr[idx].clone()],
+                        },
+                        Err(_) => Value::EnumVariant {
+                            type_name: "Опція".to_string(), variant: "Нічого".to_string(), fields: vec![],
+                        },
+                    }),
+                    _ => Err(anyhow::anyhow!("Помилка типу: отримати(масив, індекс) очікує цілий індекс")),
+                }
+            }
+            "отримати" => {
+                // Блокує до появи значення; закритий і порожній канал — помилка
+                let (id, receiver) = match args.first() {
+                    Some(Value::Channel(end)) => match &**end {
+                        ChannelEnd::Receiver { id, receiver } => (*id, receiver.clone()),
+                        ChannelEnd::Sender { .. } => return Err(anyhow::anyhow!("отримати() очікує отримувач каналу")),
+                    },
+                    _ => return Err(anyhow::anyhow!("отримати() очікує отримувач каналу")),
+                };
+                receiver.recv()
+                    .map(SendValue::into_value)
+                    .map_err(|_| anyhow::anyhow!("Канал {} закрито", id))
+            }
+            "закрити_канал" => {
+                // Після закриття отримувач ще дочитує надіслані значення
+                let (_, sender) = Self::channel_sender(args.first(), "закрити_канал")?;
+                sender.lock().unwrap_or_else(|e| e.into_inner()).take();
+                Ok(Value::Null)
             }
 
             // ── JSON ──
@@ -3891,6 +5109,23 @@ impl VM {
                     } else { Err(anyhow::anyhow!("файл_записати очікує (шлях, зміст)")) }
                 } else { Err(anyhow::anyhow!("файл_записати очікує 2 аргументи")) }
             }
+            // Варіанти, що кидають помилку вводу-виводу (ловиться через спробувати/зловити)
+            "читати_файл" => {
+                match args.first() {
+                    Some(Value::String(path)) => std::fs::read_to_string(path)
+                        .map(Value::String)
+                        .map_err(|e| Self::io_error("прочитати", path, e)),
+                    _ => Err(anyhow::anyhow!("читати_файл очікує шлях (тхт)")),
+                }
+            }
+            "записати_файл" => {
+                match (args.first(), args.get(1)) {
+                    (Some(Value::String(path)), Some(content)) => std::fs::write(path, content.to_display_string())
+                        .map(|_| Value::Null)
+                        .map_err(|e| Self::io_error("записати", path, e)),
+                    _ => Err(anyhow::anyhow!("записати_файл очікує (шлях, вміст)")),
+                }
+            }
             "файл_існує" => {
                 match args.first() {
                     Some(Value::String(path)) => Ok(Value::Bool(std::path::Path::new(path).exists())),
@@ -3906,7 +5141,7 @@ impl VM {
                                 Ok(Value::EnumVariant {
                                     type_name: "Результат".to_string(),
                                     variant: "Успіх".to_string(),
-                                    fields: vec![Value::Array(lines)],
+                                    fields: vec![Value::Array(lines.into())],
                                 })
                             }
                             Err(e) => Ok(Value::EnumVariant {
@@ -4416,7 +5651,7 @@ impl VM {
                     }).map_err(|e| anyhow::anyhow!("SQL: {}", e))?;
 
                     let results: Vec<Value> = rows.filter_map(|r| r.ok()).collect();
-                    Ok(Value::Array(results))
+                    Ok(Value::Array(results.into()))
                 } else {
                     Err(anyhow::anyhow!("Немає відкритої бази даних"))
                 }
@@ -4514,7 +5749,7 @@ impl VM {
                                 Ok(Value::Dict(pairs))
                             }).map_err(|e| anyhow::anyhow!("SQL: {}", e))?;
                             let results: Vec<Value> = rows.filter_map(|r| r.ok()).collect();
-                            Ok(Value::Array(results))
+                            Ok(Value::Array(results.into()))
                         } else {
                             let affected = db.execute(sql, param_refs.as_slice())
                                 .map_err(|e| anyhow::anyhow!("SQL: {}", e))?;
@@ -4823,8 +6058,21 @@ impl VM {
                 }
                 Ok(Value::Dict(pairs))
             }
+            // множина([1, 2, 2]) або множина(1, 2, 2) — дублікати відкидаються,
+            // порядок першої появи зберігається
             "множина" => {
-                Ok(Value::Set(args))
+                let items = match args.as_slice() {
+                    [Value::Array(arr)] => arr.to_vec(),
+                    [Value::Set(items)] => items.clone(),
+                    _ => args,
+                };
+                let mut unique: Vec<Value> = Vec::with_capacity(items.len());
+                for item in items {
+                    if !unique.iter().any(|v| self.values_equal(v, &item)) {
+                        unique.push(item);
+                    }
+                }
+                Ok(Value::Set(unique))
             }
 
             // ── Регулярні вирази ──
@@ -4848,7 +6096,7 @@ impl VM {
                                 let matches: Vec<Value> = re.find_iter(text)
                                     .map(|m| Value::String(m.as_str().to_string()))
                                     .collect();
-                                Ok(Value::Array(matches))
+                                Ok(Value::Array(matches.into()))
                             }
                             Err(e) => Err(anyhow::anyhow!("Невалідний regex: {}", e)),
                         }
@@ -5084,7 +6332,7 @@ impl VM {
                                 }
                             }
                         }
-                        Ok(Value::Array(open_ports))
+                        Ok(Value::Array(open_ports.into()))
                     } else { Err(anyhow::anyhow!("сканувати_порти(хост, від, до)")) }
                 } else { Err(anyhow::anyhow!("сканувати_порти очікує 3 аргументи")) }
             }
@@ -5124,9 +6372,9 @@ impl VM {
                                 let ips: Vec<Value> = addrs
                                     .map(|a| Value::String(a.ip().to_string()))
                                     .collect();
-                                Ok(Value::Array(ips))
+                                Ok(Value::Array(ips.into()))
                             }
-                            Err(e) => Ok(Value::Array(vec![Value::String(format!("Помилка: {}", e))])),
+                            Err(e) => Ok(Value::Array(vec![Value::String(format!("Помилка: {}", e))].into())),
                         }
                     }
                     _ => Err(anyhow::anyhow!("dns_запит очікує домен")),
@@ -5207,7 +6455,7 @@ impl VM {
                     Ok(Value::Dict(vec![
                         (Value::String("тестовано".into()), Value::Integer(tested as i64)),
                         (Value::String("падінь".into()), Value::Integer(crashes.len() as i64)),
-                        (Value::String("деталі".into()), Value::Array(crashes)),
+                        (Value::String("деталі".into()), Value::Array(crashes.into())),
                     ]))
                 } else { Err(anyhow::anyhow!("фазити(функція, кількість)")) }
             }
@@ -5238,7 +6486,7 @@ impl VM {
                         }
                         Ok(Value::Dict(vec![
                             (Value::String("безпечно".into()), Value::Bool(issues.is_empty())),
-                            (Value::String("вразливості".into()), Value::Array(issues)),
+                            (Value::String("вразливості".into()), Value::Array(issues.into())),
                         ]))
                     }
                     _ => Err(anyhow::anyhow!("аудит_рядок очікує рядок")),
@@ -5433,7 +6681,7 @@ impl VM {
                             (Value::String("оцінка".into()), Value::Integer(score)),
                             (Value::String("довжина".into()), Value::Integer(len as i64)),
                             (Value::String("кирилиця".into()), Value::Bool(has_cyrillic)),
-                            (Value::String("рекомендації".into()), Value::Array(recommendations)),
+                            (Value::String("рекомендації".into()), Value::Array(recommendations.into())),
                         ]))
                     }
                     _ => Err(anyhow::anyhow!("перевірити_пароль очікує рядок")),
@@ -5477,7 +6725,7 @@ impl VM {
                                         Err(_) => break,
                                     }
                                 }
-                                Ok(Value::Array(connections))
+                                Ok(Value::Array(connections.into()))
                             }
                             Err(e) => Err(anyhow::anyhow!("Хонейпот: {}", e)),
                         }
@@ -5503,7 +6751,7 @@ impl VM {
                         (Value::String("тип".into()), Value::String(format!("{:?}", p.port_type))),
                     ])
                 }).collect();
-                Ok(Value::Array(result))
+                Ok(Value::Array(result.into()))
             }
 
             "serial_відкрити" => {
@@ -5751,10 +6999,36 @@ impl VM {
                     let num = match &args[0] { Value::Integer(n) => *n, _ => 0 };
                     let count = args.get(1).and_then(|v| if let Value::Integer(n) = v { Some(*n) } else { None }).unwrap_or(4) as usize;
                     let bytes: Vec<Value> = (0..count).map(|i| Value::Integer((num >> (i * 8)) & 0xFF)).collect();
-                    Ok(Value::Array(bytes))
+                    Ok(Value::Array(bytes.into()))
                 } else { Err(anyhow::anyhow!("число_в_байти(число)")) }
             }
 
+            "врядок_байти" => {
+                // врядок_байти(рядок) — UTF-8 байти рядка
+                match args.first() {
+                    Some(Value::String(s)) => {
+                        let bytes: Vec<Value> = s.bytes().map(|b| Value::Integer(b as i64)).collect();
+                        Ok(Value::Array(bytes.into()))
+                    }
+                    _ => Err(anyhow::anyhow!("Помилка типу: врядок_байти очікує рядок")),
+                }
+            }
+
+            "збайтів_рядок" => {
+                // збайтів_рядок(масив) — рядок з UTF-8 байтів
+                let Some(Value::Array(items)) = args.first() else {
+                    return Err(anyhow::anyhow!("Помилка типу: збайтів_рядок очікує масив байтів"));
+                };
+                let bytes = items.iter().map(|v| match v {
+                    Value::Integer(n) => u8::try_from(*n)
+                        .map_err(|_| anyhow::anyhow!("Помилка значення: {} не є байтом (0..255)", n)),
+                    other => Err(anyhow::anyhow!("Помилка типу: збайтів_рядок очікує цілі числа, отримано {}", other.type_name())),
+                }).collect::<Result<Vec<u8>>>()?;
+                String::from_utf8(bytes).map(Value::String).map_err(|e| anyhow::anyhow!(
+                    "Помилка значення: невалідна UTF-8 послідовність на байті {}", e.utf8_error().valid_up_to()
+                ))
+            }
+
             "біт_встановити" => {
                 // біт_встановити(число, позиція, значення) — встановлює біт
                 if args.len() >= 3 {
@@ -5889,7 +7163,7 @@ impl VM {
                 let norm: f64 = a.iter().map(|x| x * x).sum::<f64>().sqrt();
                 if norm == 0.0 { return Ok(args[0].clone()); }
                 let normalized: Vec<Value> = a.iter().map(|x| Value::Float(x / norm)).collect();
-                Ok(Value::Array(normalized))
+                Ok(Value::Array(normalized.into()))
             }
             "вектор_евклідова_відстань" => {
                 let a = self.value_to_float_vec(&args[0]);
@@ -5923,7 +7197,7 @@ impl VM {
                     map.push((Value::String("подібність".to_string()), Value::Float(*sim)));
                     Value::Dict(map)
                 }).collect();
-                Ok(Value::Array(results))
+                Ok(Value::Array(results.into()))
             }
 
             // ── Vector Index ──
@@ -5960,7 +7234,7 @@ impl VM {
                     map.push((Value::String("подібність".to_string()), Value::Float(*sim)));
                     Value::Dict(map)
                 }).collect();
-                Ok(Value::Array(results))
+                Ok(Value::Array(results.into()))
             }
 
             // ── VIN парсер ──
@@ -6050,7 +7324,7 @@ impl VM {
                     start = start + pos + delimiter.len();
                     if body.get(start) == Some(&b'\r') { start += 2; }
                 }
-                Ok(Value::Array(parts))
+                Ok(Value::Array(parts.into()))
             }
 
             "зображення_розмір" => {
@@ -6159,15 +7433,15 @@ impl VM {
                     b_channel.push(Value::Float((pixel[2] as f64 / 255.0 - 0.406) / 0.225));
                 }
                 let tensor = vec![
-                    Value::Array(r_channel),
-                    Value::Array(g_channel),
-                    Value::Array(b_channel),
+                    Value::Array(r_channel.into()),
+                    Value::Array(g_channel.into()),
+                    Value::Array(b_channel.into()),
                 ];
                 let mut result = Vec::new();
-                result.push((Value::String("тензор".to_string()), Value::Array(tensor)));
+                result.push((Value::String("тензор".to_string()), Value::Array(tensor.into())));
                 result.push((Value::String("форма".to_string()), Value::Array(vec![
                     Value::Integer(3), Value::Integer(height as i64), Value::Integer(width as i64)
-                ])));
+                ].into())));
                 Ok(Value::Dict(result))
             }
 
@@ -6262,7 +7536,7 @@ except Exception as e:
                 match serde_json::from_str::<serde_json::Value>(&stdout) {
                     Ok(serde_json::Value::Array(arr)) => {
                         let vec: Vec<Value> = arr.iter().filter_map(|v| v.as_f64().map(Value::Float)).collect();
-                        Ok(Value::Array(vec))
+                        Ok(Value::Array(vec.into()))
                     }
                     Ok(serde_json::Value::Object(obj)) => {
                         if let Some(err) = obj.get("error") {
@@ -6667,6 +7941,61 @@ except Exception as e:
         }
     }
 
+    /// Реєструє член блоку `реалізація` як `Тип::ім'я`: метод або статичне поле
+    fn register_impl_member(&mut self, for_type: &str, member: Declaration) -> Result<()> {
+        self.impl_types.insert(for_type.to_string());
+        match member {
+            Declaration::Function { name, generic_params, params, return_type, body, is_static, is_async, .. } => {
+                if is_static {
+                    self.static_methods.insert(format!("{}::{}", for_type, name));
+                }
+                let func = Value::Function {
+                    name: Some(name.clone()),
+                    generic_params,
+                    params,
+                    return_type,
+                    body: body.clone(),
+                    closure: self.current_env.clone(),
+                    is_async,
+                };
+                self.current_env.borrow_mut().set(format!("{}::{}", for_type, name), func);
+                self.trait_methods.insert((for_type.to_string(), name), body);
+            }
+            // Статичне поле: статичний стала ІМ'Я = значення
+            Declaration::Variable { name, value, .. } => {
+                let val = match value {
+                    Some(expr) => self.evaluate_expression(expr)?,
+                    None => Value::Null,
+                };
+                self.current_env.borrow_mut().set(format!("{}::{}", for_type, name), val);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Ім'я типу з виразу-ідентифікатора (для статичних звернень `Тип.член`)
+    fn type_name_of(expr: &Expression) -> Option<&str> {
+        match expr {
+            Expression::Identifier(name) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Шукає статичний член `Тип::член`, якщо ідентифікатор називає тип з реалізацією
+    /// і не перекритий змінною
+    fn resolve_static_member(&self, object: &Expression, member: &str) -> Option<Value> {
+        let type_name = Self::type_name_of(object)?;
+        if !self.impl_types.contains(type_name) {
+            return None;
+        }
+        let env = self.current_env.borrow();
+        if env.get(type_name).is_some() {
+            return None;
+        }
+        env.get(&format!("{}::{}", type_name, member))
+    }
+
     /// Каррінг: зберігає аргументи та повертає CurriedBuiltin
     fn curry_builtin(&self, name: &str, args: Vec<Value>) -> Value {
         Value::CurriedBuiltin {
@@ -6701,6 +8030,24 @@ except Exception as e:
         }
     }
 
+    /// Член модуля; вбудований `математика` без `імпорт` довантажує решту
+    /// членів зі stdlib при першому зверненні до відсутнього
+    fn module_member(&mut self, module: &str, members: &HashMap<String, Value>, member: &str) -> Result<Option<Value>> {
+        if let Some(value) = members.get(member) {
+            return Ok(Some(value.clone()));
+        }
+        if module != MATH_MODULE {
+            return Ok(None);
+        }
+        if !self.loaded_modules.contains_key(MATH_MODULE) {
+            self.load_module(MATH_MODULE)?;
+        }
+        Ok(match self.module_values.get(MATH_MODULE) {
+            Some(Value::Module(_, members)) => members.get(member).cloned(),
+            _ => None,
+        })
+    }
+
     fn load_module(&mut self, name: &str) -> Result<()> {
         // Перевірка циклічних залежностей
         if self.loading_modules.contains(name) {
@@ -6730,7 +8077,7 @@ except Exception as e:
                     self.loading_modules.insert(name.to_string());
 
                     let tokens = tryzub_lexer::tokenize(&source)?;
-                    let program = tryzub_parser::parse(tokens)?;
+                    let program = tryzub_parser::parse(tokens)?.configured();
 
                     // Зберігаємо поточне середовище та створюємо ізольоване для модуля
                     let prev_env = self.current_env.clone();
@@ -6747,6 +8094,14 @@ except Exception as e:
                     for (k, v) in &scope.variables {
                         members.insert(k.clone(), v.clone());
                     }
+                    // stdlib/математика.тризуб загорнуто в `модуль математика { ... }`;
+                    // вбудований модуль дає його члени напряму, разом з нативними
+                    if name == MATH_MODULE {
+                        if let Some(Value::Module(_, inner)) = members.get(name) {
+                            members = inner.clone();
+                        }
+                        members.extend(native_math_members());
+                    }
 
                     // Відновлюємо попереднє середовище
                     self.current_env = prev_env;
@@ -6793,6 +8148,201 @@ except Exception as e:
         Err(anyhow::anyhow!("Ефект '{}::{}' не оброблено — немає активного обробника", effect_name, operation))
     }
 
+    /// Замикання лямбди: локальні змінні копіюються на момент її створення,
+    /// тож подальші зміни ззовні лямбду не зачіпають, а її власні присвоєння
+    /// не виходять назовні. Глобальні імена та змінні, оголошені пізніше
+    /// (наприклад, сама лямбда для рекурсії), шукаються в живому середовищі.
+    fn capture_environment(&self) -> Environment {
+        let mut snapshot = Scope::new(Some(self.current_env.clone()));
+        let mut env = Some(self.current_env.clone());
+        while let Some(current) = env {
+            if Rc::ptr_eq(&current, &self.global_env) {
+                break;
+            }
+            let scope = current.borrow();
+            for (name, value) in &scope.variables {
+                if !snapshot.variables.contains_key(name) {
+                    snapshot.variables.insert(name.clone(), value.clone());
+                    if scope.constants.contains(name) {
+                        snapshot.constants.insert(name.clone());
+                    }
+                }
+            }
+            env = scope.parent.clone();
+        }
+        Rc::new(RefCell::new(snapshot))
+    }
+
+    /// Готує функцію до виконання в іншому потоці: аргументи та видимі змінні
+    /// копіюються як `SendValue`, глобальні функції робітник відтворить сам
+    fn make_send_task(&self, func: &Value, args: Vec<Value>) -> Result<SendTask> {
+        let (send_func, param_names, closure) = match func {
+            Value::Function { name, generic_params, params, return_type, body, closure, is_async } => (
+                SendFunction::Function {
+                    name: name.clone(),
+                    generic_params: generic_params.clone(),
+                    params: params.clone(),
+                    return_type: return_type.clone(),
+                    body: body.clone(),
+                    is_async: *is_async,
+                },
+                params.iter().map(|p| p.name.clone()).collect::<Vec<_>>(),
+                closure,
+            ),
+            Value::Lambda { params, body, closure } => (
+                SendFunction::Lambda { params: params.clone(), body: body.clone() },
+                params.iter().map(|p| p.name.clone()).collect::<Vec<_>>(),
+                closure,
+            ),
+            other => return Err(anyhow::anyhow!(
+                "Очікувалась функція для виконання в потоці, отримано '{}'", other.kind_name()
+            )),
+        };
+
+        let mut send_args = Vec::new();
+        for (i, arg) in args.iter().enumerate() {
+            let name = param_names.get(i).cloned().unwrap_or_else(|| (i + 1).to_string());
+            send_args.push(SendValue::from_value(arg).ok_or_else(|| anyhow::anyhow!(
+                "Аргумент '{}' типу '{}' не можна передати в інший потік", name, arg.kind_name()
+            ))?);
+        }
+
+        // Параметри затіняють змінні замикання з тими ж іменами
+        let mut bindings = Vec::new();
+        let mut seen: HashSet<String> = param_names.into_iter().collect();
+        // Ближча змінна затіняє дальшу, навіть якщо сама не передається
+        let mut env = Some(closure.clone());
+        while let Some(current) = env {
+            if Rc::ptr_eq(&current, &self.global_env) {
+                break;
+            }
+            let scope = current.borrow();
+            for (name, value) in &scope.variables {
+                if seen.insert(name.clone()) {
+                    if let Some(value) = SendValue::from_value(value) {
+                        bindings.push((name.clone(), value));
+                    }
+                }
+            }
+            env = scope.parent.clone();
+        }
+        // З глобальної області — лише змінні програми: функції й вбудовані
+        // робітник має сам, а глобальних змінних не виконує
+        let globals = self.global_env.borrow();
+        for decl in self.program_decls.iter() {
+            if let Declaration::Variable { name, .. } = decl {
+                if seen.insert(name.clone()) {
+                    if let Some(value) = globals.variables.get(name).and_then(SendValue::from_value) {
+                        bindings.push((name.clone(), value));
+                    }
+                }
+            }
+        }
+        Ok(SendTask { func: send_func, args: send_args, captured: bindings })
+    }
+
+    /// Виконує задачу у VM робочого потоку
+    fn run_send_task(decls: Arc<Vec<Declaration>>, task: SendTask) -> std::result::Result<SendValue, String> {
+        IN_POOL_WORKER.with(|w| w.set(true));
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let cached = WORKER_VM.with(|cell| cell.borrow_mut().take())
+                .filter(|vm| Arc::ptr_eq(&vm.program_decls, &decls));
+            let mut vm = match cached {
+                Some(vm) => vm,
+                None => {
+                    let mut vm = VM::new();
+                    for decl in decls.iter() {
+                        // Глобальні змінні передаються знімком у bindings кожної задачі
+                        if !matches!(decl, Declaration::Variable { .. }) {
+                            vm.execute_declaration(decl.clone())?;
+                        }
+                    }
+                    vm.program_decls = decls.clone();
+                    vm
+                }
+            };
+
+            let mut scope = Scope::new(Some(vm.global_env.clone()));
+            for (name, value) in task.captured {
+                scope.set(name, value.into_value());
+            }
+            let closure = Rc::new(RefCell::new(scope));
+            let func = match task.func {
+                SendFunction::Function { name, generic_params, params, return_type, body, is_async } => {
+                    Value::Function { name, generic_params, params, return_type, body, closure, is_async }
+                }
+                SendFunction::Lambda { params, body } => Value::Lambda { params, body, closure },
+            };
+            let args = task.args.into_iter().map(SendValue::into_value).collect();
+
+            vm.current_env = vm.global_env.clone();
+            let result = vm.call_value(func, args)?;
+            // VM повертається в кеш лише після успішної задачі: помилка могла
+            // лишити його посеред виклику
+            vm.current_env = vm.global_env.clone();
+            WORKER_VM.with(|cell| *cell.borrow_mut() = Some(vm));
+            SendValue::from_value(&result).ok_or_else(|| anyhow::anyhow!(
+                "Результат типу '{}' не можна передати між потоками", result.kind_name()
+            ))
+        }))
+        .unwrap_or_else(|_| Err(anyhow::anyhow!("паніка у робочому потоці")))
+        .map_err(|e| e.to_string())
+    }
+
+    fn channel_sender<'a>(handle: Option<&'a Value>, func: &str) -> Result<(usize, &'a Mutex<Option<crossbeam_channel::Sender<SendValue>>>)> {
+        match handle {
+            Some(Value::Channel(end)) => match &**end {
+                ChannelEnd::Sender { id, sender } => Ok((*id, sender)),
+                ChannelEnd::Receiver { .. } => Err(anyhow::anyhow!("{}() очікує відправник каналу", func)),
+            },
+            _ => Err(anyhow::anyhow!("{}() очікує відправник каналу", func)),
+        }
+    }
+
+    fn task_runtime(&self) -> &Arc<tryzub_runtime::AsyncRuntime> {
+        self.task_runtime.get_or_init(|| Arc::new(tryzub_runtime::AsyncRuntime::new(num_cpus::get())))
+    }
+
+    /// Ставить задачу в пул VM; результат з'явиться в комірці
+    fn start_task(&self, task: SendTask) -> Result<(usize, TaskSlot)> {
+        let slot: TaskSlot = Arc::new(Mutex::new(None));
+        let result_slot = slot.clone();
+        let decls = self.program_decls.clone();
+        let id = self.task_runtime().spawn_task(move || {
+            let value = VM::run_send_task(decls, task).map_err(|e| anyhow::anyhow!(e))?;
+            *result_slot.lock().unwrap_or_else(|e| e.into_inner()) = Some(value);
+            Ok(tryzub_runtime::TryzubValue {
+                value_type: tryzub_runtime::ValueType::Null,
+                data: tryzub_runtime::ValueData { null: () },
+            })
+        })?;
+        Ok((id, slot))
+    }
+
+    /// Блокує до завершення задачі й забирає її результат
+    fn finish_task(&self, id: usize, slot: TaskSlot) -> Result<SendValue> {
+        self.task_runtime().take_task(id)?;
+        let value = slot.lock().unwrap_or_else(|e| e.into_inner()).take();
+        value.ok_or_else(|| anyhow::anyhow!("задача {} завершилась без результату", id))
+    }
+
+    /// Запускає функцію в пулі VM; повертає дескриптор задачі
+    fn spawn_async_task(&mut self, func: &Value, args: Vec<Value>) -> Result<Value> {
+        let task = self.make_send_task(func, args)?;
+        let (id, slot) = self.start_task(task)?;
+        self.async_tasks.insert(id, slot);
+        Ok(Value::Task(id))
+    }
+
+    /// Блокує до завершення задачі; помилка задачі стає помилкою `чекати`
+    fn await_async_task(&mut self, id: usize) -> Result<Value> {
+        let slot = self.async_tasks.remove(&id)
+            .ok_or_else(|| anyhow::anyhow!("Задачу {} не знайдено або її вже очікували", id))?;
+        self.finish_task(id, slot)
+            .map(SendValue::into_value)
+            .map_err(|e| anyhow::anyhow!("Помилка в асинхронній задачі {}: {}", id, e))
+    }
+
     /// Виконує async завдання з черги
     fn drain_async_queue(&mut self) -> Result<()> {
         while let Some((stmts, env)) = self.async_queue.pop() {
@@ -6969,7 +8519,7 @@ except Exception as e:
                     }
                     if let Some(rest_name) = rest {
                         let rest_vals = arr[elements.len()..].to_vec();
-                        self.current_env.borrow_mut().set(rest_name.clone(), Value::Array(rest_vals));
+                        self.current_env.borrow_mut().set(rest_name.clone(), Value::Array(rest_vals.into()));
                     }
                 }
             }
@@ -7017,22 +8567,24 @@ except Exception as e:
     fn apply_binary_op(&self, op: BinaryOp, lhs: Value, rhs: Value) -> Result<Value> {
         match (op, &lhs, &rhs) {
             // Арифметика цілих
-            (BinaryOp::Add, Value::Integer(a), Value::Integer(b)) => Ok(Value::Integer(a + b)),
-            (BinaryOp::Sub, Value::Integer(a), Value::Integer(b)) => Ok(Value::Integer(a - b)),
-            (BinaryOp::Mul, Value::Integer(a), Value::Integer(b)) => Ok(Value::Integer(a * b)),
-            (BinaryOp::Div, Value::Integer(a), Value::Integer(b)) => {
-                if *b == 0 { Err(anyhow::anyhow!("Ділення на нуль")) }
-                else { Ok(Value::Integer(a / b)) }
-            }
-            (BinaryOp::Mod, Value::Integer(a), Value::Integer(b)) => {
-                if *b == 0 { Err(anyhow::anyhow!("Ділення на нуль")) }
-                else { Ok(Value::Integer(a % b)) }
-            }
+            (BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div | BinaryOp::Mod,
+                Value::Integer(a), Value::Integer(b)) => Self::checked_int_op(&op, *a, *b),
+            // Від'ємний показник дає дробове (2 ** -1 == 0.5), але 0 ** -n — це
+            // ділення на нуль, а не нескінченність
             (BinaryOp::Pow, Value::Integer(a), Value::Integer(b)) => {
-                if *b < 0 { Ok(Value::Float((*a as f64).powf(*b as f64))) }
-                else { Ok(Value::Integer(a.pow(*b as u32))) }
+                if *b >= 0 { Self::checked_int_op(&op, *a, *b) }
+                else if *a == 0 { Err(anyhow::anyhow!("Ділення на нуль: 0 ** {}", b)) }
+                else { Ok(Value::Float((*a as f64).powf(*b as f64))) }
             }
 
+            // Змішані числа: ціле приводиться до дробового, результат — дробове
+            (op @ (BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div | BinaryOp::Mod | BinaryOp::Pow
+                | BinaryOp::Eq | BinaryOp::Ne | BinaryOp::Lt | BinaryOp::Le | BinaryOp::Gt | BinaryOp::Ge),
+                Value::Integer(a), Value::Float(_)) => self.apply_binary_op(op, Value::Float(*a as f64), rhs),
+            (op @ (BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div | BinaryOp::Mod | BinaryOp::Pow
+                | BinaryOp::Eq | BinaryOp::Ne | BinaryOp::Lt | BinaryOp::Le | BinaryOp::Gt | BinaryOp::Ge),
+                Value::Float(_), Value::Integer(b)) => self.apply_binary_op(op, lhs, Value::Float(*b as f64)),
+
             // Арифметика дробових
             (BinaryOp::Add, Value::Float(a), Value::Float(b)) => Ok(Value::Float(a + b)),
             (BinaryOp::Sub, Value::Float(a), Value::Float(b)) => Ok(Value::Float(a - b)),
@@ -7041,18 +8593,12 @@ except Exception as e:
                 if *b == 0.0 { Err(anyhow::anyhow!("Ділення на нуль")) }
                 else { Ok(Value::Float(a / b)) }
             }
+            (BinaryOp::Mod, Value::Float(a), Value::Float(b)) => {
+                if *b == 0.0 { Err(anyhow::anyhow!("Ділення на нуль")) }
+                else { Ok(Value::Float(a % b)) }
+            }
             (BinaryOp::Pow, Value::Float(a), Value::Float(b)) => Ok(Value::Float(a.powf(*b))),
 
-            // Змішані числа
-            (BinaryOp::Add, Value::Integer(a), Value::Float(b)) => Ok(Value::Float(*a as f64 + b)),
-            (BinaryOp::Add, Value::Float(a), Value::Integer(b)) => Ok(Value::Float(a + *b as f64)),
-            (BinaryOp::Sub, Value::Integer(a), Value::Float(b)) => Ok(Value::Float(*a as f64 - b)),
-            (BinaryOp::Sub, Value::Float(a), Value::Integer(b)) => Ok(Value::Float(a - *b as f64)),
-            (BinaryOp::Mul, Value::Integer(a), Value::Float(b)) => Ok(Value::Float(*a as f64 * b)),
-            (BinaryOp::Mul, Value::Float(a), Value::Integer(b)) => Ok(Value::Float(a * *b as f64)),
-            (BinaryOp::Div, Value::Integer(a), Value::Float(b)) => Ok(Value::Float(*a as f64 / b)),
-            (BinaryOp::Div, Value::Float(a), Value::Integer(b)) => Ok(Value::Float(a / *b as f64)),
-
             // Конкатенація рядків
             (BinaryOp::Add, Value::String(a), Value::String(b)) => Ok(Value::String(format!("{}{}", a, b))),
             (BinaryOp::Add, Value::String(a), b) => Ok(Value::String(format!("{}{}", a, b.to_display_string()))),
@@ -7105,17 +8651,149 @@ except Exception as e:
             (BinaryOp::BitAnd, Value::Integer(a), Value::Integer(b)) => Ok(Value::Integer(a & b)),
             (BinaryOp::BitOr, Value::Integer(a), Value::Integer(b)) => Ok(Value::Integer(a | b)),
             (BinaryOp::BitXor, Value::Integer(a), Value::Integer(b)) => Ok(Value::Integer(a ^ b)),
-            (BinaryOp::Shl, Value::Integer(a), Value::Integer(b)) => Ok(Value::Integer(a << b)),
-            (BinaryOp::Shr, Value::Integer(a), Value::Integer(b)) => Ok(Value::Integer(a >> b)),
+            (BinaryOp::Shl | BinaryOp::Shr, Value::Integer(a), Value::Integer(b)) => {
+                if !(0..64).contains(b) {
+                    return Err(anyhow::anyhow!("Зсув на {} біт поза межами 0..64", b));
+                }
+                Ok(Value::Integer(if op == BinaryOp::Shl { a << b } else { a >> b }))
+            }
 
             _ => Err(anyhow::anyhow!("Несумісні типи для операції {:?}: {} та {}",
                 op, lhs.type_name(), rhs.type_name())),
         }
     }
 
+    /// Підставляє аргументи в шаблон: `{}` або `{:специфікатор}`, де
+    /// специфікатор — `[<>^][0][ширина][.точність]`; `{{` і `}}` — дужки
+    fn format_template(template: &str, args: &[Value]) -> Result<String> {
+        let mut out = String::new();
+        let mut rest = args.iter();
+        let mut chars = template.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => { chars.next(); out.push('{'); }
+                '}' if chars.peek() == Some(&'}') => { chars.next(); out.push('}'); }
+                '{' => {
+                    let mut spec = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(ch) => spec.push(ch),
+                            None => return Err(anyhow::anyhow!(
+                                "Помилка значення: незакрита '{{' у шаблоні \"{}\"", template
+                            )),
+                        }
+                    }
+                    let value = rest.next().ok_or_else(|| anyhow::anyhow!(
+                        "Помилка значення: шаблон \"{}\" має більше місць, ніж аргументів ({})", template, args.len()
+                    ))?;
+                    let spec = match spec.strip_prefix(':') {
+                        Some(spec) => spec,
+                        None if spec.is_empty() => "",
+                        None => return Err(anyhow::anyhow!(
+                            "Помилка значення: некоректний специфікатор формату '{{{}}}'", spec
+                        )),
+                    };
+                    out.push_str(&Self::format_with_spec(value, spec)?);
+                }
+                '}' => return Err(anyhow::anyhow!(
+                    "Помилка значення: непарна '}}' у шаблоні \"{}\"", template
+                )),
+                _ => out.push(c),
+            }
+        }
+        if rest.next().is_some() {
+            return Err(anyhow::anyhow!(
+                "Помилка значення: шаблон \"{}\" має менше місць, ніж аргументів ({})", template, args.len()
+            ));
+        }
+        Ok(out)
+    }
+
+    /// Одне значення за специфікатором `[<>^][0][ширина][.точність]`
+    fn format_with_spec(value: &Value, spec: &str) -> Result<String> {
+        let invalid = || anyhow::anyhow!("Помилка значення: некоректний специфікатор формату '{{:{}}}'", spec);
+        let mut rest = spec;
+        let align = match rest.chars().next() {
+            Some(a @ ('<' | '>' | '^')) => { rest = &rest[1..]; Some(a) }
+            _ => None,
+        };
+        let zero_pad = rest.starts_with('0') && rest.len() > 1;
+        if zero_pad {
+            rest = &rest[1..];
+        }
+        let (width, precision) = match rest.split_once('.') {
+            Some((w, p)) => (w, Some(p.parse::<usize>().map_err(|_| invalid())?)),
+            None => (rest, None),
+        };
+        let width = if width.is_empty() { 0 } else { width.parse::<usize>().map_err(|_| invalid())? };
+
+        let text = match (value, precision) {
+            (Value::Float(f), Some(p)) => format!("{:.*}", p, f),
+            (Value::Integer(n), Some(p)) => format!("{:.*}", p, *n as f64),
+            (_, Some(_)) => return Err(anyhow::anyhow!(
+                "Помилка значення: точність '{{:{}}}' застосовна лише до чисел, отримано '{}'", spec, value.type_name()
+            )),
+            (other, None) => other.to_display_string(),
+        };
+        let len = text.chars().count();
+        if len >= width {
+            return Ok(text);
+        }
+        let fill = width - len;
+        if zero_pad && matches!(value, Value::Integer(_) | Value::Float(_)) {
+            // Нулі йдуть після знака: -0042
+            let (sign, digits) = match text.strip_prefix('-') {
+                Some(digits) => ("-", digits),
+                None => ("", text.as_str()),
+            };
+            return Ok(format!("{}{}{}", sign, "0".repeat(fill), digits));
+        }
+        // Числа за замовчуванням вирівнюються праворуч, решта — ліворуч
+        let align = align.unwrap_or(if matches!(value, Value::Integer(_) | Value::Float(_)) { '>' } else { '<' });
+        Ok(match align {
+            '>' => format!("{}{}", " ".repeat(fill), text),
+            '^' => format!("{}{}{}", " ".repeat(fill / 2), text, " ".repeat(fill - fill / 2)),
+            _ => format!("{}{}", text, " ".repeat(fill)),
+        })
+    }
+
+    /// Кількість повторень для повторити/повторити_масив — від'ємна є помилкою значення
+    fn repeat_count(name: &str, n: i64) -> Result<usize> {
+        usize::try_from(n).map_err(|_| {
+            anyhow::anyhow!("Помилка значення: {} очікує невід'ємну кількість, отримано {}", name, n)
+        })
+    }
+
+    /// Цілочисельна арифметика з перевіркою: переповнення цл64 — помилка значення, а не паніка
+    fn checked_int_op(op: &BinaryOp, a: i64, b: i64) -> Result<Value> {
+        if matches!(op, BinaryOp::Div | BinaryOp::Mod) && b == 0 {
+            return Err(anyhow::anyhow!("Ділення на нуль"));
+        }
+        let (result, symbol) = match op {
+            BinaryOp::Add => (a.checked_add(b), "+"),
+            BinaryOp::Sub => (a.checked_sub(b), "-"),
+            BinaryOp::Mul => (a.checked_mul(b), "*"),
+            BinaryOp::Div => (a.checked_div(b), "/"),
+            BinaryOp::Mod => (a.checked_rem(b), "%"),
+            BinaryOp::Pow => (match (a, u32::try_from(b)) {
+                (_, Ok(e)) => a.checked_pow(e),
+                // Показник понад u32 не переповнює лише основи 0, 1 та -1
+                (0 | 1, Err(_)) => Some(a),
+                (-1, Err(_)) => Some(if b % 2 == 0 { 1 } else { -1 }),
+                _ => None,
+            }, "**"),
+            _ => unreachable!("checked_int_op викликано для {:?}", op),
+        };
+        result.map(Value::Integer).ok_or_else(|| {
+            anyhow::anyhow!("Помилка значення: переповнення цл64 у {} {} {}", a, symbol, b)
+        })
+    }
+
     fn apply_unary_op(&self, op: UnaryOp, val: Value) -> Result<Value> {
         match (op, &val) {
-            (UnaryOp::Neg, Value::Integer(n)) => Ok(Value::Integer(-n)),
+            (UnaryOp::Neg, Value::Integer(n)) => n.checked_neg().map(Value::Integer)
+                .ok_or_else(|| anyhow::anyhow!("Помилка значення: переповнення цл64 у -({})", n)),
             (UnaryOp::Neg, Value::Float(f)) => Ok(Value::Float(-f)),
             (UnaryOp::Not, _) => Ok(Value::Bool(!val.to_bool())),
             (UnaryOp::BitNot, Value::Integer(n)) => Ok(Value::Integer(!n)),
@@ -7123,6 +8801,56 @@ except Exception as e:
         }
     }
 
+    /// Стабільне сортування масиву. Без компаратора — природний порядок
+    /// чисел, рядків і символів; компаратор повертає ціле (<0, 0, >0)
+    fn sort_values(&mut self, mut items: Vec<Value>, comparator: Option<Value>) -> Result<Vec<Value>> {
+        let mut error = None;
+        items.sort_by(|a, b| {
+            if error.is_some() {
+                return std::cmp::Ordering::Equal;
+            }
+            let ordering = match &comparator {
+                Some(func) => match self.call_value(func.clone(), vec![a.clone(), b.clone()]) {
+                    Ok(Value::Integer(n)) => Ok(n.cmp(&0)),
+                    Ok(other) => Err(anyhow::anyhow!(
+                        "Помилка типу: компаратор сортувати має повертати ціле, отримано '{}'",
+                        other.kind_name()
+                    )),
+                    Err(e) => Err(e),
+                },
+                None => Self::natural_order(a, b),
+            };
+            ordering.unwrap_or_else(|e| {
+                error = Some(e);
+                std::cmp::Ordering::Equal
+            })
+        });
+        match error {
+            Some(e) => Err(e),
+            None => Ok(items),
+        }
+    }
+
+    /// Природний порядок для сортування; несумісні типи — помилка типу
+    fn natural_order(a: &Value, b: &Value) -> Result<std::cmp::Ordering> {
+        let ordering = match (a, b) {
+            (Value::Integer(x), Value::Integer(y)) => Some(x.cmp(y)),
+            (Value::Float(x), Value::Float(y)) => x.partial_cmp(y),
+            (Value::Integer(x), Value::Float(y)) => (*x as f64).partial_cmp(y),
+            (Value::Float(x), Value::Integer(y)) => x.partial_cmp(&(*y as f64)),
+            (Value::String(x), Value::String(y)) => Some(x.cmp(y)),
+            (Value::Char(x), Value::Char(y)) => Some(x.cmp(y)),
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Помилка типу: сортувати не може порівняти '{}' і '{}'",
+                    a.kind_name(),
+                    b.kind_name()
+                ))
+            }
+        };
+        Ok(ordering.unwrap_or(std::cmp::Ordering::Equal))
+    }
+
     fn values_equal(&self, a: &Value, b: &Value) -> bool {
         match (a, b) {
             (Value::Integer(a), Value::Integer(b)) => a == b,
@@ -7131,6 +8859,7 @@ except Exception as e:
             (Value::Char(a), Value::Char(b)) => a == b,
             (Value::Bool(a), Value::Bool(b)) => a == b,
             (Value::Null, Value::Null) => true,
+            (Value::Channel(a), Value::Channel(b)) => Arc::ptr_eq(a, b),
             (Value::Array(a), Value::Array(b)) => {
                 a.len() == b.len() &&
                     a.iter().zip(b.iter()).all(|(x, y)| self.values_equal(x, y))
@@ -7331,8 +9060,8 @@ except Exception as e:
                     // Фільтри
                     if let Some(f) = filter {
                         text = match f {
-                            "великими" => text.to_uppercase(),
-                            "малими" => text.to_lowercase(),
+                            "великими" => change_case(&text, true),
+                            "малими" => change_case(&text, false),
                             _ if f.starts_with("обрізати_до(") => {
                                 let len_str = f.trim_start_matches("обрізати_до(").trim_end_matches(')');
                                 let max_len: usize = len_str.parse().unwrap_or(100);
@@ -7475,6 +9204,71 @@ except Exception as e:
     }
 }
 
+/// Число з одиницею без пробілу чи через пробіл: `5с`, `1.5 хв`
+fn split_quantity(text: &str) -> Option<(f64, String)> {
+    let text: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+    let split = text.find(|c: char| !(c.is_ascii_digit() || c == '.'))?;
+    let number: f64 = text[..split].parse().ok()?;
+    (number >= 0.0).then(|| (number, text[split..].to_string()))
+}
+
+/// Крок від змінної до місця запису в складеному присвоєнні
+enum LvalueStep {
+    Field(String),
+    Index(Value),
+}
+
+/// Запис бінарної операції у вихідному коді
+fn binary_op_symbol(op: &BinaryOp) -> &'static str {
+    match op {
+        BinaryOp::Add => "+", BinaryOp::Sub => "-", BinaryOp::Mul => "*", BinaryOp::Div => "/",
+        BinaryOp::Mod => "%", BinaryOp::Pow => "**",
+        BinaryOp::Eq => "==", BinaryOp::Ne => "!=", BinaryOp::Lt => "<", BinaryOp::Le => "<=",
+        BinaryOp::Gt => ">", BinaryOp::Ge => ">=",
+        BinaryOp::And => "і", BinaryOp::Or => "або",
+        BinaryOp::BitAnd => "&", BinaryOp::BitOr => "|", BinaryOp::BitXor => "^",
+        BinaryOp::Shl => "<<", BinaryOp::Shr => ">>",
+        BinaryOp::In => "в",
+    }
+}
+
+/// Тривалість для `//@таймаут`: мс, с, хв
+fn parse_duration(text: &str) -> Option<std::time::Duration> {
+    let (number, unit) = split_quantity(text)?;
+    let seconds = match unit.as_str() {
+        "мс" => number / 1000.0,
+        "с" | "сек" => number,
+        "хв" => number * 60.0,
+        _ => return None,
+    };
+    Some(std::time::Duration::from_secs_f64(seconds))
+}
+
+/// Розмір для `//@ліміт_памʼяті`: Б, КБ, МБ, ГБ (множник 1024)
+fn parse_byte_size(text: &str) -> Option<usize> {
+    let (number, unit) = split_quantity(text)?;
+    let multiplier = match unit.as_str() {
+        "Б" => 1.0,
+        "КБ" => 1024.0,
+        "МБ" => 1024.0 * 1024.0,
+        "ГБ" => 1024.0 * 1024.0 * 1024.0,
+        _ => return None,
+    };
+    Some((number * multiplier) as usize)
+}
+
+/// Регістр посимвольно: `і`↔`І`, `ї`↔`Ї`, `ґ`↔`Ґ`. Символ, що розкладається
+/// у кілька (`ß` → `SS`, `ŉ` → `ʼN`), лишається як є, щоб великі→малі→великі
+/// зберігали рядок; апостроф регістру не має і не змінюється
+fn change_case(text: &str, upper: bool) -> String {
+    text.chars()
+        .map(|c| {
+            let mut mapped = if upper { c.to_uppercase().collect::<Vec<_>>() } else { c.to_lowercase().collect() };
+            if mapped.len() == 1 { mapped.remove(0) } else { c }
+        })
+        .collect()
+}
+
 pub fn execute(program: Program, args: Vec<String>) -> Result<()> {
     let handle = std::thread::Builder::new()
         .name("tryzub-vm".into())
@@ -7577,6 +9371,85 @@ mod tests {
         assert!(execute(program, vec![]).is_ok());
     }
 
+    #[test]
+    fn test_task_runtime_reused() {
+        let source = r#"
+функція головна() {
+    змінна сума = 0
+    для раунд від 0 до 20 {
+        змінна р = паралельно([|| раунд, || раунд * 2, || раунд * 3])
+        сума = сума + р[0] + р[1] + р[2]
+    }
+    перевірити_рівне(сума, 1140)
+}
+"#;
+        let program = parse(tokenize(source).unwrap()).unwrap();
+        let mut vm = VM::new();
+        // Пул не створюється, поки програма не запустить задачу
+        assert!(vm.task_runtime.get().is_none());
+        vm.execute_program(program, vec![]).unwrap();
+
+        // Усі 60 задач пройшли через один і той самий пул, і дочекані прибрано
+        let runtime = vm.task_runtime.get().unwrap().clone();
+        assert_eq!(runtime.task_status(0), None);
+        assert_eq!(runtime.task_status(59), None);
+        assert!(runtime.active_tasks().is_empty());
+        drop(vm);
+        assert_eq!(Arc::strong_count(&runtime), 1);
+    }
+
+    #[test]
+    fn test_worker_vm_declares_program_once() {
+        let source = r#"
+функція подвоїти(х: цл64) -> цл64 { повернути х * 2 }
+
+функція головна() {}
+"#;
+        let program = parse(tokenize(source).unwrap()).unwrap();
+        let decls = Arc::new(program.declarations);
+        let task = |n: i64| SendTask {
+            func: SendFunction::Lambda {
+                params: vec![],
+                body: LambdaBody::Expr(Expression::Call {
+                    callee: Box::new(Expression::Identifier("подвоїти".to_string())),
+                    args: vec![Expression::Literal(tryzub_parser::Literal::Integer(n))],
+                    span: tryzub_parser::Span::default(),
+                }),
+            },
+            args: Vec::new(),
+            captured: Vec::new(),
+        };
+        let worker_globals = || WORKER_VM.with(|cell| cell.borrow().as_ref().map(|vm| vm.global_env.clone()));
+
+        assert!(matches!(VM::run_send_task(decls.clone(), task(2)), Ok(SendValue::Integer(4))));
+        let first = worker_globals().expect("VM робітника не збережено");
+        assert!(matches!(VM::run_send_task(decls.clone(), task(5)), Ok(SendValue::Integer(10))));
+        assert!(Rc::ptr_eq(&first, &worker_globals().unwrap()));
+
+        // Інша програма — новий VM
+        let other = Arc::new(parse(tokenize(source).unwrap()).unwrap().declarations);
+        assert!(VM::run_send_task(other, task(1)).is_ok());
+        assert!(!Rc::ptr_eq(&first, &worker_globals().unwrap()));
+    }
+
+    #[test]
+    fn test_channel_freed_with_last_handle() {
+        let mut vm = VM::new();
+        let ends = match vm.call_builtin("канал", vec![]).unwrap() {
+            Value::Tuple(items) => items,
+            other => panic!("очікувався кортеж, отримано {:?}", other),
+        };
+        let weak: Vec<std::sync::Weak<ChannelEnd>> = ends.iter().map(|end| match end {
+            Value::Channel(end) => Arc::downgrade(end),
+            other => panic!("очікувався кінець каналу, отримано {:?}", other),
+        }).collect();
+        vm.call_builtin("надіслати", vec![ends[0].clone(), Value::Integer(1)]).unwrap();
+
+        // Реєстру каналів немає: буфер зникає разом з останнім дескриптором
+        drop(ends);
+        assert!(weak.iter().all(|end| end.upgrade().is_none()));
+    }
+
     #[test]
     fn test_auth_hash_verify() {
         // Тест на рівні VM напряму — без парсера
@@ -7645,7 +9518,7 @@ mod tests {
         let data3 = Value::Dict(vec![
             (Value::String("елементи".to_string()), Value::Array(vec![
                 Value::Integer(1), Value::Integer(2), Value::Integer(3),
-            ])),
+            ].into())),
         ]);
         let result3 = vm.render_template("{для х в елементи}[{х}]{/для}", &data3).unwrap();
         assert!(result3.contains("[1]"));
@@ -7948,4 +9821,92 @@ mod tests {
 "#);
         assert!(r.is_ok(), "Default params failed: {:?}", r.err());
     }
+
+    #[test]
+    fn test_shared_array_copy_on_write() {
+        let original = Value::Array(vec![Value::Integer(1), Value::Integer(2)].into());
+        let mut copy = original.clone();
+        match (&original, &copy) {
+            (Value::Array(a), Value::Array(b)) => assert!(Shared::ptr_eq(a, b)),
+            _ => unreachable!(),
+        }
+
+        if let Value::Array(items) = &mut copy {
+            items[0] = Value::Integer(10);
+        }
+        match (&original, &copy) {
+            (Value::Array(a), Value::Array(b)) => {
+                assert!(!Shared::ptr_eq(a, b));
+                assert!(matches!(a[0], Value::Integer(1)));
+                assert!(matches!(b[0], Value::Integer(10)));
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_large_array_passed_without_copy() {
+        let tokens = tokenize("функція тотожна(м) { повернути м }\nфункція головна() {}").unwrap();
+        let mut vm = VM::new();
+        vm.execute_program(parse(tokens).unwrap(), vec![]).unwrap();
+        let func = vm.global_env.borrow().get("тотожна").unwrap();
+
+        let large = Value::Array(vec![Value::Integer(1); 200_000].into());
+        let returned = vm.call_value(func, vec![large.clone()]).unwrap();
+        match (&large, &returned) {
+            (Value::Array(a), Value::Array(b)) => assert!(Shared::ptr_eq(a, b)),
+            other => panic!("Очікувались масиви, отримано {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_coverage_counts_loop_body() {
+        let source = r#"
+функція квадрат(х: цл64) -> цл64 {
+    повернути х * х
+}
+
+функція головна() {
+    змінна сума = 0
+    для і від 0 до 5 {
+        сума += квадрат(і)
+    }
+    перевірити_рівне(сума, 30)
+}
+"#;
+        let tokens = tokenize(source).unwrap();
+        let program = parse(tokens).unwrap();
+        let mut vm = VM::new();
+        vm.enable_coverage();
+        vm.execute_program(program, vec![]).unwrap();
+
+        let coverage = vm.coverage().unwrap();
+        assert_eq!(coverage.functions.get("квадрат"), Some(&5));
+        assert_eq!(coverage.functions.get("головна"), Some(&1));
+        assert_eq!(coverage.lines.get(&3), Some(&5));
+        assert_eq!(coverage.lines.get(&9), Some(&5));
+        assert!(vm.coverage_report().contains("квадрат"));
+    }
+
+    #[test]
+    fn test_coverage_counts_lambda_body() {
+        let source = r#"
+функція головна() {
+    стала подвоїти = |х| х * 2
+    змінна сума = 0
+    для і від 0 до 3 {
+        сума += подвоїти(і)
+    }
+}
+"#;
+        let tokens = tokenize(source).unwrap();
+        let program = parse(tokens).unwrap();
+        let mut vm = VM::new();
+        vm.enable_coverage();
+        vm.execute_program(program, vec![]).unwrap();
+
+        let coverage = vm.coverage().unwrap();
+        assert_eq!(coverage.functions.get("<лямбда>"), Some(&3));
+        assert_eq!(coverage.lines.get(&3), Some(&4));
+    }
 }
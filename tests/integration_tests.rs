@@ -85,6 +85,23 @@ fn test_for_in_array() {
 "#);
 }
 
+#[test]
+fn test_for_in_string_chars() {
+    run(r#"
+функція головна() {
+    змінна кількість = 0
+    змінна літер_а = 0
+    для (с в "Їжачок ґава") {
+        перевірити_рівне(тип_значення(с), "сим")
+        кількість += 1
+        якщо с == 'а' { літер_а += 1 }
+    }
+    перевірити_рівне(кількість, 11)
+    перевірити_рівне(літер_а, 3)
+}
+"#);
+}
+
 #[test]
 fn test_function_call() {
     run(r#"
@@ -307,6 +324,70 @@ fn test_break_continue() {
 "#);
 }
 
+#[test]
+fn test_for_loop_steps() {
+    run(r#"
+функція головна() {
+    змінна спадні = []
+    для (і від 10 до 0 через -2) {
+        спадні = спадні.додати(і)
+    }
+    перевірити_рівне(спадні, [10, 8, 6, 4, 2])
+
+    // крок проти напрямку меж — нуль ітерацій
+    змінна ітерацій = 0
+    для (і від 0 до 3 через -1) { ітерацій += 1 }
+    для (і від 3 до 0) { ітерацій += 1 }
+    перевірити_рівне(ітерацій, 0)
+
+    змінна дробові = []
+    для (х від 0 до 1 через 0.25) {
+        дробові = дробові.додати(х)
+    }
+    перевірити_рівне(дробові, [0.0, 0.25, 0.5, 0.75])
+}
+"#);
+
+    let err = run_err(r#"
+функція головна() {
+    для (і від 0 до 3 через 0) {}
+}
+"#);
+    assert!(err.contains("Крок циклу не може дорівнювати нулю"), "{}", err);
+}
+
+#[test]
+fn test_while_continue_skips_rest_of_body() {
+    run(r#"
+функція головна() {
+    змінна і = 0
+    змінна сума = 0
+    змінна пропущено = 0
+    поки (і < 10) {
+        і += 1
+        якщо (і % 3 == 0) {
+            пропущено += 1
+            продовжити
+        }
+        сума += і
+    }
+    перевірити_рівне(і, 10)
+    перевірити_рівне(пропущено, 3)
+    перевірити_рівне(сума, 55 - 3 - 6 - 9)
+
+    // продовжити на останній ітерації не зачіпає код після циклу
+    змінна після = 0
+    змінна к = 0
+    поки (к < 2) {
+        к += 1
+        продовжити
+    }
+    після = 1
+    перевірити_рівне(після, 1)
+}
+"#);
+}
+
 // ═══ БЛОК 1: Stack traces та професійні помилки ═══
 
 #[test]
@@ -332,6 +413,19 @@ fn test_did_you_mean_suggestion() {
 "#);
     assert!(err.contains("повідомлення"), "Має підказати: {}", err);
     assert!(err.contains("Т001"), "Має мати error code: {}", err);
+
+    // Ім'я з зовнішньої області шукається крізь увесь ланцюг
+    let err = run_err(r#"
+стала МАКСИМУМ = 10
+функція головна() {
+    для і від 0 до 3 {
+        якщо і > 1 {
+            друк(МАКСИМУН)
+        }
+    }
+}
+"#);
+    assert!(err.contains("можливо, ви мали на увазі 'МАКСИМУМ'?"), "{}", err);
 }
 
 #[test]
@@ -987,6 +1081,105 @@ fn test_lambda_no_params() {
 "#);
 }
 
+#[test]
+fn test_lambda_captures_by_value() {
+    run(r#"
+функція головна() {
+    змінна множник = 2
+    змінна помножити = |х| х * множник
+    множник = 10
+    // лямбда бачить значення на момент створення
+    перевірити_рівне(помножити(3), 6)
+
+    змінна лічильник = 0
+    змінна збільшити = || {
+        лічильник = лічильник + 1
+        повернути лічильник
+    }
+    перевірити_рівне(збільшити(), 1)
+    перевірити_рівне(збільшити(), 2)
+    // власна копія лямбди не змінює зовнішню змінну
+    перевірити_рівне(лічильник, 0)
+
+    // рекурсивна лямбда бачить себе, бо оголошується після створення
+    змінна факторіал = |н| н <= 1 ? 1 : н * факторіал(н - 1)
+    перевірити_рівне(факторіал(5), 120)
+}
+"#);
+}
+
+#[test]
+fn test_math_namespace() {
+    run(r#"
+функція головна() {
+    перевірити_рівне(математика.ПІ, ПІ)
+    перевірити(математика.Е > 2.71 && математика.Е < 2.72)
+    перевірити_рівне(математика.корінь(16), 4.0)
+    перевірити_рівне(математика.степінь(2, 10), 1024.0)
+    перевірити_рівне(математика.логарифм(1), 0.0)
+    // Решта членів приходить зі stdlib/математика.тризуб
+    перевірити_рівне(математика.абс(-3), 3)
+    перевірити_рівне(математика.нсд(12, 18), 6)
+    перевірити(математика.НЕСКІНЧЕННІСТЬ > найбільше_цл64)
+}
+"#);
+
+    run(r#"
+імпорт математика
+
+функція головна() {
+    перевірити_рівне(математика.корінь(9), 3.0)
+    перевірити_рівне(математика.макс(2, 7), 7)
+    перевірити_рівне(математика.факторіал(5), 120)
+}
+"#);
+
+    // Користувацьке ім'я затіняє вбудований модуль
+    run(r#"
+функція головна() {
+    змінна математика = 5
+    перевірити_рівне(математика, 5)
+}
+"#);
+
+    let err = run_err(r#"
+функція головна() {
+    математика.тангенс(1)
+}
+"#);
+    assert!(err.contains("'тангенс' не знайдена в модулі 'математика'"), "{}", err);
+}
+
+#[test]
+fn test_array_copy_on_write() {
+    run(r#"
+структура Точка { х: цл64, у: цл64 }
+
+функція змінити(м) {
+    м[0] = 100
+    повернути м
+}
+
+функція головна() {
+    змінна а = [1, 2, 3]
+    змінна б = а
+    б[0] = 9
+    перевірити_рівне(а, [1, 2, 3])
+    перевірити_рівне(б, [9, 2, 3])
+
+    змінна в_функції = змінити(а)
+    перевірити_рівне(в_функції, [100, 2, 3])
+    перевірити_рівне(а, [1, 2, 3])
+
+    змінна т = Точка { х: 1, у: 2 }
+    змінна к = т
+    к.х = 5
+    перевірити_рівне(т.х, 1)
+    перевірити_рівне(к.х, 5)
+}
+"#);
+}
+
 #[test]
 fn test_large_loop_gc_safe() {
     run(r#"
@@ -1017,3 +1210,2006 @@ fn test_fibonacci_25_recursive() {
 }
 "#);
 }
+
+#[test]
+fn test_static_method_and_field() {
+    run(r#"
+структура Точка { х: цл64, у: цл64 }
+
+реалізація Точка {
+    статичний стала ВИМІРІВ = 2
+    статичний функція нова(х: цл64, у: цл64) -> Точка {
+        повернути Точка { х: х, у: у }
+    }
+    функція сума(себе) -> цл64 {
+        повернути себе.х + себе.у
+    }
+}
+
+функція головна() {
+    змінна т = Точка.нова(3, 4)
+    перевірити_рівне(7, т.сума())
+    перевірити_рівне(2, Точка.ВИМІРІВ)
+}
+"#);
+}
+
+#[test]
+fn test_static_method_call_mismatch() {
+    let err = run_err(r#"
+структура Точка { х: цл64 }
+
+реалізація Точка {
+    статичний функція нова() -> Точка { повернути Точка { х: 0 } }
+    функція значення(себе) -> цл64 { повернути себе.х }
+}
+
+функція головна() {
+    змінна т = Точка.нова()
+    т.нова()
+}
+"#);
+    assert!(err.contains("Статичний метод"), "{}", err);
+
+    let err = run_err(r#"
+структура Точка { х: цл64 }
+
+реалізація Точка {
+    функція значення(себе) -> цл64 { повернути себе.х }
+}
+
+функція головна() {
+    Точка.значення()
+}
+"#);
+    assert!(err.contains("не є статичним"), "{}", err);
+}
+
+#[test]
+fn test_static_field_in_trait_impl() {
+    run(r#"
+структура Лічильник { значення: цл64 }
+
+трейт Назване {
+    функція назва(себе) -> рядок
+}
+
+реалізація Назване для Лічильник {
+    статичний змінна ОДИНИЦЯ = "шт"
+    функція назва(себе) -> рядок { повернути Лічильник.ОДИНИЦЯ }
+}
+
+функція головна() {
+    змінна л = Лічильник { значення: 1 }
+    перевірити_рівне("шт", л.назва())
+    перевірити_рівне("шт", Лічильник.ОДИНИЦЯ)
+}
+"#);
+}
+
+#[test]
+fn test_fixed_size_array_with_const_size() {
+    run(r#"
+стала РОЗМІР = 3
+
+функція головна() {
+    змінна а: цл64[РОЗМІР]
+    перевірити_рівне(3, довжина(а))
+    перевірити_рівне(0, а[2])
+    змінна б: цл64[2] = [7, 8]
+    перевірити_рівне(8, б[1])
+}
+"#);
+    let err = run_err(r#"
+функція головна() {
+    змінна а: цл64[2] = [1, 2, 3]
+}
+"#);
+    assert!(err.contains("розміру масиву"), "{}", err);
+}
+
+#[test]
+fn test_negative_array_index() {
+    run(r#"
+функція головна() {
+    змінна а = [10, 20, 30]
+    перевірити_рівне(30, а[-1])
+    перевірити_рівне(20, а[-2])
+    перевірити_рівне(10, а[-3])
+    а[-1] = 99
+    перевірити_рівне(99, а[2])
+    перевірити_рівне('ь', "тінь"[-1])
+}
+"#);
+    let err = run_err(r#"
+функція головна() {
+    змінна а = [10, 20, 30]
+    друк(а[-4])
+}
+"#);
+    assert!(err.contains("поза межами"), "{}", err);
+    let err = run_err(r#"
+функція головна() {
+    змінна а = [10, 20, 30]
+    а[3] = 1
+}
+"#);
+    assert!(err.contains("поза межами"), "{}", err);
+}
+
+#[test]
+fn test_value_copy_on_assignment() {
+    run(r#"
+структура Точка {
+    х: цл64,
+    у: цл64
+}
+
+функція головна() {
+    змінна а = [1, 2, 3]
+    змінна б = а
+    б[0] = 9
+    перевірити_рівне(1, а[0])
+    перевірити_рівне(9, б[0])
+
+    змінна м = [[1, 2], [3, 4]]
+    змінна н = м
+    н[0] = [0, 0]
+    перевірити_рівне(1, м[0][0])
+
+    змінна т = Точка { х: 1, у: 2 }
+    змінна к = т
+    к.х = 5
+    перевірити_рівне(1, т.х)
+    перевірити_рівне(5, к.х)
+}
+"#);
+}
+
+#[test]
+fn test_bool_conversion() {
+    run(r#"
+функція головна() {
+    перевірити_рівне(істина, влог(5))
+    перевірити_рівне(хиба, влог(0))
+    перевірити_рівне(хиба, влог(0.0))
+    перевірити_рівне(хиба, влог(""))
+    перевірити_рівне(істина, влог("хиба"))
+    перевірити_рівне(хиба, влог([]))
+    перевірити_рівне(істина, врядкувлог("істина"))
+    перевірити_рівне(хиба, врядкувлог("хиба"))
+}
+"#);
+    let err = run_err(r#"
+функція головна() {
+    врядкувлог("так")
+}
+"#);
+    assert!(err.contains("Помилка значення"), "{}", err);
+}
+
+#[test]
+fn test_bitwise_operators() {
+    run(r#"
+функція головна() {
+    перевірити_рівне(8, 12 & 10)
+    перевірити_рівне(14, 12 | 10)
+    перевірити_рівне(6, 12 ^ 10)
+    перевірити_рівне(40, 5 << 3)
+    перевірити_рівне(5, 40 >> 3)
+    перевірити_рівне(-4, -16 >> 2)
+    перевірити_рівне(7, 1 | 2 | 4)
+    перевірити_рівне(істина, (6 & 3) == 2 && (1 | 0) == 1)
+    змінна ф = |x| x & 1
+    перевірити_рівне(1, ф(9))
+}
+"#);
+    let err = run_err(r#"
+функція головна() {
+    друк(1 << 64)
+}
+"#);
+    assert!(err.contains("Зсув"), "{}", err);
+}
+
+#[test]
+fn test_type_introspection() {
+    run(r#"
+структура Точка {
+    х: цл64
+}
+
+функція подвоїти(х: цл64) -> цл64 {
+    повернути х * 2
+}
+
+функція головна() {
+    перевірити_рівне("ціле", тип(42))
+    перевірити_рівне("дробове", тип(3.14))
+    перевірити_рівне("рядок", тип("привіт"))
+    перевірити_рівне("масив", тип([1, 2]))
+    перевірити_рівне("структура", тип(Точка { х: 1 }))
+    перевірити_рівне("функція", тип(подвоїти))
+    перевірити_рівне("функція", тип(|х| х))
+    перевірити_рівне("нуль", тип(нуль))
+    перевірити_рівне("логічне", тип(істина))
+    змінна т = тип(1)
+    тип(т)
+}
+"#);
+}
+
+#[test]
+fn test_time_builtins() {
+    run(r#"
+функція головна() {
+    змінна початок = час_зараз()
+    перевірити_рівне("ціле", тип(початок))
+    спати(20)
+    змінна кінець = час_зараз()
+    перевірити(кінець - початок >= 20)
+    перевірити(час_зараз() >= кінець)
+}
+"#);
+    let err = run_err(r#"
+функція головна() {
+    спати(-5)
+}
+"#);
+    assert!(err.contains("невід'ємну"), "{}", err);
+    let err = run_err(r#"
+функція головна() {
+    спати(1.5)
+}
+"#);
+    assert!(err.contains("невід'ємну"), "{}", err);
+}
+
+#[test]
+fn test_file_builtins_roundtrip() {
+    let dir = std::env::temp_dir();
+    let path = dir.join(format!("тризуб_файл_{}.txt", std::process::id()));
+    let path_str = path.to_string_lossy().replace('\\', "/");
+    let missing = dir.join("тризуб_немає_такого_каталогу/файл.txt");
+    let missing_str = missing.to_string_lossy().replace('\\', "/");
+    run(&format!(r#"
+функція головна() {{
+    записати_файл("{p}", "Привіт, файл!")
+    перевірити (файл_існує("{p}"))
+    перевірити_рівне(читати_файл("{p}"), "Привіт, файл!")
+    перевірити_рівне(файл_існує("{m}"), хиба)
+    змінна зловлено = хиба
+    спробувати {{
+        читати_файл("{m}")
+    }} зловити п {{
+        зловлено = істина
+    }}
+    перевірити (зловлено)
+}}
+"#, p = path_str, m = missing_str));
+    let _ = std::fs::remove_file(&path);
+
+    let err = run_err(&format!(r#"
+функція головна() {{ читати_файл("{}") }}
+"#, missing_str));
+    assert!(err.contains("Помилка вводу-виводу"), "{}", err);
+    assert!(err.contains("не існує"), "{}", err);
+
+    let err = run_err(&format!(r#"
+функція головна() {{ записати_файл("{}", "х") }}
+"#, missing_str));
+    assert!(err.contains("не вдалося записати"), "{}", err);
+}
+
+#[test]
+fn test_паралельно_thread_pool() {
+    run(r#"
+функція сума_до(н: цл64) -> цл64 {
+    змінна с = 0
+    для і від 1 до н + 1 {
+        с = с + і
+    }
+    повернути с
+}
+
+функція привітання() { повернути "привіт" }
+
+функція головна() {
+    змінна множник = 3
+    змінна р = паралельно([
+        || сума_до(1000),
+        || множник * 7,
+        привітання,
+        || [1, 2, 3]
+    ])
+    перевірити_рівне(4, довжина(р))
+    перевірити_рівне(500500, р[0])
+    перевірити_рівне(21, р[1])
+    перевірити_рівне("привіт", р[2])
+    перевірити_рівне(3, довжина(р[3]))
+}
+"#);
+
+    let err = run_err(r#"
+функція головна() {
+    паралельно([|| паніка("зламано")])
+}
+"#);
+    assert!(err.contains("паралельній задачі 0"), "{}", err);
+}
+
+#[test]
+fn test_паралельно_runs_tasks_as_function_calls() {
+    run(r#"
+функція розібрати(х: цл64) -> Результат<цл64, тхт> {
+    якщо х < 0 { повернути Помилка("від'ємне") }
+    повернути Успіх(х)
+}
+
+функція перевірене() -> Результат<цл64, тхт> {
+    стала х = розібрати(-1)?
+    повернути Успіх(х * 2)
+}
+
+функція головна() {
+    стала р = паралельно([перевірене])
+    зіставити р[0] {
+        Помилка(п) => перевірити_рівне(п, "від'ємне"),
+        _ => паніка("очікувалась помилка")
+    }
+}
+"#);
+
+    let err = run_err(r#"
+функція число() -> цл64 { повернути "не число" }
+
+функція головна() {
+    паралельно([число])
+}
+"#);
+    assert!(err.contains("Функція 'число'"), "{}", err);
+
+    let err = run_err(r#"
+асинхронний функція подвоїти(х: цл64) -> цл64 { повернути х * 2 }
+
+функція головна() {
+    стала задача = подвоїти("два")
+    чекати задача
+}
+"#);
+    assert!(err.contains("Помилка в асинхронній задачі"), "{}", err);
+    assert!(err.contains("цл64"), "{}", err);
+}
+
+#[test]
+fn test_async_await() {
+    run(r#"
+асинхронний функція порахувати(н: цл64) -> цл64 {
+    змінна с = 0
+    для і від 0 до н {
+        с = с + і
+    }
+    повернути с
+}
+
+асинхронний функція привітати(ім: тхт) -> тхт {
+    повернути "Привіт, " + ім
+}
+
+функція головна() {
+    змінна а = порахувати(100)
+    змінна б = привітати("світ")
+    перевірити_рівне(тип(а), "задача")
+    перевірити_рівне(чекати б, "Привіт, світ")
+    перевірити_рівне(чекати а, 4950)
+    перевірити_рівне(чекати порахувати(5), 10)
+    перевірити_рівне(чекати привітати("тризуб") + "!", "Привіт, тризуб!")
+}
+"#);
+
+    // Асинхронність належить самій функції, а не її імені
+    run(r#"
+асинхронний функція порахувати(н: цл64) -> цл64 {
+    повернути н * 2
+}
+
+модуль м {
+    функція порахувати(н: цл64) -> цл64 { повернути н + 1 }
+}
+
+структура Задача { ід: цл64 }
+
+функція головна() {
+    перевірити_рівне(м.порахувати(1), 2)
+    змінна ф = порахувати
+    змінна з = ф(4)
+    перевірити_рівне(тип(з), "задача")
+    перевірити_рівне(чекати з, 8)
+
+    // Звичайна структура не стає дескриптором задачі
+    змінна с = Задача { ід: 0 }
+    перевірити_рівне((чекати с).ід, 0)
+}
+"#);
+
+    let err = run_err(r#"
+асинхронний функція зламана() {
+    паніка("ой")
+}
+
+функція головна() {
+    змінна з = зламана()
+    чекати з
+}
+"#);
+    assert!(err.contains("асинхронній задачі"), "{}", err);
+}
+
+#[test]
+fn test_channels_between_threads() {
+    run(r#"
+асинхронний функція виробник(відпр, н: цл64) {
+    для і від 1 до н + 1 {
+        надіслати(відпр, і * 10)
+    }
+    закрити_канал(відпр)
+}
+
+функція головна() {
+    змінна к = канал()
+    змінна відпр = к[0]
+    змінна отр = к[1]
+    змінна задача = виробник(відпр, 5)
+
+    змінна сума = 0
+    змінна кількість = 0
+    змінна закрито = хиба
+    поки !закрито {
+        спробувати {
+            сума = сума + отримати(отр)
+            кількість = кількість + 1
+        } зловити п {
+            закрито = істина
+        }
+    }
+    чекати задача
+    перевірити_рівне(кількість, 5)
+    перевірити_рівне(сума, 150)
+}
+"#);
+
+    let err = run_err(r#"
+функція головна() {
+    змінна к = канал()
+    закрити_канал(к[0])
+    надіслати(к[0], 1)
+}
+"#);
+    assert!(err.contains("закрито"), "{}", err);
+
+    // Коли зникають усі копії відправника, канал закривається сам
+    let err = run_err(r#"
+функція наповнити() {
+    змінна к = канал()
+    надіслати(к[0], 7)
+    повернути к[1]
+}
+
+функція головна() {
+    змінна отр = наповнити()
+    перевірити_рівне(тип(отр), "канал")
+    перевірити_рівне(отримати(отр), 7)
+    отримати(отр)
+}
+"#);
+    assert!(err.contains("закрито"), "{}", err);
+}
+
+#[test]
+fn test_compound_assignment_to_elements() {
+    run(r#"
+структура Точка {
+    x: цл64,
+    y: цл64
+}
+
+функція головна() {
+    змінна масив = [1, 2, 3]
+    масив[0] += 5
+    масив[-1] *= 10
+    масив[1] -= 2
+    перевірити_рівне(масив[0], 6)
+    перевірити_рівне(масив[1], 0)
+    перевірити_рівне(масив[2], 30)
+
+    змінна точка = Точка { x: 4, y: 7 }
+    точка.x *= 2
+    точка.y %= 4
+    перевірити_рівне(точка.x, 8)
+    перевірити_рівне(точка.y, 3)
+
+    змінна слова = словник()
+    слова["а"] = 1
+    слова["а"] += 1
+    перевірити_рівне(слова.отримати("а"), 2)
+}
+"#);
+
+    let err = run_err(r#"
+функція головна() {
+    змінна масив = [1, 2]
+    масив[5] += 1
+}
+"#);
+    assert!(err.contains("поза межами"), "{}", err);
+
+    let err = run_err(r#"
+функція головна() {
+    змінна масив = [1, 2]
+    масив[0] += "текст"
+}
+"#);
+    assert!(err.contains("Невідповідність типів"), "{}", err);
+}
+
+#[test]
+fn test_block_expression() {
+    run(r#"
+функція подвоїти(х: цл64) -> цл64 { повернути х * 2 }
+
+функція головна() {
+    змінна зовнішня = 10
+    змінна а = {
+        змінна т = подвоїти(зовнішня);
+        т + 1
+    }
+    перевірити_рівне(а, 21)
+
+    змінна б = { змінна к = 5 }
+    перевірити_рівне(б, нуль)
+
+    змінна в_ = { друк("бічний ефект"); }
+    перевірити_рівне(в_, нуль)
+
+    змінна р = зіставити а {
+        21 => {
+            змінна п = а * 2
+            п + 1
+        },
+        _ => 0
+    }
+    перевірити_рівне(р, 43)
+}
+"#);
+}
+
+#[test]
+fn test_зєднати_builtin() {
+    run(r#"
+функція головна() {
+    перевірити_рівне(з'єднати([1, 2, 3], ", "), "1, 2, 3")
+    перевірити_рівне(з'єднати(["а", "б", "в"], "-"), "а-б-в")
+    перевірити_рівне(з'єднати([], ", "), "")
+    перевірити_рівне(з'єднати(["один"], ", "), "один")
+    перевірити_рівне(з'єднати("а,б".розділити(","), ";"), "а;б")
+}
+"#);
+    assert!(run_err(r#"
+функція головна() {
+    з'єднати([1, 2], 3)
+}
+"#).contains("з'єднати очікує"));
+}
+
+#[test]
+fn test_constant_assignment() {
+    run(r#"
+стала МАКСИМУМ = 10
+
+функція головна() {
+    змінна лічильник = 0
+    лічильник = МАКСИМУМ
+    лічильник += 1
+    перевірити_рівне(лічильник, 11)
+
+    стала к = 1
+    змінна к = 2
+    к = 3
+    перевірити_рівне(к, 3)
+}
+"#);
+
+    let err = run_err(r#"
+функція головна() {
+    стала межа = 5
+    межа = 6
+}
+"#);
+    assert!(err.contains("Не можна присвоїти сталій 'межа'"), "{}", err);
+
+    let err = run_err(r#"
+стала МАКСИМУМ = 10
+
+функція головна() {
+    МАКСИМУМ += 1
+}
+"#);
+    assert!(err.contains("сталій"), "{}", err);
+
+    let err = run_err(r#"
+функція головна() {
+    стала масив = [1, 2]
+    масив[0] = 5
+}
+"#);
+    assert!(err.contains("сталій"), "{}", err);
+}
+
+#[test]
+fn test_це_in_methods() {
+    run(r#"
+структура Лічильник {
+    значення: цл64,
+    крок: цл64
+}
+
+реалізація Лічильник {
+    функція збільшити(себе) {
+        це.значення += це.крок
+    }
+
+    функція двічі(себе) {
+        це.збільшити()
+        себе.збільшити()
+    }
+
+    функція поточне(себе) -> цл64 {
+        повернути це.значення
+    }
+}
+
+функція головна() {
+    змінна л = Лічильник { значення: 0, крок: 5 }
+    л.збільшити()
+    перевірити_рівне(л.значення, 5)
+    л.двічі()
+    перевірити_рівне(л.поточне(), 15)
+
+    змінна копія = л
+    копія.збільшити()
+    перевірити_рівне(л.значення, 15)
+    перевірити_рівне(копія.значення, 20)
+}
+"#);
+
+    let err = run_err(r#"
+структура Т { х: цл64 }
+реалізація Т {
+    функція змінити(себе) { це.х = 1 }
+}
+функція головна() {
+    стала т = Т { х: 0 }
+    т.змінити()
+}
+"#);
+    assert!(err.contains("сталій"), "{}", err);
+}
+
+#[test]
+fn test_caught_method_error_keeps_caller_self() {
+    run(r#"
+структура Лічильник { значення: цл64 }
+
+реалізація Лічильник {
+    функція зламати(себе) {
+        це.значення = 100
+        паніка("збій")
+    }
+
+    функція спробувати_зламати(себе) {
+        це.значення = 12
+        спробувати {
+            це.зламати()
+        } зловити п {}
+        перевірити_рівне(це.значення, 12)
+    }
+}
+
+функція головна() {
+    змінна л = Лічильник { значення: 0 }
+    л.спробувати_зламати()
+    перевірити_рівне(л.значення, 12)
+}
+"#);
+}
+
+#[test]
+fn test_question_mark_propagation() {
+    run(r#"
+функція поділити(а: цл64, б: цл64) -> Результат<цл64, тхт> {
+    якщо (б == 0) {
+        повернути Помилка("ділення на нуль")
+    }
+    повернути Успіх(а / б)
+}
+
+функція обчислити(а: цл64, б: цл64) -> Результат<цл64, тхт> {
+    змінна частка = поділити(а, б)? + 1
+    повернути Успіх(частка * 2)
+}
+
+функція головна() {
+    зіставити обчислити(10, 2) {
+        Успіх(р) => перевірити_рівне(р, 12),
+        Помилка(п) => перевірити_рівне(п, "")
+    }
+    зіставити обчислити(10, 0) {
+        Успіх(р) => перевірити_рівне(р, 0),
+        Помилка(п) => перевірити_рівне(п, "ділення на нуль")
+    }
+}
+"#);
+
+    let err = run_err(r#"
+функція поділити(а: цл64, б: цл64) -> Результат<цл64, тхт> {
+    якщо (б == 0) { повернути Помилка("ділення на нуль") }
+    повернути Успіх(а / б)
+}
+
+функція число(а: цл64) -> цл64 {
+    повернути поділити(а, 0)?
+}
+
+функція головна() {
+    число(1)
+}
+"#);
+    assert!(err.contains("Оператор '?' у функції 'число'"), "{}", err);
+
+    let err = run_err(r#"
+функція головна() {
+    змінна х = Помилка("збій")?
+}
+"#);
+    assert!(err.contains("збій"), "{}", err);
+}
+
+#[test]
+fn test_result_option_builtins() {
+    run(r#"
+функція знайти(масив: [цл64], ціль: цл64) -> Опція<цл64> {
+    для (і в 0..масив.довжина()) {
+        якщо (масив[і] == ціль) { повернути щось(і) }
+    }
+    повернути ніщо
+}
+
+функція головна() {
+    змінна добре = успіх(42)
+    змінна погано = помилка("збій")
+    перевірити_рівне(є_помилкою(добре), хиба)
+    перевірити_рівне(є_помилкою(погано), істина)
+    перевірити_рівне(розгорнути(добре), 42)
+
+    перевірити_рівне(розгорнути(знайти([3, 5, 7], 7)), 2)
+    перевірити_рівне(є_помилкою(знайти([3, 5, 7], 4)), істина)
+
+    зіставити погано {
+        Помилка(п) => перевірити_рівне(п, "збій"),
+        _ => паніка("очікувалась помилка")
+    }
+}
+"#);
+
+    let err = run_err(r#"
+функція головна() {
+    розгорнути(помилка("файл не знайдено"))
+}
+"#);
+    assert!(err.contains("Паніка: розгорнути() на помилці: файл не знайдено"), "{}", err);
+
+    let err = run_err(r#"
+функція головна() {
+    розгорнути(ніщо)
+}
+"#);
+    assert!(err.contains("на ніщо"), "{}", err);
+}
+
+#[test]
+fn test_generic_functions_and_structs() {
+    run(r#"
+структура Пара<К, З> {
+    ключ: К,
+    значення: З
+}
+
+функція перший<Т>(масив: Т[]) -> Т {
+    повернути масив[0]
+}
+
+функція поміняти<А, Б>(п: Пара<А, Б>) -> Пара<Б, А> {
+    повернути Пара { ключ: п.значення, значення: п.ключ }
+}
+
+функція головна() {
+    перевірити_рівне(перший([3, 1, 2]), 3)
+    перевірити_рівне(перший(["а", "б"]), "а")
+
+    змінна п = поміняти(Пара { ключ: "вік", значення: 30 })
+    перевірити_рівне(п.ключ, 30)
+    перевірити_рівне(п.значення, "вік")
+}
+"#);
+}
+
+#[test]
+fn test_sort_builtin() {
+    run(r#"
+функція головна() {
+    змінна числа = [5, 3, 9, 1]
+    перевірити_рівне(сортувати(числа), [1, 3, 5, 9])
+    перевірити_рівне(числа, [5, 3, 9, 1])
+    перевірити_рівне(сортувати([2.5, 1, 3]), [1, 2.5, 3])
+    перевірити_рівне(сортувати(["в", "а", "б"]), ["а", "б", "в"])
+
+    перевірити_рівне(сортувати(числа, |а, б| б - а), [9, 5, 3, 1])
+    перевірити_рівне(числа.сортувати(|а, б| б - а), [9, 5, 3, 1])
+
+    змінна слова = ["бб", "а", "вв", "г"]
+    перевірити_рівне(сортувати(слова, |а, б| довжина(а) - довжина(б)), ["а", "г", "бб", "вв"])
+}
+"#);
+
+    let err = run_err(r#"
+функція головна() {
+    сортувати([1, "два", 3])
+}
+"#);
+    assert!(err.contains("Помилка типу: сортувати не може порівняти"), "{}", err);
+}
+
+#[test]
+fn test_nested_display() {
+    run(r#"
+структура Точка {
+    х: цл64,
+    у: цл64,
+    мітка: тхт
+}
+
+структура Шлях {
+    назва: тхт,
+    точки: Точка[]
+}
+
+функція головна() {
+    перевірити_рівне("" + ["5", 5], "[\"5\", 5]")
+
+    змінна точки = [Точка { х: 1, у: 2, мітка: "а" }, Точка { х: 3, у: 4, мітка: "б" }]
+    перевірити_рівне("" + точки, "[Точка { мітка: \"а\", у: 2, х: 1 }, Точка { мітка: \"б\", у: 4, х: 3 }]")
+
+    змінна шлях = Шлях { назва: "маршрут", точки: [Точка { х: 0, у: 0, мітка: "старт" }] }
+    перевірити_рівне("" + шлях, "Шлях { назва: \"маршрут\", точки: [Точка { мітка: \"старт\", у: 0, х: 0 }] }")
+    перевірити_рівне("" + шлях, "" + шлях)
+}
+"#);
+}
+
+#[test]
+fn test_watch_mode_reruns_on_change() {
+    use std::io::{BufRead, BufReader};
+    use std::process::{Command, Stdio};
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    let dir = std::env::temp_dir().join(format!("tryzub_watch_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let file = dir.join("головна.тризуб");
+    std::fs::write(&file, "друк(\"перший\")\n").unwrap();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_tryzub"))
+        .arg("запустити")
+        .arg("--стежити")
+        .arg(&file)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("не вдалося запустити tryzub");
+
+    let (tx, rx) = mpsc::channel();
+    let stdout = child.stdout.take().unwrap();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines().map_while(|l| l.ok()) {
+            if tx.send(line).is_err() { break; }
+        }
+    });
+    let wait_for = |needle: &str| loop {
+        match rx.recv_timeout(Duration::from_secs(20)) {
+            Ok(line) if line.contains(needle) => break true,
+            Ok(_) => {}
+            Err(_) => break false,
+        }
+    };
+
+    let first = wait_for("перший") && wait_for("Чекаю на зміни");
+
+    // Синтаксична помилка не повинна завершувати режим спостереження
+    std::fs::write(&file, "друк(\"зламаний\"\n").unwrap();
+    let survived_error = first && wait_for("Чекаю на зміни");
+
+    std::fs::write(&file, "друк(\"другий\")\n").unwrap();
+    let rerun = survived_error && wait_for("другий");
+
+    let _ = child.kill();
+    let _ = child.wait();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert!(first, "перший запуск не відбувся");
+    assert!(survived_error, "режим спостереження не пережив синтаксичну помилку");
+    assert!(rerun, "програма не перезапустилась після зміни файлу");
+}
+
+#[test]
+fn test_watch_mode_forwards_run_flags() {
+    use std::io::{BufRead, BufReader};
+    use std::process::{Command, Stdio};
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    let dir = std::env::temp_dir().join(format!("tryzub_watch_flags_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let file = dir.join("головна.тризуб");
+    std::fs::write(&file, "друк(\"привіт\")\n").unwrap();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_tryzub"))
+        .arg("запустити")
+        .arg("--стежити")
+        .arg("--профіль")
+        .arg(&file)
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("не вдалося запустити tryzub");
+
+    let (tx, rx) = mpsc::channel();
+    let stderr = child.stderr.take().unwrap();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stderr).lines().map_while(|l| l.ok()) {
+            if tx.send(line).is_err() { break; }
+        }
+    });
+    let profiled = loop {
+        match rx.recv_timeout(Duration::from_secs(20)) {
+            Ok(line) if line.contains("Покриття виконання") => break true,
+            Ok(_) => {}
+            Err(_) => break false,
+        }
+    };
+
+    let _ = child.kill();
+    let _ = child.wait();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert!(profiled, "перезапуск не отримав --профіль");
+}
+
+#[test]
+fn test_project_local_dependency() {
+    use std::process::Command;
+
+    let root = std::env::temp_dir().join(format!("tryzub_deps_{}", std::process::id()));
+    let app = root.join("застосунок");
+    let lib = root.join("ліба");
+    std::fs::create_dir_all(&app).unwrap();
+    std::fs::create_dir_all(&lib).unwrap();
+
+    std::fs::write(app.join("проект.toml"), r#"[проект]
+назва = "застосунок"
+
+[залежності]
+ліба = { шлях = "../ліба" }
+"#).unwrap();
+    std::fs::write(app.join("головна.тризуб"), r#"
+імпорт ліба
+
+функція головна() {
+    друк(ліба.привітання("світ"))
+}
+"#).unwrap();
+    std::fs::write(lib.join("ліба.тризуб"), r#"
+функція привітання(ім_я) {
+    повернути "Привіт, " + ім_я + " з бібліотеки"
+}
+"#).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_tryzub"))
+        .arg("запустити")
+        .arg(app.join("головна.тризуб"))
+        .output()
+        .expect("не вдалося запустити tryzub");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(stdout.contains("Привіт, світ з бібліотеки"), "{}", stdout);
+
+    // Збірка бачить модулі залежності: їхня зміна перезбирає імпортера
+    let build = || {
+        let output = Command::new(env!("CARGO_BIN_EXE_tryzub"))
+            .arg("зібрати")
+            .arg(&app)
+            .output()
+            .expect("не вдалося запустити tryzub");
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+    build();
+    std::fs::write(lib.join("ліба.тризуб"), "функція привітання(ім_я) {\n    повернути ім_я\n}\n").unwrap();
+    let rebuilt = build();
+    let _ = std::fs::remove_dir_all(&root);
+    assert!(rebuilt.contains("[зібрано] головна.тризуб"), "{}", rebuilt);
+}
+
+#[test]
+fn test_incremental_project_build() {
+    use std::process::Command;
+
+    let root = std::env::temp_dir().join(format!("tryzub_build_{}", std::process::id()));
+    std::fs::create_dir_all(root.join("src")).unwrap();
+    std::fs::write(root.join("проект.toml"), "[проект]\nназва = \"збірка\"\n\n[залежності]\n").unwrap();
+    std::fs::write(root.join("src/головна.тризуб"), "імпорт утиліти\n\nфункція головна() {\n    друк(утиліти.двічі(21))\n}\n").unwrap();
+    std::fs::write(root.join("src/утиліти.тризуб"), "функція двічі(х) {\n    повернути х * 2\n}\n").unwrap();
+    std::fs::write(root.join("src/окремо.тризуб"), "функція окремо() {}\n").unwrap();
+
+    let build = || {
+        let output = Command::new(env!("CARGO_BIN_EXE_tryzub"))
+            .arg("зібрати")
+            .arg(&root)
+            .output()
+            .expect("не вдалося запустити tryzub");
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+    let modified = |name: &str| std::fs::metadata(root.join("ціль/src").join(name)).unwrap().modified().unwrap();
+
+    let first = build();
+    assert!(first.contains("Зібрано: 3, без змін: 0"), "{}", first);
+    let before = ["головна.тризубс", "утиліти.тризубс", "окремо.тризубс"].map(modified);
+
+    // Друга збірка без змін нічого не перезаписує
+    std::thread::sleep(std::time::Duration::from_millis(20));
+    let second = build();
+    assert!(second.contains("Зібрано: 0, без змін: 3"), "{}", second);
+    assert_eq!(["головна.тризубс", "утиліти.тризубс", "окремо.тризубс"].map(modified), before);
+
+    // Зміна модуля перезбирає і його імпортера
+    std::fs::write(root.join("src/утиліти.тризуб"), "функція двічі(х) {\n    повернути х + х\n}\n").unwrap();
+    let third = build();
+    let _ = std::fs::remove_dir_all(&root);
+    assert!(third.contains("Зібрано: 2, без змін: 1"), "{}", third);
+    assert!(third.contains("[зібрано] src/головна.тризуб"), "{}", third);
+    assert!(!third.contains("[зібрано] src/окремо.тризуб"), "{}", third);
+}
+
+#[test]
+fn test_markdown_documentation() {
+    use std::process::Command;
+
+    let root = std::env::temp_dir().join(format!("tryzub_docs_{}", std::process::id()));
+    std::fs::create_dir_all(&root).unwrap();
+    std::fs::write(root.join("геометрія.тризуб"), r#"
+/// Відстань між двома точками
+публічний функція відстань(а: Точка, б: Точка) -> дрб64 {
+    повернути 0.0
+}
+
+/// Точка на площині
+публічний структура Точка { публічний х: дрб64, у: дрб64 }
+
+реалізація Точка {
+    функція довжина(себе) -> дрб64 { повернути 0.0 }
+}
+
+публічний інтерфейс Фігура {
+    функція площа(себе) -> дрб64
+}
+
+реалізація Фігура для Точка {
+    функція площа(себе) -> дрб64 { повернути 0.0 }
+}
+
+функція прихована() {}
+"#).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_tryzub"))
+        .arg("документація")
+        .arg(root.join("геометрія.тризуб"))
+        .arg("--формат").arg("md")
+        .arg("--вивід").arg(root.join("docs"))
+        .output()
+        .expect("не вдалося запустити tryzub");
+    let md = std::fs::read_to_string(root.join("docs").join("документація.md")).unwrap_or_default();
+    let _ = std::fs::remove_dir_all(&root);
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(md.contains("## Модуль `геометрія.тризуб`"), "{}", md);
+    assert!(md.contains("функція відстань(а: Точка, б: Точка) -> дрб64"), "{}", md);
+    assert!(md.contains("Відстань між двома точками"), "{}", md);
+    assert!(md.contains("- `х: дрб64`"), "{}", md);
+    assert!(!md.contains("у: дрб64"), "{}", md);
+    assert!(md.contains("- `.довжина() -> дрб64`"), "{}", md);
+    assert!(md.contains("- `.площа() -> дрб64`"), "{}", md);
+    assert!(md.contains("інтерфейс Фігура"), "{}", md);
+    assert!(!md.contains("прихована"), "{}", md);
+}
+
+#[test]
+fn test_integer_overflow_is_value_error() {
+    run(r#"
+функція головна() {
+    перевірити_рівне(найбільше_цл64 - 1 + 1, найбільше_цл64)
+    перевірити_рівне(найменше_цл64 + найбільше_цл64, -1)
+    перевірити_рівне(2 ** 62, 4611686018427387904)
+}
+"#);
+
+    let err = run_err(r#"
+функція головна() {
+    змінна х = найбільше_цл64 + 1
+}
+"#);
+    assert!(err.contains("Помилка значення: переповнення цл64"), "{}", err);
+
+    let err = run_err(r#"
+функція головна() {
+    змінна х = найбільше_цл64
+    х *= 2
+}
+"#);
+    assert!(err.contains("переповнення"), "{}", err);
+
+    let err = run_err(r#"
+функція головна() {
+    змінна х = 2 ** 64
+}
+"#);
+    assert!(err.contains("переповнення"), "{}", err);
+
+    let err = run_err(r#"
+функція головна() {
+    змінна х = абс(найменше_цл64)
+}
+"#);
+    assert!(err.contains("Помилка значення: переповнення цл64 у абс(-9223372036854775808)"), "{}", err);
+
+    let err = run_err(r#"
+функція головна() {
+    змінна х = (-9223372036854775807 - 1).абс()
+}
+"#);
+    assert!(err.contains("Помилка значення: переповнення цл64"), "{}", err);
+}
+
+#[test]
+fn test_optimized_loop_overflow() {
+    // Формули для циклів-накопичувачів не мають обходити перевірку переповнення
+    let err = run_err(r#"
+функція головна() {
+    змінна х = 1
+    для і від 0 до 100 {
+        х = х * 2
+    }
+}
+"#);
+    assert!(err.contains("Помилка значення: переповнення цл64"), "{}", err);
+
+    let err = run_err(r#"
+функція головна() {
+    змінна х = найбільше_цл64
+    для і від 0 до 10 {
+        х += і
+    }
+}
+"#);
+    assert!(err.contains("Помилка значення: переповнення цл64"), "{}", err);
+
+    run(r#"
+функція головна() {
+    змінна х = 0
+    для і від 0 до 1000000 {
+        х += і
+    }
+    перевірити_рівне(х, 499999500000)
+}
+"#);
+}
+
+#[test]
+fn test_safe_array_get() {
+    run(r#"
+функція головна() {
+    змінна м = [10, 20, 30]
+    перевірити_рівне(отримати(м, 1), щось(20))
+    перевірити_рівне(отримати(м, -1), щось(30))
+    перевірити_рівне(отримати(м, 3), ніщо)
+    перевірити_рівне(отримати([], 0), ніщо)
+
+    зіставити отримати(м, 0) {
+        Деякий(х) => перевірити_рівне(х, 10),
+        _ => паніка("очікувалось щось")
+    }
+    перевірити_рівне(є_помилкою(отримати(м, 100)), істина)
+}
+"#);
+
+    let err = run_err(r#"
+функція головна() {
+    отримати([1, 2], "0")
+}
+"#);
+    assert!(err.contains("Помилка типу: отримати(масив, індекс)"), "{}", err);
+}
+
+#[test]
+fn test_format_specifiers() {
+    run(r#"
+функція головна() {
+    перевірити_рівне(форматувати("{:05}", 42), "00042")
+    перевірити_рівне(форматувати("{:05}", -42), "-0042")
+    перевірити_рівне(форматувати("{:.3}", 3.14159), "3.142")
+    перевірити_рівне(форматувати("{:.2}", 2), "2.00")
+    перевірити_рівне(форматувати("{:08.2}", 3.5), "00003.50")
+    перевірити_рівне(форматувати("{} + {} = {}", 1, 2, 3), "1 + 2 = 3")
+    перевірити_рівне(форматувати("[{:>4}|{:<4}|{:^4}]", 7, "аб", "в"), "[   7|аб  | в  ]")
+    перевірити_рівне(форматувати("{{}} {}", "x"), "{} x")
+    друкф("{:02}:{:02}", 9, 5)
+}
+"#);
+
+    let err = run_err(r#"
+функція головна() {
+    друкф("{:x5}", 1)
+}
+"#);
+    assert!(err.contains("Помилка значення: некоректний специфікатор формату '{:x5}'"), "{}", err);
+
+    let err = run_err(r#"
+функція головна() {
+    друкф("{} {}", 1)
+}
+"#);
+    assert!(err.contains("Помилка значення: шаблон"), "{}", err);
+}
+
+#[test]
+fn test_repeat_builtins() {
+    run(r#"
+функція головна() {
+    перевірити_рівне(повторити("ab", 3), "ababab")
+    перевірити_рівне(повторити("ab", 0), "")
+    перевірити_рівне(повторити_масив([0], 3), [0, 0, 0])
+    перевірити_рівне(повторити_масив([1, 2], 2), [1, 2, 1, 2])
+    перевірити_рівне(повторити_масив([1, 2], 0), [])
+}
+"#);
+
+    let err = run_err(r#"
+функція головна() {
+    повторити("ab", -1)
+}
+"#);
+    assert!(err.contains("Помилка значення: повторити очікує невід'ємну кількість"), "{}", err);
+
+    let err = run_err(r#"
+функція головна() {
+    повторити_масив([1], -2)
+}
+"#);
+    assert!(err.contains("Помилка значення: повторити_масив"), "{}", err);
+}
+
+#[test]
+fn test_ternary_operator() {
+    run(r#"
+функція знак(х) {
+    повернути х > 0 ? 1 : х < 0 ? -1 : 0
+}
+
+функція головна() {
+    перевірити_рівне(знак(5), 1)
+    перевірити_рівне(знак(-3), -1)
+    перевірити_рівне(знак(0), 0)
+    змінна мітка = істина || хиба ? "так" : "ні"
+    перевірити_рівне(мітка, "так")
+}
+"#);
+}
+
+#[test]
+fn test_multi_assign() {
+    run(r#"
+функція мін_макс(м) {
+    змінна с = сортувати(м)
+    повернути [с[0], с[-1]]
+}
+
+функція головна() {
+    змінна а = 1
+    змінна б = 2
+    а, б = б, а
+    перевірити_рівне(а, 2)
+    перевірити_рівне(б, 1)
+
+    змінна низ = 0
+    змінна верх = 0
+    низ, верх = мін_макс([4, 9, 1, 7])
+    перевірити_рівне(низ, 1)
+    перевірити_рівне(верх, 9)
+
+    змінна м = [0, 0]
+    м[0], м[1] = 5, 6
+    перевірити_рівне(м, [5, 6])
+}
+"#);
+
+    let err = run_err(r#"
+функція головна() {
+    змінна а = 1
+    змінна б = 2
+    а, б = [1, 2, 3]
+}
+"#);
+    assert!(err.contains("Множинне присвоєння: 2 цілей, але 3 значень"), "{}", err);
+}
+
+#[test]
+fn test_return_multiple_values() {
+    run(r#"
+функція ділення_з_остачею(а, б) {
+    повернути а / б, а % б
+}
+
+функція одне() {
+    повернути 7
+}
+
+функція головна() {
+    перевірити_рівне(ділення_з_остачею(17, 5), [3, 2])
+
+    змінна частка = 0
+    змінна остача = 0
+    частка, остача = ділення_з_остачею(23, 4)
+    перевірити_рівне(частка, 5)
+    перевірити_рівне(остача, 3)
+
+    перевірити_рівне(одне(), 7)
+}
+"#);
+}
+
+#[test]
+fn test_mixed_int_float_arithmetic() {
+    run(r#"
+функція головна() {
+    перевірити_рівне(2 + 3.0, 5.0)
+    перевірити_рівне(5 / 2.0, 2.5)
+    перевірити_рівне(7.5 - 2, 5.5)
+    перевірити_рівне(7.5 % 2, 1.5)
+    перевірити_рівне(2.0 ** 3, 8.0)
+    перевірити_рівне(тип_значення(4 * 0.5), тип_значення(2.0))
+
+    перевірити_рівне(3 < 3.5, істина)
+    перевірити_рівне(3.5 <= 3, хиба)
+    перевірити_рівне(4 > 3.9, істина)
+    перевірити_рівне(3 == 3.0, істина)
+    перевірити_рівне(3 != 3.5, істина)
+}
+"#);
+}
+
+#[test]
+fn test_conditional_compilation_attribute() {
+    run(r#"
+@якщо(неіснуюча_ос)
+функція версія() -> рядок { повернути "там" }
+
+@якщо(не неіснуюча_ос)
+функція версія() -> рядок { повернути "тут" }
+
+функція головна() {
+    перевірити_рівне(версія(), "тут")
+}
+"#);
+
+    let err = run_err(r#"
+@якщо(неіснуюча_ос)
+функція лише_там() {}
+
+функція головна() {
+    лише_там()
+}
+"#);
+    assert!(err.contains("лише_там"), "{}", err);
+}
+
+#[test]
+fn test_integer_division_by_zero_and_power() {
+    run(r#"
+функція головна() {
+    перевірити_рівне(2 ** -1, 0.5)
+    перевірити_рівне((-1) ** 5000000001, -1)
+    перевірити_рівне(1 ** 5000000000, 1)
+}
+"#);
+
+    let err = run_err(r#"
+функція головна() {
+    змінна х = 5 % 0
+}
+"#);
+    assert!(err.contains("Ділення на нуль"), "{}", err);
+
+    let err = run_err(r#"
+функція головна() {
+    змінна х = 0 ** -1
+}
+"#);
+    assert!(err.contains("Ділення на нуль"), "{}", err);
+
+    let err = run_err(r#"
+функція головна() {
+    змінна х = 3 ** 5000000000
+}
+"#);
+    assert!(err.contains("Помилка значення: переповнення цл64 у 3 ** 5000000000"), "{}", err);
+}
+
+#[test]
+fn test_run_from_stdin() {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_tryzub"))
+        .arg("запустити")
+        .arg("-")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("не вдалося запустити tryzub");
+    child.stdin.take().unwrap()
+        .write_all("функція головна() {\n    друк(\"Привіт з конвеєра, \" + 2 * 21)\n}\n".as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "Привіт з конвеєра, 42");
+}
+
+#[test]
+fn test_runtime_directives() {
+    let err = run_err(r#"
+//@ліміт_памʼяті 64КБ
+функція головна() {
+    стала великий = повторити("а", 100000)
+    змінна сума = 0
+    для і від 0 до 100000 {
+        якщо і % 2 == 0 { сума += і }
+    }
+}
+"#);
+    assert!(err.contains("Перевищено ліміт пам'яті"), "{}", err);
+
+    // Рядок живе в кадрі викликача, поки виконується викликана функція
+    let err = run_err(r#"
+//@ліміт_памʼяті 64КБ
+функція рахувати() -> цл64 {
+    змінна сума = 0
+    для і від 0 до 100000 {
+        якщо і % 2 == 0 { сума += і }
+    }
+    повернути сума
+}
+
+функція головна() {
+    стала великий = повторити("а", 100000)
+    рахувати()
+}
+"#);
+    assert!(err.contains("Перевищено ліміт пам'яті"), "{}", err);
+
+    let err = run_err(r#"
+//@таймаут 50мс
+функція головна() {
+    поки істина {}
+}
+"#);
+    assert!(err.contains("перевищено час виконання"), "{}", err);
+
+    // Невідома директива — лише попередження
+    run(r#"
+//@невідома 1
+//@таймаут 10с
+функція головна() {
+    друк("ок")
+}
+"#);
+
+    let err = run_err(r#"
+//@таймаут п'ять
+функція головна() {}
+"#);
+    assert!(err.contains("некоректне значення директиви //@таймаут"), "{}", err);
+}
+
+#[test]
+fn test_mutually_assigned_structs_print() {
+    // Присвоєння копіює значення, тож `а.друг = б; б.друг = а` дає скінченні знімки
+    run(r#"
+структура Вузол { імя: тхт, друг: Вузол }
+
+функція головна() {
+    змінна а = Вузол { імя: "а", друг: нуль }
+    змінна б = Вузол { імя: "б", друг: а }
+    а.друг = б
+    б.друг = а
+    перевірити_рівне("" + а, "Вузол { друг: Вузол { друг: Вузол { друг: нуль, імя: \"а\" }, імя: \"б\" }, імя: \"а\" }")
+    друк(б)
+}
+"#);
+}
+
+#[test]
+fn test_block_expression_early_exit() {
+    run(r#"
+функція знайти(числа: масив, ціль: цл64) -> цл64 {
+    стала індекс = {
+        для і від 0 до довжина(числа) {
+            якщо числа[і] == ціль { переривати і }
+        }
+        -1
+    }
+    повернути індекс
+}
+
+функція головна() {
+    перевірити_рівне(знайти([4, 8, 15], 8), 1)
+    перевірити_рівне(знайти([4, 8, 15], 16), -1)
+
+    // Виходить лише з найближчого блоку
+    стала ззовні = {
+        стала всередині = { переривати 10; 20 }
+        всередині + 1
+    }
+    перевірити_рівне(ззовні, 11)
+
+    // спробувати не перехоплює вихід з блоку
+    стала з_винятком = {
+        спробувати { переривати "вихід" } зловити е { друк("не має виконатись") }
+        "після"
+    }
+    перевірити_рівне(з_винятком, "вихід")
+}
+"#);
+
+    let err = run_err(r#"
+функція вийти() {
+    переривати 5
+}
+
+функція головна() {
+    стала х = { вийти(); 1 }
+}
+"#);
+    assert!(err.contains("'переривати' зі значенням можливий лише всередині блоку-виразу"), "{}", err);
+}
+
+#[test]
+fn test_vm_timeout() {
+    let source = r#"
+функція крок(н: цл64) -> цл64 { повернути н + 1 }
+
+функція головна() {
+    змінна н = 0
+    поки істина {
+        н = крок(н)
+    }
+}
+"#;
+    let program = parse(tokenize(source).unwrap()).unwrap();
+    let start = std::time::Instant::now();
+    let err = tryzub_vm::VM::new()
+        .with_timeout(std::time::Duration::from_millis(100))
+        .execute_program(program, vec![])
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("перевищено час виконання"), "{}", err);
+    assert!(start.elapsed() < std::time::Duration::from_secs(5));
+
+    // Директива не може послабити ліміт, заданий ззовні
+    let source = r#"
+//@таймаут 10хв
+функція головна() {
+    поки істина {}
+}
+"#;
+    let program = parse(tokenize(source).unwrap()).unwrap();
+    let err = tryzub_vm::VM::new()
+        .with_timeout(std::time::Duration::from_millis(100))
+        .execute_program(program, vec![])
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("перевищено час виконання"), "{}", err);
+}
+
+#[test]
+fn test_run_saved_ast() {
+    use std::process::Command;
+
+    let root = std::env::temp_dir().join(format!("tryzub_ast_{}", std::process::id()));
+    std::fs::create_dir_all(&root).unwrap();
+    let source = root.join("програма.тризуб");
+    std::fs::write(&source, "функція головна() {\n    друк(\"сума\", 40 + 2)\n}\n").unwrap();
+
+    let saved = Command::new(env!("CARGO_BIN_EXE_tryzub"))
+        .arg("розібрати").arg(&source).arg("--зберегти-аст")
+        .output()
+        .expect("не вдалося запустити tryzub");
+    let run = Command::new(env!("CARGO_BIN_EXE_tryzub"))
+        .arg("запустити").arg(root.join("програма.тризубс"))
+        .output()
+        .expect("не вдалося запустити tryzub");
+    let _ = std::fs::remove_dir_all(&root);
+
+    assert!(saved.status.success(), "{}", String::from_utf8_lossy(&saved.stderr));
+    assert!(run.status.success(), "{}", String::from_utf8_lossy(&run.stderr));
+    assert_eq!(String::from_utf8_lossy(&run.stdout).trim(), "сума 42");
+}
+
+#[test]
+fn test_ukrainian_case_conversion() {
+    run(r#"
+функція головна() {
+    стала малий = "абвгґдеєжзиіїйклмнопрстуфхцчшщьюя мʼята пам'ять"
+    стала великий = "АБВГҐДЕЄЖЗИІЇЙКЛМНОПРСТУФХЦЧШЩЬЮЯ МʼЯТА ПАМ'ЯТЬ"
+    перевірити_рівне(великі(малий), великий)
+    перевірити_рівне(малі(великий), малий)
+    перевірити_рівне(великі(малі(великий)), великий)
+    перевірити_рівне(малий.великими(), великий)
+
+    // Символи, що розкладаються на кілька, не змінюються
+    перевірити_рівне(великі("ß"), "ß")
+}
+"#);
+}
+
+#[test]
+fn test_trace_mode() {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let source = r#"функція подвоїти(х: цл64) -> цл64 {
+    повернути х * 2
+}
+
+функція головна() {
+    змінна а = подвоїти(5)
+    друк(а + 1)
+}
+"#;
+    let mut child = Command::new(env!("CARGO_BIN_EXE_tryzub"))
+        .arg("запустити")
+        .arg("-")
+        .arg("--трасувати")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("не вдалося запустити tryzub");
+    child.stdin.take().unwrap().write_all(source.as_bytes()).unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    let trace = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{}", trace);
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "11");
+    let lines: Vec<&str> = trace.lines().collect();
+    for expected in [
+        "[рядок 6] змінна а",
+        "  [рядок 2] повернути",
+        "    х * 2 = 10",
+        "  подвоїти(…) = 10",
+        "[рядок 7] друк(…)",
+        "  а + 1 = 11",
+    ] {
+        assert!(lines.contains(&expected), "немає рядка {:?} у трасуванні:\n{}", expected, trace);
+    }
+}
+
+#[test]
+fn test_tuple_values() {
+    run(r#"
+функція поділ(а: цл64, б: цл64) -> (цл64, цл64) {
+    повернути (а / б, а % б)
+}
+
+функція головна() {
+    стала т = (1, "текст", істина)
+    перевірити_рівне(т.0, 1)
+    перевірити_рівне(т.1, "текст")
+    перевірити_рівне(т.2, істина)
+    перевірити_рівне(довжина(т), 3)
+
+    // Дужки з одним виразом — групування, а не кортеж
+    перевірити_рівне((2 + 3) * 2, 10)
+
+    стала вкладений = ((1, 2), (3, (4, 5)))
+    перевірити_рівне(вкладений.0.1, 2)
+    перевірити_рівне(вкладений.1.1.0, 4)
+
+    стала частка = поділ(17, 5)
+    перевірити_рівне(частка.0, 3)
+    перевірити_рівне(частка.1, 2)
+}
+"#);
+    let err = run_err(r#"
+функція головна() {
+    стала т = (1, 2)
+    друк(т.2)
+}
+"#);
+    assert!(err.contains("поза межами"), "{}", err);
+}
+
+#[test]
+fn test_set_values() {
+    run(r#"
+функція головна() {
+    стала м = множина([1, 2, 2, 3, 1])
+    перевірити_рівне(м.довжина(), 3)
+    перевірити_рівне(м.в_масив(), [1, 2, 3])
+    перевірити_рівне(множина(3, 3, "а", "а").довжина(), 2)
+
+    перевірити(м.містить(2))
+    перевірити(!м.містить(5))
+    перевірити(3 в м)
+
+    стала більша = м.додати(4).додати(1)
+    перевірити_рівне(більша.довжина(), 4)
+
+    стала інша = множина([3, 4, 5])
+    перевірити_рівне(м.обʼєднання(інша), множина([1, 2, 3, 4, 5]))
+    перевірити_рівне(м.перетин(інша).в_масив(), [3])
+}
+"#);
+}
+
+#[test]
+fn test_nested_lvalue_assignment() {
+    run(r#"
+структура Точка { х: цл64, у: цл64 }
+структура Відрізок { початок: Точка, кінець: Точка }
+
+функція головна() {
+    змінна матриця = [[1, 2], [3, 4]]
+    матриця[1][0] = 30
+    матриця[0][-1] += 5
+    перевірити_рівне(матриця, [[1, 7], [30, 4]])
+
+    змінна відрізок = Відрізок { початок: Точка { х: 0, у: 0 }, кінець: Точка { х: 1, у: 1 } }
+    відрізок.кінець.у = 10
+    відрізок.початок.х -= 2
+    перевірити_рівне(відрізок.кінець.у, 10)
+    перевірити_рівне(відрізок.початок.х, -2)
+
+    змінна точки = [Точка { х: 1, у: 1 }]
+    точки[0].х = 9
+    перевірити_рівне(точки[0].х, 9)
+}
+"#);
+
+    let err = run_err(r#"
+функція головна() {
+    змінна матриця = [[1, 2], [3, 4]]
+    матриця[0][2] = 5
+}
+"#);
+    assert!(err.contains("поза межами"), "{}", err);
+}
+
+#[test]
+fn test_defer_runs_on_function_exit() {
+    run(r#"
+змінна журнал = ""
+
+функція записати(мітка: тхт) {
+    журнал += мітка
+}
+
+функція звичайна() -> цл64 {
+    відкласти { журнал += "1" }
+    відкласти записати("2")
+    журнал += "т"
+    повернути 5
+}
+
+функція рання(прапор: лог) -> цл64 {
+    відкласти { журнал += "в" }
+    якщо прапор {
+        стала локальна = "р"
+        відкласти { журнал += локальна }
+        повернути 1
+    }
+    журнал += "к"
+    повернути 2
+}
+
+функція збій() {
+    відкласти { журнал += "з" }
+    стала х = [1][5]
+}
+
+функція головна() {
+    перевірити_рівне(звичайна(), 5)
+    перевірити_рівне(журнал, "т21")
+
+    журнал = ""
+    перевірити_рівне(рання(істина), 1)
+    перевірити_рівне(журнал, "рв")
+    журнал = ""
+    перевірити_рівне(рання(хиба), 2)
+    перевірити_рівне(журнал, "кв")
+
+    журнал = ""
+    спробувати {
+        збій()
+    } зловити п {
+        журнал += "!"
+    }
+    перевірити_рівне(журнал, "з!")
+
+    // Кожна дія бачить значення змінної циклу на момент реєстрації
+    журнал = ""
+    цикли()
+    перевірити_рівне(журнал, "ба210")
+}
+
+функція цикли() {
+    для і від 0 до 3 {
+        відкласти записати(ф"{і}")
+    }
+    для ім в ["а", "б"] {
+        відкласти записати(ім)
+    }
+}
+"#);
+
+    let err = run_err(r#"
+функція головна() {
+    відкласти { стала х = 1 / 0 }
+    перевірити_рівне(1, 2)
+}
+"#);
+    assert!(err.contains("Перевірка рівності не пройшла: очікувалось '1', отримано '2'"), "помилка тіла має переважати: {}", err);
+}
+
+#[test]
+fn test_string_bytes_round_trip() {
+    run(r#"
+функція головна() {
+    стала байти = врядок_байти("Привіт!")
+    перевірити_рівне(довжина(байти), 13)
+    перевірити_рівне(байти[0], 208)
+    перевірити_рівне(байти[12], 33)
+    перевірити_рівне(збайтів_рядок(байти), "Привіт!")
+    перевірити_рівне(збайтів_рядок(врядок_байти("")), "")
+}
+"#);
+
+    let err = run_err(r#"
+функція головна() {
+    збайтів_рядок([208, 159, 255])
+}
+"#);
+    assert!(err.contains("Помилка значення") && err.contains("UTF-8"), "{}", err);
+    let err = run_err("функція головна() { збайтів_рядок([300]) }");
+    assert!(err.contains("не є байтом"), "{}", err);
+}
+
+#[test]
+fn test_structured_error_output() {
+    use std::process::Command;
+
+    let run_tryzub = |name: &str, source: &str| {
+        let path = std::env::temp_dir().join(format!("tryzub_report_{}_{}.тризуб", name, std::process::id()));
+        std::fs::write(&path, source).unwrap();
+        // stderr перенаправлено в канал, тож це не термінал і кольорів бути не повинно
+        let output = Command::new(env!("CARGO_BIN_EXE_tryzub"))
+            .arg("запустити")
+            .arg(&path)
+            .env_remove("NO_COLOR")
+            .output()
+            .expect("не вдалося запустити tryzub");
+        let _ = std::fs::remove_file(&path);
+        assert!(!output.status.success());
+        (path, String::from_utf8_lossy(&output.stderr).to_string())
+    };
+
+    let (path, stderr) = run_tryzub("syntax", "функція головна() {\n    змінна х = (1 + 2\n}\n");
+    assert!(!stderr.contains('\x1b'), "{:?}", stderr);
+    assert!(stderr.starts_with("помилка[синтаксис]: "), "{}", stderr);
+    assert!(stderr.contains(&format!("  --> {}:3:1\n", path.display())), "{}", stderr);
+    assert!(stderr.contains("2 |     змінна х = (1 + 2\n3 | }\n  | ^\n"), "{}", stderr);
+
+    let (path, stderr) = run_tryzub("runtime", "функція головна() {\n    друк(1 / 0)\n}\n");
+    assert!(!stderr.contains('\x1b'), "{:?}", stderr);
+    assert!(stderr.starts_with("помилка[виконання]: Ділення на нуль\n"), "{}", stderr);
+    assert!(stderr.contains(&format!("  --> {}\n", path.display())), "{}", stderr);
+}
+
+#[test]
+fn test_typed_declaration_assigned_later() {
+    run(r#"
+функція головна() {
+    змінна х: цл64
+    стала у = { х = 5; х + 1 }
+    перевірити_рівне(х, 5)
+    перевірити_рівне(у, 6)
+}
+"#);
+}
+
+#[test]
+fn test_coverage_lines_match_script_without_main() {
+    let dir = std::env::temp_dir().join(format!("tryzub_coverage_lines_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let file = dir.join("скрипт.тризуб");
+    std::fs::write(&file, "друк(1)\nдрук(2)\n").unwrap();
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_tryzub"))
+        .arg("запустити")
+        .arg("--профіль")
+        .arg(&file)
+        .output()
+        .expect("не вдалося запустити tryzub");
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let report = String::from_utf8_lossy(&output.stderr);
+    let lines: Vec<&str> = report.lines().filter(|l| l.trim_start().starts_with("рядок")).collect();
+    assert_eq!(lines.len(), 2, "{}", report);
+    assert!(lines[0].trim_start().starts_with("рядок 1 "), "{}", report);
+    assert!(lines[1].trim_start().starts_with("рядок 2 "), "{}", report);
+}
+
+#[test]
+fn test_test_runner_applies_conditional_compilation() {
+    let dir = std::env::temp_dir().join(format!("tryzub_test_cfg_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let file = dir.join("тести.тризуб");
+    std::fs::write(&file, r#"
+@якщо(неіснуюча_ос)
+функція версія() -> рядок { повернути "там" }
+
+@якщо(не неіснуюча_ос)
+функція версія() -> рядок { повернути "тут" }
+
+@якщо(не неіснуюча_ос)
+тест "версія тут" {
+    перевірити_рівне(версія(), "тут")
+}
+
+@якщо(неіснуюча_ос)
+тест "лише там" {
+    паніка("не мав запуститися")
+}
+"#).unwrap();
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_tryzub"))
+        .arg("тестувати")
+        .arg(&file)
+        .output()
+        .expect("не вдалося запустити tryzub");
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("[OK] версія тут"), "{}", stdout);
+    assert!(!stdout.contains("лише там"), "{}", stdout);
+}